@@ -39,6 +39,8 @@
 };
 use crate::unsupported::Unsupported;
 use crate::{BodyProperties, CdisBody, CdisPdu};
+use dis_rs::custom::registry::CustomPduRegistry;
+use dis_rs::errors::DisError;
 use dis_rs::model::{EntityId, Pdu, PduBody};
 use dis_rs::{BodyRaw, VariableParameters};
 use std::collections::HashMap;
@@ -201,6 +203,22 @@ pub fn decode(
         let pdu = Pdu::finalize_from_parts(header, body, timestamp);
         (pdu, state_result)
     }
+
+    /// Decodes the PDU like [`CdisPdu::decode`], and then parses the variable parameter records
+    /// with a record type registered in `registry` as `VariableParameter::Custom`.
+    ///
+    /// # Errors
+    /// Returns a `DisError` when a record with a registered type is not valid.
+    pub fn decode_with_registry(
+        &self,
+        state: &mut DecoderState,
+        options: &CodecOptions,
+        registry: &CustomPduRegistry,
+    ) -> Result<(Pdu, CodecStateResult), DisError> {
+        let (mut pdu, state_result) = self.decode(state, options);
+        registry.resolve_variable_parameters(&mut pdu.body)?;
+        Ok((pdu, state_result))
+    }
 }
 
 impl CdisBody {
@@ -497,6 +515,10 @@ pub fn encode(
                 Self::Unsupported(Unsupported),
                 CodecStateResult::StateUnaffected,
             ),
+            PduBody::Custom(_) => (
+                Self::Unsupported(Unsupported),
+                CodecStateResult::StateUnaffected,
+            ),
         }
     }
 
@@ -491,7 +491,13 @@ fn encode(item: &Self::Counterpart) -> Self {
             VariableParameter::EntityAssociation(vp) => {
                 CdisVariableParameter::EntityAssociation(CdisEntityAssociationVP::encode(vp))
             }
-            VariableParameter::Unspecified(_, _) => CdisVariableParameter::Unspecified,
+            VariableParameter::Unspecified(record_type, record) => {
+                CdisVariableParameter::Unspecified(*record_type, *record)
+            }
+            VariableParameter::Custom(record) => CdisVariableParameter::Unspecified(
+                record.record_type().into(),
+                record.serialize_record(),
+            ),
         }
     }
 
@@ -508,7 +514,9 @@ fn decode(&self) -> Self::Counterpart {
             CdisVariableParameter::EntityAssociation(vp) => {
                 VariableParameter::EntityAssociation(vp.decode())
             }
-            CdisVariableParameter::Unspecified => VariableParameter::Unspecified(0u8, [0u8; 15]),
+            CdisVariableParameter::Unspecified(record_type, record) => {
+                VariableParameter::Unspecified(*record_type, *record)
+            }
         }
     }
 }
@@ -1,8 +1,9 @@
 pub use super::timestamp::{CdisTimeUnits, CdisTimestamp};
 use crate::BitBuffer;
 use crate::constants::{
-    EIGHT_BITS, FIFTEEN_BITS, FIVE_BITS, FOUR_BITS, FOURTEEN_BITS, ONE_BIT, SEVENTEEN_BITS,
-    SIXTY_FOUR_BITS, THIRTY_NINE_BITS, THIRTY_TWO_BITS, THREE_BITS, TWENTY_SIX_BITS, TWO_BITS,
+    EIGHT_BITS, FIFTEEN_BITS, FIVE_BITS, FOUR_BITS, FOURTEEN_BITS, HUNDRED_TWENTY_BITS, ONE_BIT,
+    SEVENTEEN_BITS, SIXTY_FOUR_BITS, THIRTY_NINE_BITS, THIRTY_TWO_BITS, THREE_BITS,
+    TWENTY_SIX_BITS, TWO_BITS,
 };
 use crate::parsing::{BitInput, take_signed};
 use crate::records::model::CdisProtocolVersion::{Reserved, SISO_023_2023, StandardDis};
@@ -967,7 +968,9 @@ pub enum CdisVariableParameter {
     EntitySeparation(CdisEntitySeparationVP),
     EntityType(CdisEntityTypeVP),
     EntityAssociation(CdisEntityAssociationVP),
-    Unspecified,
+    /// A record type without a compressed format, such as user-defined record types.
+    /// Holds the record type and the record specific fields, and is always written uncompressed.
+    Unspecified(u8, [u8; 15]),
 }
 
 impl CdisRecord for CdisVariableParameter {
@@ -980,7 +983,8 @@ fn record_length(&self) -> usize {
                 CdisVariableParameter::EntitySeparation(vp) => vp.record_length(),
                 CdisVariableParameter::EntityType(vp) => vp.record_length(),
                 CdisVariableParameter::EntityAssociation(vp) => vp.record_length(),
-                CdisVariableParameter::Unspecified => 0,
+                // 8-bit record type instead of 3 bits, followed by the 15 record octets
+                CdisVariableParameter::Unspecified(_, _) => FIVE_BITS + HUNDRED_TWENTY_BITS,
             }
     }
 }
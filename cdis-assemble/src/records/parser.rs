@@ -28,7 +28,7 @@
     parse_pdu_status_fields,
 };
 use nom::bits::complete::take;
-use nom::multi::count;
+use nom::multi::{count, fill};
 use nom::{IResult, Parser};
 use num::Integer;
 
@@ -269,7 +269,18 @@ pub(crate) fn variable_parameter(input: BitInput) -> IResult<BitInput, CdisVaria
             let (input, vp) = entity_association_vp(input)?;
             (input, CdisVariableParameter::EntityAssociation(vp))
         }
-        (_, _) => (input, CdisVariableParameter::Unspecified),
+        (VariableParameterRecordType::Unspecified(record_type), false) => {
+            let mut record = [0u8; 15];
+            let (input, ()) = fill(take(EIGHT_BITS), &mut record).parse(input)?;
+            (
+                input,
+                CdisVariableParameter::Unspecified(record_type, record),
+            )
+        }
+        (_, _) => (
+            input,
+            CdisVariableParameter::Unspecified(record_type.into(), [0u8; 15]),
+        ),
     };
     Ok((input, variable_parameter))
 }
@@ -151,8 +151,14 @@ fn serialize(&self, buf: &mut BitBuffer, cursor: usize) -> usize {
 impl SerializeCdis for CdisVariableParameter {
     fn serialize(&self, buf: &mut BitBuffer, cursor: usize) -> usize {
         const COMPRESSED_FLAG_TRUE: u8 = 1; // FIXME currently only writes compressed Variable Parameters; where is it decided/configured that normal VPs should be processed?
+        const COMPRESSED_FLAG_FALSE: u8 = 0;
         const RECORD_TYPE_BIT_LENGTH: usize = 3;
-        let cursor = write_value_unsigned(buf, cursor, ONE_BIT, COMPRESSED_FLAG_TRUE);
+        let compressed_flag = if let CdisVariableParameter::Unspecified(_, _) = self {
+            COMPRESSED_FLAG_FALSE
+        } else {
+            COMPRESSED_FLAG_TRUE
+        };
+        let cursor = write_value_unsigned(buf, cursor, ONE_BIT, compressed_flag);
 
         match self {
             CdisVariableParameter::ArticulatedPart(vp) => {
@@ -200,7 +206,12 @@ fn serialize(&self, buf: &mut BitBuffer, cursor: usize) -> usize {
                 );
                 vp.serialize(buf, cursor)
             }
-            CdisVariableParameter::Unspecified => cursor,
+            CdisVariableParameter::Unspecified(record_type, record) => {
+                let cursor = write_value_unsigned(buf, cursor, EIGHT_BITS, *record_type);
+                record.iter().fold(cursor, |cursor, octet| {
+                    write_value_unsigned(buf, cursor, EIGHT_BITS, *octet)
+                })
+            }
         }
     }
 }
@@ -386,7 +397,9 @@ pub fn serialize_with_length(
 mod tests {
     use crate::records::model::{
         CdisEntityMarking, CdisHeader, CdisProtocolVersion, CdisRecord, CdisTimestamp,
+        CdisVariableParameter,
     };
+    use crate::records::parser::variable_parameter;
     use crate::types::model::UVINT8;
     use crate::writing::BitBuffer;
     use crate::writing::SerializeCdis;
@@ -446,4 +459,20 @@ fn serialize_cdis_header() {
         assert_eq!(next_cursor, header.record_length());
         assert_eq!(buf.data[..64][..8], expected);
     }
+
+    #[test]
+    fn serialize_unspecified_variable_parameter_uncompressed() {
+        let mut buf: BitBuffer = BitArray::ZERO;
+
+        let input = CdisVariableParameter::Unspecified(200, [0xAB; 15]);
+        let next_cursor = input.serialize(&mut buf, 0);
+
+        assert_eq!(next_cursor, input.record_length());
+        assert_eq!(next_cursor, 129);
+        // compressed flag 0, followed by the 8-bit record type
+        assert_eq!(buf.as_raw_slice()[..2], [0b0110_0100, 0b0101_0101]);
+
+        let (_input, parsed) = variable_parameter((buf.as_raw_slice(), 0)).unwrap();
+        assert_eq!(parsed, input);
+    }
 }
@@ -12,6 +12,7 @@
 use cdis_assemble::{BitBuffer, BodyProperties, CdisBody, CdisPdu, SerializeCdisPdu};
 use dis_rs::BodyRaw;
 use dis_rs::designator::model::Designator;
+use dis_rs::diff::{DiffOptions, Tolerance};
 use dis_rs::electromagnetic_emission::model::{
     Beam, ElectromagneticEmission, EmitterSystem, FundamentalParameterData, TrackJam,
 };
@@ -240,6 +241,60 @@ fn codec_consistency_entity_state_full_mode() {
     );
 }
 
+#[test]
+fn codec_fidelity_entity_state_full_mode() {
+    let mut encoder_state = EncoderState::new();
+    let codec_options = CodecOptions::new_full_update();
+    let mut decoder_state = DecoderState::new();
+
+    let dis_header =
+        PduHeader::new_v7(7, PduType::EntityState).with_pdu_status(PduStatus::default());
+    let dis_body = EntityState::builder()
+        .with_entity_id(EntityId::new(7, 127, 255))
+        .with_entity_type(
+            EntityType::default()
+                .with_domain(PlatformDomain::Air)
+                .with_country(Country::Netherlands_NLD_)
+                .with_kind(EntityKind::Platform),
+        )
+        .with_force_id(ForceId::Friendly8)
+        .with_location(Location::new(0.0, 0.0, 5_000_000.0))
+        .with_appearance(EntityAppearance::AirPlatform(
+            AirPlatformAppearance::default(),
+        ))
+        .with_marking(EntityMarking::new("TEST", EntityMarkingCharacterSet::ASCII))
+        .with_capabilities(EntityCapabilities::AirPlatformEntityCapabilities(
+            AirPlatformCapabilities::default(),
+        ))
+        .build()
+        .into_pdu_body();
+    let dis_pdu_in = Pdu::finalize_from_parts(dis_header, dis_body, dis_header.timestamp);
+
+    let (cdis_pdu, _state_result) =
+        CdisPdu::encode(&dis_pdu_in, &mut encoder_state, &codec_options);
+    let (dis_pdu_out, _state_result) = cdis_pdu.decode(&mut decoder_state, &codec_options);
+
+    let options =
+        DiffOptions::new().with_record_tolerance("Location", Tolerance::new_absolute(0.5));
+    let diffs = dis_pdu_in.diff(&dis_pdu_out, &options);
+    for path in [
+        "header",
+        "body.entity_id",
+        "body.entity_type",
+        "body.force_id",
+        "body.entity_appearance",
+        "body.entity_marking",
+        "body.entity_capabilities",
+        "body.entity_location.x_coordinate",
+        "body.entity_location.y_coordinate",
+    ] {
+        assert!(
+            diffs.iter().all(|diff| !diff.path.starts_with(path)),
+            "{path} differs: {diffs:?}"
+        );
+    }
+}
+
 #[test]
 fn codec_consistency_fire() {
     use dis_rs::fire::model::{Fire, FireDescriptor};
@@ -6,6 +6,13 @@
 use quick_xml::Reader;
 
 const SISO_REF_FILE: &str = "./enumerations/SISO-REF-010.xml";
+/// Environment variable to point the build script at an alternative SISO-REF-010 XML file,
+/// such as a newer release of the reference. Relative paths are resolved against the `dis-rs` package directory.
+const SISO_REF_FILE_ENV: &str = "DIS_SISO_REF_FILE";
+/// Environment variable to supply an overlay XML file, which adds or overrides enum entries
+/// (`enumrow`, `enumrow_range`) and bitfield entries (`bitfieldrow`) of the SISO-REF-010 file.
+/// The overlay uses the same structure as the SISO-REF-010 file.
+const SISO_REF_OVERLAY_ENV: &str = "DIS_SISO_REF_OVERLAY";
 
 /// Array containing all the uids of enumerations that should be generated.
 /// Each entry is a tuple containing:
@@ -299,11 +306,27 @@ pub struct BitfieldItem {
 }
 
 fn main() {
-    let mut reader = Reader::from_file(Path::new(SISO_REF_FILE)).unwrap();
-    reader.config_mut().trim_text(true);
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={SISO_REF_FILE_ENV}");
+    println!("cargo:rerun-if-env-changed={SISO_REF_OVERLAY_ENV}");
 
     // Extract enums and bitfields from the source file
-    let generation_items = extraction::extract(&mut reader);
+    let source_file = env::var(SISO_REF_FILE_ENV).unwrap_or_else(|_| SISO_REF_FILE.to_string());
+    let mut generation_items = extract_file(&source_file);
+
+    // Add or override entries from the overlay file, when provided
+    if let Ok(overlay_file) = env::var(SISO_REF_OVERLAY_ENV) {
+        let overlay_items = extract_file(&overlay_file);
+        overlay::apply(&mut generation_items, overlay_items);
+    }
+
+    // Ensure the generated names are unique
+    if let Err(errors) = validation::validate(&generation_items) {
+        panic!(
+            "The generated names of the SISO-REF-010 enumerations are not unique:\n{}",
+            errors.join("\n")
+        );
+    }
 
     // Generate all code for enums
     let generated = generation::generate(&generation_items);
@@ -318,6 +341,15 @@ fn main() {
     fs::write(dest_path, contents).unwrap();
 }
 
+fn extract_file(path: &str) -> Vec<GenerationItem> {
+    println!("cargo:rerun-if-changed={path}");
+    let mut reader = Reader::from_file(Path::new(path))
+        .unwrap_or_else(|err| panic!("Cannot read SISO-REF-010 file '{path}': {err}"));
+    reader.config_mut().trim_text(true);
+
+    extraction::extract(&mut reader)
+}
+
 fn format_name_postfix(value: &str, uid: usize, needs_postfix: bool) -> String {
     #[allow(clippy::collapsible_str_replace)]
     let intermediate: String = value
@@ -377,6 +409,17 @@ fn format_name(value: &str, uid: usize) -> String {
     format_name_postfix(value, uid, false)
 }
 
+/// Formats the name of the variant that is generated for `item`.
+fn format_variant_name(item: &EnumItem, postfix_items: bool) -> String {
+    match item {
+        EnumItem::Basic(item) => {
+            format_name_postfix(item.description.as_str(), item.value, postfix_items)
+        }
+        EnumItem::Range(item) => format_name(item.description.as_str(), *item.range.start()),
+        EnumItem::CrossRef(item) => format_name(item.description.as_str(), item.value),
+    }
+}
+
 fn format_field_name(name: &str) -> String {
     #[allow(clippy::collapsible_str_replace)]
     name.to_lowercase()
@@ -762,12 +805,152 @@ fn extract_bitfield_item(
     }
 }
 
+mod overlay {
+    use crate::{Bitfield, Enum, EnumItem, GenerationItem};
+
+    /// Applies the items of an overlay file to the items extracted from the SISO-REF-010 file.
+    ///
+    /// Enum entries override the entry with the same value (or the same start value for ranges),
+    /// and are added otherwise. Bitfield entries override the entry at the same bit position, and are added otherwise.
+    /// Enums and bitfields that are not present in the SISO-REF-010 file are added as a whole.
+    /// The name and size of existing enums and bitfields are not changed by the overlay.
+    pub fn apply(items: &mut Vec<GenerationItem>, overlay: Vec<GenerationItem>) {
+        for overlay_item in overlay {
+            match (
+                items
+                    .iter_mut()
+                    .find(|item| item.uid() == overlay_item.uid()),
+                overlay_item,
+            ) {
+                (Some(GenerationItem::Enum(base)), GenerationItem::Enum(overlay)) => {
+                    apply_enum(base, overlay);
+                }
+                (Some(GenerationItem::Bitfield(base)), GenerationItem::Bitfield(overlay)) => {
+                    apply_bitfield(base, overlay);
+                }
+                (Some(_), overlay) => panic!(
+                    "Overlay item with uid {} is not of the same kind (enum or bitfield) as in the SISO-REF-010 file",
+                    overlay.uid()
+                ),
+                (None, overlay) => items.push(overlay),
+            }
+        }
+    }
+
+    fn apply_enum(base: &mut Enum, overlay: Enum) {
+        for overlay_item in overlay.items {
+            if let Some(existing) = base
+                .items
+                .iter_mut()
+                .find(|item| item_value(item) == item_value(&overlay_item))
+            {
+                *existing = overlay_item;
+            } else {
+                base.items.push(overlay_item);
+            }
+        }
+    }
+
+    fn apply_bitfield(base: &mut Bitfield, overlay: Bitfield) {
+        for overlay_field in overlay.fields {
+            if let Some(existing) = base
+                .fields
+                .iter_mut()
+                .find(|field| field.bit_position == overlay_field.bit_position)
+            {
+                *existing = overlay_field;
+            } else {
+                base.fields.push(overlay_field);
+            }
+        }
+    }
+
+    fn item_value(item: &EnumItem) -> usize {
+        match item {
+            EnumItem::Basic(item) => item.value,
+            EnumItem::Range(item) => *item.range.start(),
+            EnumItem::CrossRef(item) => item.value,
+        }
+    }
+}
+
+mod validation {
+    use crate::{EnumItem, GenerationItem, format_field_name, format_name, format_variant_name};
+    use std::collections::HashMap;
+
+    /// Name of the catch-all variant that is generated for every enum.
+    const UNSPECIFIED_VARIANT: &str = "Unspecified";
+
+    /// Validates that the generated type names, enum variant names and bitfield field names are unique.
+    ///
+    /// Returns a list of all found conflicts.
+    pub fn validate(items: &[GenerationItem]) -> Result<(), Vec<String>> {
+        let mut errors = vec![];
+        let mut type_names = HashMap::new();
+
+        for item in items {
+            let type_name = format_name(item.name(), item.uid());
+            if let Some(other_uid) = type_names.insert(type_name.clone(), item.uid()) {
+                errors.push(format!(
+                    "Enumerations with uid {other_uid} and {} both generate type `{type_name}`",
+                    item.uid()
+                ));
+            }
+
+            match item {
+                GenerationItem::Enum(e) => {
+                    let mut variant_names = HashMap::new();
+                    variant_names.insert(UNSPECIFIED_VARIANT.to_string(), None);
+                    for enum_item in &e.items {
+                        let variant_name = format_variant_name(enum_item, e.postfix_items);
+                        let description = match enum_item {
+                            EnumItem::Basic(item) => item.description.as_str(),
+                            EnumItem::Range(item) => item.description.as_str(),
+                            EnumItem::CrossRef(item) => item.description.as_str(),
+                        };
+                        if let Some(other) =
+                            variant_names.insert(variant_name.clone(), Some(description))
+                        {
+                            errors.push(format!(
+                                "Enum `{type_name}` (uid {}): entries '{}' and '{description}' both generate variant `{variant_name}`",
+                                e.uid,
+                                other.unwrap_or(UNSPECIFIED_VARIANT),
+                            ));
+                        }
+                    }
+                }
+                GenerationItem::Bitfield(b) => {
+                    let mut field_names = HashMap::new();
+                    for field in &b.fields {
+                        let field_name = format_field_name(field.name.as_str());
+                        if let Some(other) =
+                            field_names.insert(field_name.clone(), field.name.as_str())
+                        {
+                            errors.push(format!(
+                                "Bitfield `{type_name}` (uid {}): entries '{other}' and '{}' both generate field `{field_name}`",
+                                b.uid, field.name
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
 mod generation {
     use crate::{
         Bitfield, BitfieldItem, Enum, EnumItem, GenerationItem, Ident, Literal, TokenStream,
-        format_field_name, format_name, format_name_postfix,
+        format_field_name, format_name, format_name_postfix, format_variant_name,
     };
     use quote::{format_ident, quote};
+    use std::collections::{BTreeMap, HashSet};
 
     pub fn generate(items: &Vec<GenerationItem>) -> TokenStream {
         let mut generated_items = vec![];
@@ -789,8 +972,10 @@ pub fn generate(items: &Vec<GenerationItem>) -> TokenStream {
             #[allow(clippy::match_single_binding)]
             #[allow(clippy::struct_excessive_bools)]
             #[allow(clippy::too_many_lines)]
+            #[allow(clippy::trivially_copy_pass_by_ref)]
             #[allow(clippy::uninlined_format_args)]
             #[allow(clippy::unreadable_literal)]
+            #[allow(clippy::unused_self)]
             #[allow(clippy::write_literal)]
             pub mod enumerations {
                 #[cfg(feature = "serde")]
@@ -812,10 +997,16 @@ fn generate_enum<'a, F>(item: &Enum, lookup_xref: F) -> TokenStream
         // generate From impls (2x)
         let from_impl = quote_enum_from_impl(item, &name_ident);
         let into_impl = quote_enum_into_impl(item, &name_ident);
+        // generate description() fn
+        let description_impl = quote_enum_description_impl(item, &name_ident);
         // generate Display impl
         let display_impl = quote_enum_display_impl(item, &name_ident);
+        // generate FromStr impl
+        let from_str_impl = quote_enum_from_str_impl(item, &name_ident);
         // generate Default impl
         let default_impl = quote_enum_default_impl(&name_ident);
+        // generate Arbitrary impl
+        let arbitrary_impl = quote_arbitrary_impl(&name_ident, item.size);
         quote!(
             #decl
 
@@ -823,10 +1014,15 @@ fn generate_enum<'a, F>(item: &Enum, lookup_xref: F) -> TokenStream
 
             #into_impl
 
+            #description_impl
+
             #display_impl
 
+            #from_str_impl
+
             #default_impl
 
+            #arbitrary_impl
         )
     }
 
@@ -1086,6 +1282,180 @@ fn quote_enum_display_arms(
         arms
     }
 
+    fn quote_enum_description_impl(e: &Enum, name_ident: &Ident) -> TokenStream {
+        let mut arms: Vec<TokenStream> = e
+            .items
+            .iter()
+            .map(|item| match item {
+                EnumItem::Basic(item) => {
+                    let item_description = item.description.as_str();
+                    let item_name =
+                        format_name_postfix(item_description, item.value, e.postfix_items);
+                    let item_ident = format_ident!("{}", item_name);
+                    quote!(
+                        #name_ident::#item_ident => #item_description
+                    )
+                }
+                EnumItem::Range(item) => {
+                    let item_description = item.description.as_str();
+                    let item_name = format_name(item_description, *item.range.start());
+                    let item_ident = format_ident!("{}", item_name);
+                    quote!(
+                        #name_ident::#item_ident(_) => #item_description
+                    )
+                }
+                EnumItem::CrossRef(item) => {
+                    let item_description = item.description.as_str();
+                    let item_name = format_name(item_description, item.value);
+                    let item_ident = format_ident!("{}", item_name);
+                    quote!(
+                        #name_ident::#item_ident(_) => #item_description
+                    )
+                }
+            })
+            .collect();
+        arms.push(quote!(
+            #name_ident::Unspecified(_) => "Unspecified"
+        ));
+        quote!(
+            impl #name_ident {
+                /// The description of the variant, as defined in SISO-REF-010.
+                #[must_use]
+                pub fn description(&self) -> &'static str {
+                    match self {
+                        #(#arms),*
+                    }
+                }
+            }
+        )
+    }
+
+    /// Counts the variants per description, for the descriptions that are not also the name of a variant.
+    fn description_counts(e: &Enum) -> BTreeMap<&str, usize> {
+        let variant_names: HashSet<String> = e
+            .items
+            .iter()
+            .map(|item| format_variant_name(item, e.postfix_items))
+            .collect();
+        let mut description_counts = BTreeMap::new();
+        for item in &e.items {
+            let description = match item {
+                EnumItem::Basic(item) => item.description.as_str(),
+                EnumItem::Range(item) => item.description.as_str(),
+                EnumItem::CrossRef(item) => item.description.as_str(),
+            };
+            if !variant_names.contains(description) {
+                *description_counts.entry(description).or_default() += 1;
+            }
+        }
+        description_counts
+    }
+
+    /// Generates a `FromStr` impl that accepts the name of a variant or its SISO-REF-010 description.
+    /// Variants holding a value (ranges and `Unspecified`) are also parsed from their `Display` output,
+    /// such as `"Unspecified (42)"`.
+    ///
+    /// Variant names take precedence over descriptions. Descriptions are not unique for all enums (such as Emitter Name);
+    /// a description shared by several variants is ambiguous and is rejected with an error.
+    fn quote_enum_from_str_impl(e: &Enum, name_ident: &Ident) -> TokenStream {
+        let name = name_ident.to_string();
+        let discriminant_ident = format_ident!("{}", size_to_type(e.size));
+        let description_counts = description_counts(e);
+        let ambiguous_descriptions: Vec<&str> = description_counts
+            .iter()
+            .filter(|(_, count)| **count > 1)
+            .map(|(description, _)| *description)
+            .collect();
+        let unique_patterns = |item_name: String, item_description: &str| -> Vec<String> {
+            let mut patterns = vec![item_name];
+            if description_counts.get(item_description) == Some(&1) {
+                patterns.push(item_description.to_string());
+            }
+            patterns
+        };
+        let arms: Vec<TokenStream> = e
+            .items
+            .iter()
+            .map(|item| {
+                let (patterns, constructor) = match item {
+                    EnumItem::Basic(item) => {
+                        let item_name = format_name_postfix(
+                            item.description.as_str(),
+                            item.value,
+                            e.postfix_items,
+                        );
+                        let item_ident = format_ident!("{}", item_name);
+                        (
+                            unique_patterns(item_name, item.description.as_str()),
+                            quote!(#name_ident::#item_ident),
+                        )
+                    }
+                    EnumItem::Range(item) => {
+                        let item_name = format_name(item.description.as_str(), *item.range.start());
+                        let item_ident = format_ident!("{}", item_name);
+                        let discriminant_literal =
+                            discriminant_literal(*item.range.start(), e.size);
+                        (
+                            unique_patterns(item_name, item.description.as_str()),
+                            quote!(#name_ident::#item_ident(#discriminant_literal)),
+                        )
+                    }
+                    EnumItem::CrossRef(item) => {
+                        let item_name = format_name(item.description.as_str(), item.value);
+                        let item_ident = format_ident!("{}", item_name);
+                        (
+                            unique_patterns(item_name, item.description.as_str()),
+                            quote!(#name_ident::#item_ident(Default::default())),
+                        )
+                    }
+                };
+                quote!(
+                    #(#patterns)|* => Ok(#constructor)
+                )
+            })
+            .collect();
+        let ambiguous_arm = if ambiguous_descriptions.is_empty() {
+            quote!()
+        } else {
+            quote!(
+                #(#ambiguous_descriptions)|* => Err(crate::DisError::ParseError(::alloc::format!(
+                    "'{}' is the description of several variants of {}, use the variant name", s, #name
+                ))),
+            )
+        };
+        quote!(
+            impl ::core::str::FromStr for #name_ident {
+                type Err = crate::DisError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    let s = s.trim();
+                    match s {
+                        #(#arms,)*
+                        #ambiguous_arm
+                        _ => {
+                            let parsed = s
+                                .strip_suffix(')')
+                                .and_then(|s| s.rsplit_once(" ("))
+                                .and_then(|(description, value)| {
+                                    value
+                                        .parse::<#discriminant_ident>()
+                                        .ok()
+                                        .map(#name_ident::from)
+                                        .filter(|parsed| parsed.description() == description)
+                                });
+                            if let Some(parsed) = parsed {
+                                return Ok(parsed);
+                            }
+                            Err(crate::DisError::ParseError(::alloc::format!(
+                                "'{}' is not a valid {}", s, #name
+                            )))
+                        }
+                    }
+                }
+            }
+        )
+    }
+
     fn quote_enum_default_impl(name_ident: &Ident) -> TokenStream {
         quote!(
             impl Default for #name_ident {
@@ -1096,6 +1466,20 @@ fn default() -> Self {
         )
     }
 
+    /// Generates an `Arbitrary` impl that goes through the `From` impl of the discriminant type,
+    /// so that the generated values are the same as those obtained from parsing.
+    fn quote_arbitrary_impl(name_ident: &Ident, data_size: usize) -> TokenStream {
+        let discriminant_ident = format_ident!("{}", size_to_type(data_size));
+        quote!(
+            #[cfg(feature = "arbitrary")]
+            impl<'a> arbitrary::Arbitrary<'a> for #name_ident {
+                fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                    Ok(Self::from(<#discriminant_ident as arbitrary::Arbitrary>::arbitrary(u)?))
+                }
+            }
+        )
+    }
+
     fn generate_bitfield<'a, F>(item: &Bitfield, lookup_xref: F) -> TokenStream
     where
         F: Fn(usize) -> Option<&'a GenerationItem>,
@@ -1103,7 +1487,13 @@ fn generate_bitfield<'a, F>(item: &Bitfield, lookup_xref: F) -> TokenStream
         let decl = quote_bitfield_decl(item, &lookup_xref);
         let from = quote_bitfield_from_impl(item, &lookup_xref); // struct from u32
         let into = quote_bitfield_into_impl(item, &lookup_xref); // struct into u32
+        let description = quote_bitfield_description_impl(item);
         let display = quote_bitfield_display_impl(item);
+        let from_str = quote_bitfield_from_str_impl(item);
+        let arbitrary = quote_arbitrary_impl(
+            &format_ident!("{}", format_name(item.name.as_str(), item.uid)),
+            item.size,
+        );
 
         quote!(
             #decl
@@ -1112,7 +1502,13 @@ fn generate_bitfield<'a, F>(item: &Bitfield, lookup_xref: F) -> TokenStream
 
             #into
 
+            #description
+
             #display
+
+            #from_str
+
+            #arbitrary
         )
     }
 
@@ -1280,14 +1676,123 @@ fn quote_bitfield_into_fields<'a, F>(
         }).collect()
     }
 
+    fn quote_bitfield_description_impl(item: &Bitfield) -> TokenStream {
+        let formatted_name = format_name(item.name.as_str(), item.uid);
+        let name_ident = format_ident!("{}", formatted_name);
+        let description = item.name.as_str();
+
+        quote!(
+            impl #name_ident {
+                /// The description of the bitfield, as defined in SISO-REF-010.
+                #[must_use]
+                pub fn description(&self) -> &'static str {
+                    #description
+                }
+            }
+        )
+    }
+
+    /// Generates a `Display` impl that lists the fields that are set (flags) or not default (enumerations),
+    /// separated by `" | "`. Flags are written as their description, enumerations as `"<description>: <value>"`.
+    /// A bitfield without any such fields (the default value) is written as `"None"`.
     fn quote_bitfield_display_impl(item: &Bitfield) -> TokenStream {
         let formatted_name = format_name(item.name.as_str(), item.uid);
         let name_ident = format_ident!("{}", formatted_name);
+        let fields: Vec<TokenStream> = item
+            .fields
+            .iter()
+            .map(|field| {
+                let field_ident = format_ident!("{}", format_field_name(field.name.as_str()));
+                let field_description = field.name.as_str();
+                if field.xref.is_some() {
+                    quote!(
+                        if self.#field_ident != Default::default() {
+                            write!(f, "{}{}: {}", separator, #field_description, self.#field_ident)?;
+                            separator = " | ";
+                        }
+                    )
+                } else {
+                    quote!(
+                        if self.#field_ident {
+                            write!(f, "{}{}", separator, #field_description)?;
+                            separator = " | ";
+                        }
+                    )
+                }
+            })
+            .collect();
 
         quote!(
             impl ::core::fmt::Display for #name_ident {
                 fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                    write!(f, #formatted_name)
+                    let mut separator = "";
+                    #(#fields)*
+                    if separator.is_empty() {
+                        write!(f, "None")?;
+                    }
+                    Ok(())
+                }
+            }
+        )
+    }
+
+    /// Generates a `FromStr` impl that parses the `Display` output of the bitfield, including `"None"` for the default value.
+    /// Fields are identified by either their field name or their description. Field names take precedence
+    /// over the description of another field.
+    fn quote_bitfield_from_str_impl(item: &Bitfield) -> TokenStream {
+        let formatted_name = format_name(item.name.as_str(), item.uid);
+        let name_ident = format_ident!("{}", formatted_name);
+        let field_names: HashSet<String> = item
+            .fields
+            .iter()
+            .map(|field| format_field_name(field.name.as_str()))
+            .collect();
+        let arms: Vec<TokenStream> = item
+            .fields
+            .iter()
+            .map(|field| {
+                let field_name = format_field_name(field.name.as_str());
+                let field_ident = format_ident!("{}", field_name);
+                let mut patterns = vec![field_name.clone()];
+                if !field_names.contains(&field.name) {
+                    patterns.push(field.name.clone());
+                }
+                if field.xref.is_some() {
+                    quote!(
+                        (#(#patterns)|*, Some(field_value)) => value.#field_ident = field_value.parse()?
+                    )
+                } else {
+                    quote!(
+                        (#(#patterns)|*, None) => value.#field_ident = true
+                    )
+                }
+            })
+            .collect();
+
+        quote!(
+            impl ::core::str::FromStr for #name_ident {
+                type Err = crate::DisError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    let mut value = Self::default();
+                    if s.trim() == "None" {
+                        return Ok(value);
+                    }
+                    for entry in s.split('|').map(str::trim).filter(|entry| !entry.is_empty()) {
+                        let (field, field_value) = match entry.split_once(':') {
+                            Some((field, field_value)) => (field.trim(), Some(field_value.trim())),
+                            None => (entry, None),
+                        };
+                        match (field, field_value) {
+                            #(#arms,)*
+                            _ => {
+                                return Err(crate::DisError::ParseError(::alloc::format!(
+                                    "'{}' is not a valid field of {}", entry, #formatted_name
+                                )));
+                            }
+                        }
+                    }
+                    Ok(value)
                 }
             }
         )
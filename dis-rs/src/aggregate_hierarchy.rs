@@ -0,0 +1,288 @@
+//! Reconstruction of the unit hierarchy of aggregates, groups and entities.
+//!
+//! The [`AggregateHierarchy`] is a tree (or forest) of `EntityId`s, built from the PDUs that describe composition:
+//! - an Aggregate State PDU lists the sub-aggregates and entities of an aggregate, and replaces its previous children;
+//! - an `IsGroupOf` PDU lists the members of a group, and replaces its previous children;
+//! - an `IsPartOf` PDU makes the receiving entity a part of the originating entity.
+//!
+//! Each node has at most one parent. Links that would make a node its own ancestor are ignored.
+use crate::common::aggregate_state::model::AggregateState;
+use crate::common::is_group_of::model::IsGroupOf;
+use crate::common::is_part_of::model::IsPartOf;
+use crate::common::model::{EntityId, PduBody};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    parent: Option<EntityId>,
+    children: BTreeSet<EntityId>,
+}
+
+/// Tree of aggregates, groups and entities, keyed by `EntityId`.
+///
+/// # Examples
+///
+/// ```
+/// # use dis_rs::aggregate_hierarchy::AggregateHierarchy;
+/// # use dis_rs::aggregate_state::model::AggregateState;
+/// # use dis_rs::model::EntityId;
+/// let company = EntityId::new(1, 1, 1);
+/// let platoon = EntityId::new(1, 1, 2);
+/// let tank = EntityId::new(1, 1, 3);
+///
+/// let mut hierarchy = AggregateHierarchy::default();
+/// hierarchy.aggregate_state(&AggregateState { aggregate_id: company, aggregates: vec![platoon], ..Default::default() });
+/// hierarchy.aggregate_state(&AggregateState { aggregate_id: platoon, entities: vec![tank], ..Default::default() });
+///
+/// assert_eq!(hierarchy.parent(&tank), Some(&platoon));
+/// assert_eq!(hierarchy.ancestors(&tank), vec![platoon, company]);
+/// assert_eq!(hierarchy.roots(), vec![company]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AggregateHierarchy {
+    nodes: BTreeMap<EntityId, Node>,
+    states: BTreeMap<EntityId, AggregateState>,
+}
+
+impl AggregateHierarchy {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Processes an incoming PDU body.
+    /// Aggregate State, `IsGroupOf` and `IsPartOf` bodies are used, other bodies are ignored.
+    pub fn handle(&mut self, body: &PduBody) {
+        match body {
+            PduBody::AggregateState(state) => self.aggregate_state(state),
+            PduBody::IsGroupOf(group) => self.is_group_of(group),
+            PduBody::IsPartOf(part) => self.is_part_of(part),
+            _ => {}
+        }
+    }
+
+    /// Replaces the children of the aggregate with the sub-aggregates and entities listed in `state`,
+    /// and keeps `state` as the latest state of the aggregate.
+    pub fn aggregate_state(&mut self, state: &AggregateState) {
+        let children = state
+            .aggregates
+            .iter()
+            .chain(state.entities.iter())
+            .copied();
+        self.set_children(state.aggregate_id, children);
+        self.states.insert(state.aggregate_id, state.clone());
+    }
+
+    /// Replaces the children of the group with the members listed in `group`.
+    pub fn is_group_of(&mut self, group: &IsGroupOf) {
+        let members: Vec<EntityId> = group
+            .descriptions
+            .iter()
+            .filter_map(|description| {
+                description.entity_id().map(|entity_number| {
+                    EntityId::new_sim_address(group.group_id.simulation_address, entity_number)
+                })
+            })
+            .collect();
+        self.set_children(group.group_id, members);
+    }
+
+    /// Makes the receiving entity of `part` a child of the originating entity.
+    pub fn is_part_of(&mut self, part: &IsPartOf) {
+        self.attach(part.originating_simulation_id, part.receiving_entity_id);
+    }
+
+    /// Makes `child` a child of `parent`, detaching it from its previous parent.
+    ///
+    /// Returns `false`, without changing the hierarchy, when `child` is `parent` or one of its ancestors.
+    pub fn attach(&mut self, parent: EntityId, child: EntityId) -> bool {
+        if parent == child || self.ancestors(&parent).contains(&child) {
+            return false;
+        }
+        self.detach(&child);
+        self.nodes.entry(parent).or_default().children.insert(child);
+        self.nodes.entry(child).or_default().parent = Some(parent);
+        true
+    }
+
+    /// Detaches `child` from its parent, making it a root.
+    pub fn detach(&mut self, child: &EntityId) {
+        let parent = self
+            .nodes
+            .get_mut(child)
+            .and_then(|node| node.parent.take());
+        if let Some(node) = parent.and_then(|parent| self.nodes.get_mut(&parent)) {
+            node.children.remove(child);
+        }
+    }
+
+    /// Removes `id` from the hierarchy. Its children become roots.
+    pub fn remove(&mut self, id: &EntityId) {
+        self.detach(id);
+        if let Some(node) = self.nodes.remove(id) {
+            for child in node.children {
+                if let Some(child) = self.nodes.get_mut(&child) {
+                    child.parent = None;
+                }
+            }
+        }
+        self.states.remove(id);
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: &EntityId) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    #[must_use]
+    pub fn parent(&self, id: &EntityId) -> Option<&EntityId> {
+        self.nodes.get(id).and_then(|node| node.parent.as_ref())
+    }
+
+    /// Returns the direct children of `id`.
+    pub fn children(&self, id: &EntityId) -> impl DoubleEndedIterator<Item = &EntityId> {
+        self.nodes
+            .get(id)
+            .into_iter()
+            .flat_map(|node| node.children.iter())
+    }
+
+    /// Returns the ancestors of `id`, starting with its parent.
+    #[must_use]
+    pub fn ancestors(&self, id: &EntityId) -> Vec<EntityId> {
+        let mut ancestors = Vec::new();
+        let mut current = self.parent(id);
+        while let Some(parent) = current {
+            ancestors.push(*parent);
+            current = self.parent(parent);
+        }
+        ancestors
+    }
+
+    /// Returns all descendants of `id`, depth first.
+    #[must_use]
+    pub fn descendants(&self, id: &EntityId) -> Vec<EntityId> {
+        let mut descendants = Vec::new();
+        let mut stack: Vec<&EntityId> = self.children(id).rev().collect();
+        while let Some(child) = stack.pop() {
+            descendants.push(*child);
+            stack.extend(self.children(child).rev());
+        }
+        descendants
+    }
+
+    /// Returns the nodes without a parent.
+    #[must_use]
+    pub fn roots(&self) -> Vec<EntityId> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.parent.is_none())
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Returns the latest Aggregate State of the aggregate `id`, if any.
+    #[must_use]
+    pub fn state(&self, id: &EntityId) -> Option<&AggregateState> {
+        self.states.get(id)
+    }
+
+    fn set_children(&mut self, parent: EntityId, children: impl IntoIterator<Item = EntityId>) {
+        let previous = self
+            .nodes
+            .get(&parent)
+            .map(|node| node.children.clone())
+            .unwrap_or_default();
+        for child in &previous {
+            self.detach(child);
+        }
+        self.nodes.entry(parent).or_default();
+        for child in children {
+            self.attach(parent, child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::is_group_of::model::{GEDRecord3, GroupEntityDescription};
+    use crate::common::is_part_of::model::IsPartOf;
+    use alloc::vec;
+
+    fn id(entity_id: u16) -> EntityId {
+        EntityId::new(1, 1, entity_id)
+    }
+
+    fn aggregate(
+        aggregate_id: EntityId,
+        aggregates: Vec<EntityId>,
+        entities: Vec<EntityId>,
+    ) -> PduBody {
+        PduBody::AggregateState(AggregateState {
+            aggregate_id,
+            aggregates,
+            entities,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn hierarchy_from_aggregate_states() {
+        let mut hierarchy = AggregateHierarchy::new();
+        hierarchy.handle(&aggregate(id(1), vec![id(2), id(3)], vec![]));
+        hierarchy.handle(&aggregate(id(2), vec![], vec![id(10), id(11)]));
+        hierarchy.handle(&aggregate(id(3), vec![], vec![id(12)]));
+
+        assert_eq!(hierarchy.roots(), vec![id(1)]);
+        assert_eq!(
+            hierarchy.descendants(&id(1)),
+            vec![id(2), id(10), id(11), id(3), id(12)]
+        );
+        assert_eq!(hierarchy.ancestors(&id(12)), vec![id(3), id(1)]);
+        assert!(hierarchy.state(&id(2)).is_some());
+
+        // an entity moves to another platoon
+        hierarchy.handle(&aggregate(id(2), vec![], vec![id(10)]));
+        hierarchy.handle(&aggregate(id(3), vec![], vec![id(11), id(12)]));
+        assert_eq!(hierarchy.parent(&id(11)), Some(&id(3)));
+        assert_eq!(hierarchy.children(&id(2)).count(), 1);
+    }
+
+    #[test]
+    fn groups_and_parts() {
+        let mut hierarchy = AggregateHierarchy::new();
+        hierarchy.handle(&PduBody::IsGroupOf(IsGroupOf {
+            group_id: id(20),
+            descriptions: vec![GroupEntityDescription::BasicGroundCombatSoldier(
+                GEDRecord3 {
+                    entity_id: 21,
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        }));
+        hierarchy.handle(&PduBody::IsPartOf(IsPartOf {
+            originating_simulation_id: id(21),
+            receiving_entity_id: id(22),
+            ..Default::default()
+        }));
+
+        assert_eq!(hierarchy.ancestors(&id(22)), vec![id(21), id(20)]);
+
+        hierarchy.remove(&id(21));
+        assert_eq!(hierarchy.parent(&id(22)), None);
+        assert_eq!(hierarchy.children(&id(20)).count(), 0);
+    }
+
+    #[test]
+    fn cycles_are_ignored() {
+        let mut hierarchy = AggregateHierarchy::new();
+        assert!(hierarchy.attach(id(1), id(2)));
+        assert!(hierarchy.attach(id(2), id(3)));
+        assert!(!hierarchy.attach(id(3), id(1)));
+        assert!(!hierarchy.attach(id(1), id(1)));
+        assert_eq!(hierarchy.roots(), vec![id(1)]);
+    }
+}
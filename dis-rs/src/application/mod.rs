@@ -0,0 +1,391 @@
+//! High-level entry point for simulation applications that take part in a DIS exercise.
+//!
+//! A [`DisApplication`] owns a UDP socket, the [`SimulationAddress`] of the application and the exercise it takes part in.
+//! Outgoing PDUs get a header stamped with the exercise, protocol version and a timestamp.
+//! Incoming traffic is available as a [`Stream`] of PDUs, filtered to the exercise of the application.
+//!
+//! Requires the `tokio` feature.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), dis_rs::application::ApplicationError> {
+//! use dis_rs::application::{DisApplication, UdpEndpoint};
+//! use dis_rs::model::SimulationAddress;
+//!
+//! let endpoint = UdpEndpoint::new_broadcast(
+//!     "0.0.0.0:3000".parse().unwrap(),
+//!     "192.168.1.255:3000".parse().unwrap(),
+//! );
+//! let mut application = DisApplication::new(&endpoint, SimulationAddress::new(1, 1), 1)?;
+//! let entity_id = application.next_entity_id();
+//! # Ok(())
+//! # }
+//! ```
+mod socket;
+
+pub use socket::{DEFAULT_TTL, SocketError, UdpEndpoint, UdpMode, create_udp_socket};
+
+use crate::BodyRaw;
+use crate::common::Interaction;
+use crate::common::custom::registry::CustomPduRegistry;
+use crate::common::model::{EntityId, EventId, Pdu, PduBody, PduHeader, SimulationAddress};
+use crate::common::timestamp::{TimeUnits, Timestamp};
+use crate::constants::MAXIMUM_PDU_SIZE_OCTETS;
+use crate::enumerations::ProtocolVersion;
+use crate::errors::DisError;
+use crate::fixed_parameters::{NO_ENTITY, RQST_ASSIGN_ID};
+use bytes::BytesMut;
+use core::pin::Pin;
+use core::task::{Context, Poll, ready};
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::time::Instant;
+use thiserror::Error;
+use tokio::io::ReadBuf;
+use tokio::net::UdpSocket;
+
+/// Highest entity number that is not reserved.
+const MAX_ENTITY_NUMBER: u16 = (RQST_ASSIGN_ID - 1) as u16;
+
+#[derive(Debug, Error)]
+pub enum ApplicationError {
+    #[error(transparent)]
+    Socket(#[from] SocketError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Dis(#[from] DisError),
+}
+
+/// The time reference used for the timestamps of outgoing PDUs.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TimestampMode {
+    /// Time elapsed since the creation of the `DisApplication`, for hosts with unsynchronized clocks.
+    #[default]
+    Relative,
+    /// Time elapsed since the start of the current hour (UTC) of the host clock, for hosts with synchronized clocks.
+    Absolute,
+}
+
+/// A simulation application taking part in a DIS exercise, sending and receiving PDUs over UDP.
+pub struct DisApplication {
+    socket: UdpSocket,
+    destination: SocketAddr,
+    local_address: Option<SocketAddr>,
+    simulation_address: SimulationAddress,
+    exercise_id: u8,
+    protocol_version: ProtocolVersion,
+    timestamp_mode: TimestampMode,
+    block_own_socket: bool,
+    custom_pdus: CustomPduRegistry,
+    last_entity_id: u16,
+    last_event_id: u16,
+    started: Instant,
+    send_buffer: BytesMut,
+    receive_buffer: Vec<u8>,
+    received: VecDeque<Pdu>,
+}
+
+impl DisApplication {
+    /// Creates a new `DisApplication` with a socket for `endpoint`, identified by `simulation_address`
+    /// and taking part in exercise `exercise_id`.
+    ///
+    /// By default, PDUs are sent using DIS version 7 with relative timestamps, and packets sent by the application itself are blocked.
+    ///
+    /// # Errors
+    /// Returns an `ApplicationError` when the socket cannot be created.
+    /// Must be called within the context of a tokio runtime.
+    pub fn new(
+        endpoint: &UdpEndpoint,
+        simulation_address: SimulationAddress,
+        exercise_id: u8,
+    ) -> Result<Self, ApplicationError> {
+        let socket = create_udp_socket(endpoint)?;
+        let local_address = socket.local_addr().ok();
+
+        Ok(Self {
+            socket,
+            destination: endpoint.address,
+            local_address,
+            simulation_address,
+            exercise_id,
+            protocol_version: ProtocolVersion::IEEE1278_12012,
+            timestamp_mode: TimestampMode::default(),
+            block_own_socket: true,
+            custom_pdus: CustomPduRegistry::default(),
+            last_entity_id: NO_ENTITY,
+            last_event_id: 0,
+            started: Instant::now(),
+            send_buffer: BytesMut::with_capacity(MAXIMUM_PDU_SIZE_OCTETS),
+            receive_buffer: vec![0; MAXIMUM_PDU_SIZE_OCTETS],
+            received: VecDeque::new(),
+        })
+    }
+
+    #[must_use]
+    pub fn with_protocol_version(mut self, protocol_version: ProtocolVersion) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    #[must_use]
+    pub fn with_timestamp_mode(mut self, timestamp_mode: TimestampMode) -> Self {
+        self.timestamp_mode = timestamp_mode;
+        self
+    }
+
+    /// Sets whether PDUs sent by the application itself are skipped when receiving,
+    /// recognised by the sender address or by the `SimulationAddress` of their originator.
+    #[must_use]
+    pub fn with_block_own_socket(mut self, block_own_socket: bool) -> Self {
+        self.block_own_socket = block_own_socket;
+        self
+    }
+
+    /// Sets the registry of user-defined PDU types, which are received as `PduBody::Custom` PDUs.
+    #[must_use]
+    pub fn with_custom_pdus(mut self, custom_pdus: CustomPduRegistry) -> Self {
+        self.custom_pdus = custom_pdus;
+        self
+    }
+
+    #[must_use]
+    pub fn simulation_address(&self) -> SimulationAddress {
+        self.simulation_address
+    }
+
+    #[must_use]
+    pub fn exercise_id(&self) -> u8 {
+        self.exercise_id
+    }
+
+    /// The local address the socket of the application is bound to, if any.
+    #[must_use]
+    pub fn local_address(&self) -> Option<SocketAddr> {
+        self.local_address
+    }
+
+    /// Returns the next unused `EntityId` for this application.
+    ///
+    /// Entity numbers start at 1 and wrap around, skipping the reserved values `NO_ENTITY`, `RQST_ASSIGN_ID` and `ALL_ENTITIES`.
+    pub fn next_entity_id(&mut self) -> EntityId {
+        self.last_entity_id = next_number(self.last_entity_id, MAX_ENTITY_NUMBER);
+        EntityId::new_sim_address(self.simulation_address, self.last_entity_id)
+    }
+
+    /// Returns the next `EventId` for this application.
+    ///
+    /// Event numbers start at 1 and wrap around, skipping 0.
+    pub fn next_event_id(&mut self) -> EventId {
+        self.last_event_id = next_number(self.last_event_id, u16::MAX);
+        EventId::new_sim_address(self.simulation_address, self.last_event_id)
+    }
+
+    /// Returns the current time as a `Timestamp` in the configured `TimestampMode`.
+    #[must_use]
+    pub fn timestamp(&self) -> Timestamp {
+        match self.timestamp_mode {
+            TimestampMode::Relative => {
+                Timestamp::Relative(TimeUnits::from_duration_past_hour(self.started.elapsed()))
+            }
+            TimestampMode::Absolute => Timestamp::now_absolute(),
+        }
+    }
+
+    /// Sends `body` to the destination address of the endpoint,
+    /// in a PDU with a header for the exercise, protocol version and current time of the application.
+    ///
+    /// # Errors
+    /// Returns an `ApplicationError` when the PDU cannot be serialized, or the socket fails to send it.
+    pub async fn send(&mut self, body: PduBody) -> Result<(), ApplicationError> {
+        let header = PduHeader::new(self.protocol_version, self.exercise_id, body.body_type());
+        let pdu = Pdu::finalize_from_parts(header, body, self.timestamp());
+
+        self.send_buffer.clear();
+        pdu.serialize(&mut self.send_buffer)?;
+        self.socket
+            .send_to(&self.send_buffer, self.destination)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// The `SimulationAddress` of the entity that originated `pdu`, if the PDU has an originator.
+fn originating_address(pdu: &Pdu) -> Option<SimulationAddress> {
+    pdu.body
+        .originator()
+        .map(|originator| originator.simulation_address)
+}
+
+/// Returns the number following `last`, wrapping to 1 after `max`.
+fn next_number(last: u16, max: u16) -> u16 {
+    if last >= max { 1 } else { last + 1 }
+}
+
+/// Received PDUs for the exercise of the application.
+///
+/// Packets that cannot be parsed and PDUs for other exercises are skipped.
+/// When blocking its own PDUs, packets sent from the socket of the application and PDUs originated by its `SimulationAddress` are skipped as well.
+/// The latter also covers broadcast and multicast sockets, which are bound to an unspecified address and thus
+/// cannot recognise their own packets by the sender address.
+/// The stream ends when the socket fails.
+impl Stream for DisApplication {
+    type Item = Pdu;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(pdu) = this.received.pop_front() {
+                return Poll::Ready(Some(pdu));
+            }
+
+            let mut buf = ReadBuf::new(&mut this.receive_buffer);
+            match ready!(this.socket.poll_recv_from(cx, &mut buf)) {
+                Ok(from_address) => {
+                    if this.block_own_socket && this.local_address == Some(from_address) {
+                        continue;
+                    }
+                    if let Ok(pdus) = crate::parse_with_registry(buf.filled(), &this.custom_pdus) {
+                        let exercise_id = this.exercise_id;
+                        let own_address = this.block_own_socket.then_some(this.simulation_address);
+                        this.received.extend(pdus.into_iter().filter(|pdu| {
+                            pdu.header.exercise_id == exercise_id
+                                && (own_address.is_none()
+                                    || originating_address(pdu) != own_address)
+                        }));
+                    }
+                }
+                // ICMP port unreachable responses to earlier sends surface as errors on some platforms
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        ErrorKind::ConnectionReset | ErrorKind::ConnectionRefused
+                    ) => {}
+                Err(_) => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::acknowledge::model::Acknowledge;
+    use core::future::poll_fn;
+
+    fn next_pdu(application: &mut DisApplication) -> impl Future<Output = Option<Pdu>> + '_ {
+        poll_fn(move |cx| Pin::new(&mut *application).poll_next(cx))
+    }
+
+    #[test]
+    fn entity_numbers_skip_reserved_values() {
+        assert_eq!(next_number(NO_ENTITY, MAX_ENTITY_NUMBER), 1);
+        assert_eq!(next_number(0xFFFC, MAX_ENTITY_NUMBER), 0xFFFD);
+        assert_eq!(next_number(0xFFFD, MAX_ENTITY_NUMBER), 1);
+        assert_eq!(next_number(0xFFFE, u16::MAX), 0xFFFF);
+        assert_eq!(next_number(0xFFFF, u16::MAX), 1);
+    }
+
+    #[tokio::test]
+    async fn send_and_receive_unicast() {
+        let address_a: SocketAddr = "127.0.0.1:30391".parse().unwrap();
+        let address_b: SocketAddr = "127.0.0.1:30392".parse().unwrap();
+        let mut application_a = DisApplication::new(
+            &UdpEndpoint::new_unicast(address_a, address_b),
+            SimulationAddress::new(1, 1),
+            7,
+        )
+        .unwrap();
+        let mut application_b = DisApplication::new(
+            &UdpEndpoint::new_unicast(address_b, address_a),
+            SimulationAddress::new(1, 2),
+            7,
+        )
+        .unwrap();
+        let mut other_exercise = DisApplication::new(
+            &UdpEndpoint::new_unicast("127.0.0.1:30393".parse().unwrap(), address_b),
+            SimulationAddress::new(1, 3),
+            8,
+        )
+        .unwrap();
+
+        let originator = application_a.next_entity_id();
+        assert_eq!(originator, EntityId::new(1, 1, 1));
+
+        other_exercise
+            .send(Acknowledge::builder().build().into_pdu_body())
+            .await
+            .unwrap();
+        application_a
+            .send(
+                Acknowledge::builder()
+                    .with_origination_id(originator)
+                    .with_request_id(42)
+                    .build()
+                    .into_pdu_body(),
+            )
+            .await
+            .unwrap();
+
+        let pdu = next_pdu(&mut application_b).await.unwrap();
+        assert_eq!(pdu.header.exercise_id, 7);
+        assert_eq!(pdu.header.protocol_version, ProtocolVersion::IEEE1278_12012);
+        assert!(pdu.header.timestamp.is_relative());
+        if let PduBody::Acknowledge(acknowledge) = pdu.body {
+            assert_eq!(acknowledge.originating_id, originator);
+            assert_eq!(acknowledge.request_id, 42);
+        } else {
+            panic!("expected an Acknowledge PDU");
+        }
+    }
+
+    #[tokio::test]
+    async fn block_own_pdus_on_broadcast() {
+        let interface: SocketAddr = "0.0.0.0:30394".parse().unwrap();
+        let broadcast: SocketAddr = "127.255.255.255:30394".parse().unwrap();
+        let mut application_a = DisApplication::new(
+            &UdpEndpoint::new_broadcast(interface, broadcast),
+            SimulationAddress::new(1, 1),
+            7,
+        )
+        .unwrap();
+        let mut application_b = DisApplication::new(
+            &UdpEndpoint::new_broadcast(interface, broadcast),
+            SimulationAddress::new(1, 2),
+            7,
+        )
+        .unwrap();
+
+        let own_id = application_a.next_entity_id();
+        application_a
+            .send(
+                Acknowledge::builder()
+                    .with_origination_id(own_id)
+                    .build()
+                    .into_pdu_body(),
+            )
+            .await
+            .unwrap();
+        let other_id = application_b.next_entity_id();
+        application_b
+            .send(
+                Acknowledge::builder()
+                    .with_origination_id(other_id)
+                    .build()
+                    .into_pdu_body(),
+            )
+            .await
+            .unwrap();
+
+        // Both applications send from the same address, so only the originator tells them apart.
+        let pdu = next_pdu(&mut application_a).await.unwrap();
+        if let PduBody::Acknowledge(acknowledge) = pdu.body {
+            assert_eq!(acknowledge.originating_id, other_id);
+        } else {
+            panic!("expected an Acknowledge PDU");
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Creation of UDP sockets for sending and receiving DIS traffic.
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use thiserror::Error;
+use tokio::net::UdpSocket;
+
+pub const DEFAULT_TTL: u32 = 1;
+
+/// The way in which a socket sends and receives traffic.
+#[allow(clippy::enum_variant_names)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UdpMode {
+    #[default]
+    UniCast,
+    BroadCast,
+    MultiCast,
+}
+
+/// Settings for creating a UDP socket.
+///
+/// `interface` is the local address the socket binds to, and `address` the (remote, broadcast or multicast group) address
+/// to which traffic is sent.
+/// Multicast sockets are bound to the port of the group on all interfaces, unless `bind_multicast` is disabled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UdpEndpoint {
+    pub mode: UdpMode,
+    pub interface: SocketAddr,
+    pub address: SocketAddr,
+    pub ttl: u32,
+    pub bind_multicast: bool,
+}
+
+impl UdpEndpoint {
+    #[must_use]
+    pub fn new(mode: UdpMode, interface: SocketAddr, address: SocketAddr) -> Self {
+        Self {
+            mode,
+            interface,
+            address,
+            ttl: DEFAULT_TTL,
+            bind_multicast: true,
+        }
+    }
+
+    #[must_use]
+    pub fn new_unicast(interface: SocketAddr, address: SocketAddr) -> Self {
+        Self::new(UdpMode::UniCast, interface, address)
+    }
+
+    #[must_use]
+    pub fn new_broadcast(interface: SocketAddr, address: SocketAddr) -> Self {
+        Self::new(UdpMode::BroadCast, interface, address)
+    }
+
+    #[must_use]
+    pub fn new_multicast(interface: SocketAddr, group: SocketAddr) -> Self {
+        Self::new(UdpMode::MultiCast, interface, group)
+    }
+
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: u32) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    #[must_use]
+    pub fn with_bind_multicast(mut self, bind_multicast: bool) -> Self {
+        self.bind_multicast = bind_multicast;
+        self
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SocketError {
+    #[error("Failed to create socket.")]
+    CreateSocket,
+    #[error("Failed to set SO_REUSEADDR for endpoint address {0}.")]
+    SetReuseAddress(SocketAddr),
+    #[error("Failed to set SO_REUSEPORT for endpoint address {0}.")]
+    SetReusePort(SocketAddr),
+    #[error("Failed to set non-blocking mode for endpoint address {0}.")]
+    SetNonblocking(SocketAddr),
+    #[error("Failed to set SO_BROADCAST for endpoint address {0}.")]
+    SetBroadcast(SocketAddr),
+    #[error("Failed to set TTL for endpoint address {0}.")]
+    SetTtl(SocketAddr),
+    #[error("Failed to join multicast group {0} using interface {1} (IPv4).")]
+    JoinMulticastV4(Ipv4Addr, Ipv4Addr),
+    #[error("Failed to join multicast group {0} using interface {1} (IPv6).")]
+    JoinMulticastV6(Ipv6Addr, Ipv6Addr),
+    #[error("Failed to bind to address {0:?}")]
+    BindToAddress(SocketAddr),
+    #[error("Failed to convert std::net::UdpSocket to tokio::net::UdpSocket.")]
+    ConvertToAsync,
+}
+
+/// Creates an UDP socket based on the settings contained in `endpoint`.
+///
+/// The socket allows address and port reuse, so that multiple applications on a host can use the same port.
+/// A multicast socket joins the group on the configured interface, and is bound to the port of the group on all interfaces
+/// when `bind_multicast` is set.
+///
+/// # Errors
+/// Returns a `SocketError` when the socket cannot be created, configured or bound.
+/// Must be called within the context of a tokio runtime.
+pub fn create_udp_socket(endpoint: &UdpEndpoint) -> Result<UdpSocket, SocketError> {
+    // Create socket using socket2 crate, to be able to set required socket options (SO_REUSEADDR, SO_REUSEPORT, ...)
+    let is_ipv4 = endpoint.address.is_ipv4();
+    let socket_domain = if is_ipv4 { Domain::IPV4 } else { Domain::IPV6 };
+    let socket = Socket::new(socket_domain, Type::DGRAM, Some(Protocol::UDP))
+        .map_err(|_| SocketError::CreateSocket)?;
+
+    socket
+        .set_reuse_address(true)
+        .map_err(|_| SocketError::SetReuseAddress(endpoint.address))?;
+    #[cfg(all(
+        target_family = "unix",
+        not(any(target_os = "solaris", target_os = "illumos"))
+    ))]
+    socket
+        .set_reuse_port(true)
+        .map_err(|_| SocketError::SetReusePort(endpoint.address))?;
+    socket
+        .set_nonblocking(true)
+        .map_err(|_| SocketError::SetNonblocking(endpoint.address))?;
+
+    let bind = |address: SocketAddr| {
+        socket
+            .bind(&address.into())
+            .map_err(|_| SocketError::BindToAddress(address))
+    };
+
+    match (endpoint.address.ip(), endpoint.mode) {
+        (_, UdpMode::UniCast) => bind(endpoint.interface)?,
+        (IpAddr::V4(_), UdpMode::BroadCast) => {
+            socket
+                .set_broadcast(true)
+                .map_err(|_| SocketError::SetBroadcast(endpoint.interface))?;
+            socket
+                .set_ttl_v4(endpoint.ttl)
+                .map_err(|_| SocketError::SetTtl(endpoint.interface))?;
+            bind(endpoint.interface)?;
+        }
+        (IpAddr::V6(_), UdpMode::BroadCast) => {
+            socket
+                .set_broadcast(true)
+                .map_err(|_| SocketError::SetBroadcast(endpoint.interface))?;
+            bind(endpoint.interface)?;
+        }
+        (IpAddr::V4(group), UdpMode::MultiCast) => {
+            if endpoint.bind_multicast {
+                bind(SocketAddr::new(
+                    Ipv4Addr::UNSPECIFIED.into(),
+                    endpoint.address.port(),
+                ))?;
+            }
+            if let IpAddr::V4(interface) = endpoint.interface.ip() {
+                socket
+                    .join_multicast_v4(&group, &interface)
+                    .map_err(|_| SocketError::JoinMulticastV4(group, interface))?;
+            }
+        }
+        (IpAddr::V6(group), UdpMode::MultiCast) => {
+            if endpoint.bind_multicast {
+                bind(SocketAddr::new(
+                    Ipv6Addr::UNSPECIFIED.into(),
+                    endpoint.address.port(),
+                ))?;
+            }
+            if let IpAddr::V6(interface) = endpoint.interface.ip() {
+                // IPv6 joins a group by interface index rather than address; index 0 lets the system pick the interface.
+                socket
+                    .join_multicast_v6(&group, 0)
+                    .map_err(|_| SocketError::JoinMulticastV6(group, interface))?;
+            }
+        }
+    }
+
+    // Convert socket2::Socket to tokio::net::UdpSocket via std::net::UdpSocket
+    let socket = std::net::UdpSocket::from(socket);
+    UdpSocket::try_from(socket).map_err(|_| SocketError::ConvertToAsync)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn bind_multicast_to_group_port() {
+        let interface: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let group: SocketAddr = "239.1.2.3:30396".parse().unwrap();
+
+        let bound = create_udp_socket(&UdpEndpoint::new_multicast(interface, group)).unwrap();
+        assert_eq!(bound.local_addr().unwrap().port(), group.port());
+
+        let unbound = create_udp_socket(
+            &UdpEndpoint::new_multicast(interface, group).with_bind_multicast(false),
+        )
+        .unwrap();
+        assert_eq!(unbound.local_addr().unwrap().port(), 0);
+    }
+}
@@ -16,6 +16,7 @@
 /// 7.5.7 Action Request PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ActionRequest {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -16,6 +16,7 @@
 /// 7.11.7 Action Request-R PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ActionRequestR {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -1,4 +1,5 @@
 use crate::aggregate_state::builder::AggregateStateBuilder;
+use crate::common::marking::{AGGREGATE_MARKING_LENGTH, validate_marking};
 use crate::common::{BodyInfo, Interaction};
 use crate::constants::{EIGHT_OCTETS, FOUR_OCTETS, THIRTY_TWO_OCTETS, TWO_OCTETS};
 use crate::entity_state::model::EntityAppearance;
@@ -28,6 +29,7 @@
 /// 7.8.2 Aggregate State PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AggregateState {
     pub aggregate_id: EntityId,
     pub force_id: ForceId,
@@ -137,6 +139,33 @@ pub fn with_marking<S: Into<String>>(mut self, marking: S) -> Self {
         self
     }
 
+    /// Creates a marking, checking that `marking` can be encoded in `character_set`.
+    ///
+    /// # Errors
+    /// Returns a `DisError` when the character set is not supported, the marking is too long,
+    /// or contains a character that is not part of the character set.
+    pub fn try_new(
+        marking: impl Into<String>,
+        character_set: EntityMarkingCharacterSet,
+    ) -> Result<Self, DisError> {
+        let marking = marking.into();
+        validate_marking(&marking, character_set, AGGREGATE_MARKING_LENGTH)?;
+        Ok(Self::new(marking, character_set))
+    }
+
+    /// Checks that the marking string can be encoded in the marking character set.
+    ///
+    /// # Errors
+    /// Returns a `DisError` when the character set is not supported, the marking is too long,
+    /// or contains a character that is not part of the character set.
+    pub fn validate(&self) -> Result<(), DisError> {
+        validate_marking(
+            &self.marking_string,
+            self.marking_character_set,
+            AGGREGATE_MARKING_LENGTH,
+        )
+    }
+
     #[must_use]
     pub fn record_length(&self) -> u16 {
         THIRTY_TWO_OCTETS as u16
@@ -149,27 +178,21 @@ fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     }
 }
 
+/// Parses a marking in the ASCII character set.
+///
+/// Markings in another character set, such as Army Marking (CCTT) or Digit Chevron, are parsed using [`AggregateMarking::try_new`].
 impl FromStr for AggregateMarking {
     type Err = DisError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() <= 31 {
-            Ok(Self {
-                marking_character_set: EntityMarkingCharacterSet::ASCII,
-                marking_string: s.to_string(),
-            })
-        } else {
-            Err(DisError::ParseError(format!(
-                "String is too long for AggregateMarking. Found {}, max 31 allowed.",
-                s.len()
-            )))
-        }
+        Self::try_new(s, EntityMarkingCharacterSet::ASCII)
     }
 }
 
 /// 6.2.5 Aggregate Type record
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AggregateType {
     pub aggregate_kind: AggregateStateAggregateKind,
     pub domain: PlatformDomain,
@@ -321,6 +344,7 @@ fn try_from(value: String) -> Result<Self, Self::Error> {
 /// Custom record for `SilentAggregateSystem`
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SilentAggregateSystem {
     pub number_of_aggregates: u16,
     pub aggregate_type: AggregateType,
@@ -3,8 +3,9 @@
     AggregateMarking, AggregateState, AggregateType, SilentAggregateSystem, SilentEntitySystem,
     aggregate_state_intermediate_length_padding,
 };
+use crate::common::marking::decode_marking;
 use crate::common::parser::{
-    entity_id, entity_type, location, orientation, sanitize_marking, variable_datum, vec3_f32,
+    entity_id, entity_type, location, orientation, variable_datum, vec3_f32,
 };
 use crate::entity_state::parser::{entity_appearance, force_id};
 use crate::enumerations::{
@@ -111,7 +112,7 @@ fn aggregate_marking(input: &[u8]) -> IResult<&[u8], AggregateMarking> {
     let marking_character_set = EntityMarkingCharacterSet::from(marking_character_set);
     let (input, ()) = nom::multi::fill(be_u8, &mut buf).parse(input)?;
 
-    let marking_string = sanitize_marking(&buf[..]);
+    let marking_string = decode_marking(&buf[..]);
 
     Ok((
         input,
@@ -3,6 +3,7 @@
     aggregate_state_intermediate_length_padding,
 };
 use crate::common::BodyInfo;
+use crate::common::marking::{AGGREGATE_MARKING_LENGTH, encode_marking};
 use crate::{Serialize, SerializePdu, SupportedVersion};
 use bytes::{BufMut, BytesMut};
 
@@ -75,10 +76,11 @@ fn serialize(&self, buf: &mut BytesMut) -> u16 {
 impl Serialize for AggregateMarking {
     fn serialize(&self, buf: &mut BytesMut) -> u16 {
         buf.put_u8(self.marking_character_set.into());
-        let bytes = self.marking_string.as_bytes();
-        let len = bytes.len().min(31);
-        buf.put_slice(&bytes[..len]);
-        buf.put_bytes(0x20, 31 - len);
+        // `Pdu::serialize` checks the marking against its character set before it gets here
+        buf.put_slice(&encode_marking(
+            &self.marking_string,
+            AGGREGATE_MARKING_LENGTH,
+        ));
 
         self.record_length()
     }
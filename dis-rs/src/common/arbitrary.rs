@@ -0,0 +1,699 @@
+//! Implementations of `Arbitrary` for the models that have invariants on the wire, for property based testing.
+//!
+//! Most models derive `Arbitrary`. The models in this module cannot, because the parser determines
+//! how (part of) the model is read from the values of other fields. For example, the appearance of an
+//! Entity State PDU depends on its entity type, and the layers of an IFF PDU depend on the information layers field.
+//! The generated values respect these invariants, as well as the number of octets of count fields
+//! and the padding of variable length records, so that they are equal after a round trip of serialization and parsing.
+use crate::common::BodyInfo;
+use crate::common::aggregate_state::model::{AggregateMarking, SilentEntitySystem};
+use crate::common::attribute::model::AttributeRecord;
+use crate::common::detonation::model::DetonationDescriptor;
+use crate::common::entity_state::model::{
+    DrOtherParameters, DrParameters, EntityAppearance, EntityMarking, EntityState,
+};
+use crate::common::fire::model::FireDescriptor;
+use crate::common::iff::model::{
+    ChangeOptionsRecord, DapSource, EnhancedMode1Code, FundamentalOperationalData, Iff,
+    IffDataRecord, IffLayer3, IffLayer4, InformationLayers, LayersPresenceApplicability,
+    Mode5BasicData, Mode5InterrogatorStatus, Mode5MessageFormats, Mode5TransponderStatus,
+    Mode5TransponderSupplementalData, ModeSAltitude, ModeSBasicData, ModeSInterrogatorStatus,
+    ModeSLevelsPresent, ModeSTransponderStatus, SystemId, SystemStatus,
+};
+use crate::common::is_group_of::model::{GroupEntityDescription, IsGroupOf};
+use crate::common::marking::{AGGREGATE_MARKING_LENGTH, ENTITY_MARKING_LENGTH};
+use crate::common::model::{
+    ArticulatedPart, EntityType, Pdu, PduBody, PduHeader, RecordSet, Timestamp, VariableParameter,
+};
+use crate::common::signal::model::{EncodingScheme, Signal};
+use crate::common::transmitter::model::{
+    CryptoKeyId, ModulationType, Transmitter, TransmitterMajorModulation,
+    VariableTransmitterParameter,
+};
+use crate::common::underwater_acoustic::model::{APA, PropulsionPlantConfiguration};
+use crate::enumerations::{
+    APAStatus, AirPlatformAppearance, ArticulatedPartsTypeClass, ArticulatedPartsTypeMetric,
+    DeadReckoningAlgorithm, DetonationTypeIndicator, EntityMarkingCharacterSet, FireTypeIndicator,
+    IffSystemType, IsGroupOfGroupedEntityCategory, LandPlatformAppearance, ProtocolVersion,
+    SignalEncodingClass, SignalEncodingType, SignalUserProtocolIdentificationNumber,
+    UAAdditionalPassiveActivityParameterIndex, UAPropulsionPlantConfiguration,
+};
+use crate::v7::entity_state::entity_capabilities_from_bytes;
+use crate::v7::parser::parse_pdu_status_fields;
+use alloc::string::String;
+use alloc::vec::Vec;
+use arbitrary::{Arbitrary, Unstructured};
+
+/// Maximum number of octets of the data of a Signal PDU, of which the length in bits is a 16-bit field.
+const MAX_SIGNAL_DATA_LENGTH: usize = 8191;
+
+const TRANSPONDERS: [IffSystemType; 6] = [
+    IffSystemType::MarkXXIIATCRBSTransponder,
+    IffSystemType::SovietTransponder,
+    IffSystemType::RRBTransponder,
+    IffSystemType::MarkXIIATransponder,
+    IffSystemType::Mode5Transponder,
+    IffSystemType::ModeSTransponder,
+];
+
+const INTERROGATORS: [IffSystemType; 5] = [
+    IffSystemType::MarkXXIIATCRBSInterrogator,
+    IffSystemType::SovietInterrogator,
+    IffSystemType::MarkXIIAInterrogator,
+    IffSystemType::Mode5Interrogator,
+    IffSystemType::ModeSInterrogator,
+];
+
+/// Implements `Arbitrary` for records that are parsed from an integer using their `From` impl.
+macro_rules! impl_arbitrary_from {
+    ($($record:ty => $bits:ty),* $(,)?) => {
+        $(
+            impl<'a> Arbitrary<'a> for $record {
+                fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+                    Ok(Self::from(<$bits>::arbitrary(u)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_from!(
+    Timestamp => u32,
+    CryptoKeyId => u16,
+    ChangeOptionsRecord => u8,
+    SystemStatus => u8,
+    InformationLayers => u8,
+    DapSource => u8,
+    EnhancedMode1Code => u16,
+    Mode5InterrogatorStatus => u8,
+    Mode5MessageFormats => u32,
+    Mode5TransponderSupplementalData => u8,
+    Mode5TransponderStatus => u16,
+    ModeSAltitude => u16,
+    ModeSInterrogatorStatus => u8,
+    ModeSLevelsPresent => u8,
+    ModeSTransponderStatus => u16,
+);
+
+/// Generates at most `max` elements using `element`, stopping early when the input is exhausted.
+fn capped<'a, T>(
+    u: &mut Unstructured<'a>,
+    max: usize,
+    mut element: impl FnMut(&mut Unstructured<'a>) -> arbitrary::Result<T>,
+) -> arbitrary::Result<Vec<T>> {
+    let mut elements = Vec::new();
+    while elements.len() < max && bool::arbitrary(u)? {
+        elements.push(element(u)?);
+    }
+    Ok(elements)
+}
+
+/// Generates a list of which the number of elements is an 8-bit field on the wire.
+pub(crate) fn u8_counted<'a, T: Arbitrary<'a>>(
+    u: &mut Unstructured<'a>,
+) -> arbitrary::Result<Vec<T>> {
+    capped(u, usize::from(u8::MAX), T::arbitrary)
+}
+
+/// Generates the specific fields of a variable length record with a header of `base` octets,
+/// such that the record ends on a multiple of `alignment` octets.
+/// The parser keeps the padding in the specific fields, so the fields include the padding.
+fn aligned_bytes(
+    u: &mut Unstructured,
+    base: usize,
+    alignment: usize,
+) -> arbitrary::Result<Vec<u8>> {
+    let length = u.arbitrary_len::<u8>()? / alignment * alignment
+        + (alignment - base % alignment) % alignment;
+    (0..length).map(|_| u8::arbitrary(u)).collect()
+}
+
+/// Generates a marking string of at most `max_length` characters of `character_set`, without trailing spaces.
+fn marking(
+    u: &mut Unstructured,
+    character_set: EntityMarkingCharacterSet,
+    max_length: usize,
+) -> arbitrary::Result<String> {
+    const ARMY_MARKING: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 ";
+    const DIGIT_CHEVRON: &[u8] = b"0123456789<> ";
+
+    let length = u.int_in_range(0..=max_length)?;
+    let marking = (0..length)
+        .map(|_| {
+            let c = match u8::from(character_set) {
+                2 => *u.choose(ARMY_MARKING)?,
+                3 => *u.choose(DIGIT_CHEVRON)?,
+                _ => u.int_in_range(b' '..=b'~')?,
+            };
+            Ok(char::from(c))
+        })
+        .collect::<arbitrary::Result<String>>()?;
+    Ok(String::from(marking.trim_end_matches(' ')))
+}
+
+fn marking_character_set(u: &mut Unstructured) -> arbitrary::Result<EntityMarkingCharacterSet> {
+    Ok(EntityMarkingCharacterSet::from(u.int_in_range(0..=3u8)?))
+}
+
+/// Generates the appearance of an entity of kind Other, as in the `EntityStateUpdate` PDU.
+pub(crate) fn unspecified_appearance(u: &mut Unstructured) -> arbitrary::Result<EntityAppearance> {
+    Ok(EntityAppearance::Unspecified(u.arbitrary()?))
+}
+
+/// Generates the appearance of a land platform, as in Group Entity Description records of ground vehicles and soldiers.
+pub(crate) fn land_platform_appearance(
+    u: &mut Unstructured,
+) -> arbitrary::Result<EntityAppearance> {
+    Ok(EntityAppearance::LandPlatform(
+        LandPlatformAppearance::arbitrary(u)?,
+    ))
+}
+
+/// Generates the appearance of an air platform, as in Group Entity Description records of aircraft.
+pub(crate) fn air_platform_appearance(u: &mut Unstructured) -> arbitrary::Result<EntityAppearance> {
+    Ok(EntityAppearance::AirPlatform(
+        AirPlatformAppearance::arbitrary(u)?,
+    ))
+}
+
+/// Generates an aircraft identification of at most 8 ASCII alphanumeric characters,
+/// as the parser strips trailing non-alphanumeric characters.
+pub(crate) fn aircraft_identification(u: &mut Unstructured) -> arbitrary::Result<String> {
+    const ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+    let length = u.int_in_range(0..=8)?;
+    (0..length)
+        .map(|_| Ok(char::from(*u.choose(ALPHANUMERIC)?)))
+        .collect()
+}
+
+impl<'a> Arbitrary<'a> for ArticulatedPart {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Parameter Type = Type Class + Type Metric, where the Type Metric is the five least significant bits
+        let parameter_type = u32::arbitrary(u)?;
+        let type_metric = parameter_type & 0x1F;
+
+        Ok(Self {
+            change_indicator: u.arbitrary()?,
+            attachment_id: u.arbitrary()?,
+            type_metric: ArticulatedPartsTypeMetric::from(type_metric),
+            type_class: ArticulatedPartsTypeClass::from(parameter_type - type_metric),
+            parameter_value: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for VariableParameter {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=5)? {
+            0 => VariableParameter::Articulated(u.arbitrary()?),
+            1 => VariableParameter::Attached(u.arbitrary()?),
+            2 => VariableParameter::Separation(u.arbitrary()?),
+            3 => VariableParameter::EntityType(u.arbitrary()?),
+            4 => VariableParameter::EntityAssociation(u.arbitrary()?),
+            // record types that are not defined by the standard
+            _ => VariableParameter::Unspecified(u.int_in_range(5..=u8::MAX)?, u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RecordSet {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // all record values in a record set are of the same length
+        let record_length = u.int_in_range(0..=32)?;
+        let record_count = u.arbitrary_len::<u8>()? / record_length.max(1);
+        let records = (0..record_count)
+            .map(|_| (0..record_length).map(|_| u8::arbitrary(u)).collect())
+            .collect::<arbitrary::Result<Vec<Vec<u8>>>>()?;
+
+        Ok(RecordSet::default()
+            .with_record_id(u.arbitrary()?)
+            .with_record_serial_number(u.arbitrary()?)
+            .with_records(records))
+    }
+}
+
+impl<'a> Arbitrary<'a> for EntityMarking {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let character_set = marking_character_set(u)?;
+        Ok(EntityMarking::new(
+            marking(u, character_set, ENTITY_MARKING_LENGTH)?,
+            character_set,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for AggregateMarking {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let marking_character_set = marking_character_set(u)?;
+        Ok(AggregateMarking {
+            marking_character_set,
+            marking_string: marking(u, marking_character_set, AGGREGATE_MARKING_LENGTH)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for DrParameters {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the Dead Reckoning Algorithm determines how the other parameters are parsed
+        let algorithm = DeadReckoningAlgorithm::arbitrary(u)?;
+        let other_parameters = match algorithm {
+            DeadReckoningAlgorithm::StaticNonmovingEntity
+            | DeadReckoningAlgorithm::DRM_FPW_ConstantVelocityLowAccelerationLinearMotionEntity
+            | DeadReckoningAlgorithm::DRM_FVW_HighSpeedOrManeuveringEntity
+            | DeadReckoningAlgorithm::DRM_FPB_SimilarToFPWExceptInBodyCoordinates
+            | DeadReckoningAlgorithm::DRM_FVB_SimilarToFVWExceptInBodyCoordinates => {
+                DrOtherParameters::LocalEulerAngles(u.arbitrary()?)
+            }
+            DeadReckoningAlgorithm::DRM_RPW_ConstantVelocityLowAccelerationLinearMotionEntityWithExtrapolationOfOrientation
+            | DeadReckoningAlgorithm::DRM_RVW_HighSpeedOrManeuveringEntityWithExtrapolationOfOrientation
+            | DeadReckoningAlgorithm::DRM_RPB_SimilarToRPWExceptInBodyCoordinates
+            | DeadReckoningAlgorithm::DRM_RVB_SimilarToRVWExceptInBodyCoordinates => {
+                DrOtherParameters::WorldOrientationQuaternion(u.arbitrary()?)
+            }
+            DeadReckoningAlgorithm::Other | DeadReckoningAlgorithm::Unspecified(_) => {
+                DrOtherParameters::None(u.arbitrary()?)
+            }
+        };
+
+        Ok(DrParameters {
+            algorithm,
+            other_parameters,
+            linear_acceleration: u.arbitrary()?,
+            angular_velocity: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for EntityState {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the entity type determines how the appearance and capabilities are parsed
+        let entity_type = EntityType::arbitrary(u)?;
+
+        Ok(EntityState {
+            entity_id: u.arbitrary()?,
+            force_id: u.arbitrary()?,
+            entity_type,
+            alternative_entity_type: u.arbitrary()?,
+            entity_linear_velocity: u.arbitrary()?,
+            entity_location: u.arbitrary()?,
+            entity_orientation: u.arbitrary()?,
+            entity_appearance: EntityAppearance::from_bytes(u.arbitrary()?, &entity_type),
+            dead_reckoning_parameters: u.arbitrary()?,
+            entity_marking: u.arbitrary()?,
+            entity_capabilities: entity_capabilities_from_bytes(u.arbitrary()?, &entity_type),
+            variable_parameters: u8_counted(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SilentEntitySystem {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let entity_type = EntityType::arbitrary(u)?;
+        let appearances = capped(u, usize::from(u16::MAX), |u| {
+            Ok(EntityAppearance::from_bytes(u.arbitrary()?, &entity_type))
+        })?;
+
+        Ok(SilentEntitySystem::default()
+            .with_number_of_entities(u.arbitrary()?)
+            .with_entity_type(entity_type)
+            .with_appearances(appearances))
+    }
+}
+
+impl<'a> Arbitrary<'a> for IsGroupOf {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // all descriptions are of the grouped entity category
+        let grouped_entity_category = IsGroupOfGroupedEntityCategory::arbitrary(u)?;
+        let descriptions = capped(u, usize::from(u8::MAX), |u| {
+            Ok(match grouped_entity_category {
+                IsGroupOfGroupedEntityCategory::Undefined
+                | IsGroupOfGroupedEntityCategory::Unspecified(_) => {
+                    GroupEntityDescription::Undefined
+                }
+                IsGroupOfGroupedEntityCategory::BasicGroundCombatVehicle => {
+                    GroupEntityDescription::BasicGroundCombatVehicle(u.arbitrary()?)
+                }
+                IsGroupOfGroupedEntityCategory::EnhancedGroundCombatVehicle => {
+                    GroupEntityDescription::EnhancedGroundCombatVehicle(u.arbitrary()?)
+                }
+                IsGroupOfGroupedEntityCategory::BasicGroundCombatSoldier => {
+                    GroupEntityDescription::BasicGroundCombatSoldier(u.arbitrary()?)
+                }
+                IsGroupOfGroupedEntityCategory::EnhancedGroundCombatSoldier => {
+                    GroupEntityDescription::EnhancedGroundCombatSoldier(u.arbitrary()?)
+                }
+                IsGroupOfGroupedEntityCategory::BasicRotorWingAircraft => {
+                    GroupEntityDescription::BasicRotorWingAircraft(u.arbitrary()?)
+                }
+                IsGroupOfGroupedEntityCategory::EnhancedRotorWingAircraft => {
+                    GroupEntityDescription::EnhancedRotorWingAircraft(u.arbitrary()?)
+                }
+                IsGroupOfGroupedEntityCategory::BasicFixedWingAircraft => {
+                    GroupEntityDescription::BasicFixedWingAircraft(u.arbitrary()?)
+                }
+                IsGroupOfGroupedEntityCategory::EnhancedFixedWingAircraft => {
+                    GroupEntityDescription::EnhancedFixedWingAircraft(u.arbitrary()?)
+                }
+                IsGroupOfGroupedEntityCategory::GroundLogisticsVehicle => {
+                    GroupEntityDescription::GroundLogisticsVehicle(u.arbitrary()?)
+                }
+            })
+        })?;
+
+        Ok(IsGroupOf {
+            group_id: u.arbitrary()?,
+            grouped_entity_category,
+            group_reference_point: u.arbitrary()?,
+            descriptions,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for EncodingScheme {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the encoding class is a 2-bit field, leaving 14 bits for the encoding type or number of messages
+        Ok(match u.int_in_range(0..=3)? {
+            0 => EncodingScheme::EncodedAudio {
+                encoding_class: SignalEncodingClass::EncodedAudio,
+                encoding_type: SignalEncodingType::from(u.int_in_range(0..=0x3FFFu16)?),
+            },
+            1 => EncodingScheme::RawBinaryData {
+                encoding_class: SignalEncodingClass::RawBinaryData,
+                nr_of_messages: u.int_in_range(0..=0x3FFF)?,
+            },
+            2 => EncodingScheme::ApplicationSpecificData {
+                encoding_class: SignalEncodingClass::ApplicationSpecificData,
+                user_protocol_id: SignalUserProtocolIdentificationNumber::from(u32::arbitrary(u)?),
+            },
+            _ => EncodingScheme::DatabaseIndex {
+                encoding_class: SignalEncodingClass::DatabaseIndex,
+                index: u.arbitrary()?,
+                offset_milli_secs: u.arbitrary()?,
+                duration_milli_secs: u.arbitrary()?,
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Signal {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let encoding_scheme = EncodingScheme::arbitrary(u)?;
+        // the parser reads the fields of application specific data and database index encodings from the data
+        let mut data: Vec<u8> = match &encoding_scheme {
+            EncodingScheme::ApplicationSpecificData {
+                user_protocol_id, ..
+            } => u32::from(*user_protocol_id).to_be_bytes().to_vec(),
+            EncodingScheme::DatabaseIndex {
+                index,
+                offset_milli_secs,
+                duration_milli_secs,
+                ..
+            } => [*index, *offset_milli_secs, *duration_milli_secs]
+                .iter()
+                .flat_map(|field| field.to_be_bytes())
+                .collect(),
+            _ => Vec::new(),
+        };
+        let data_length = u
+            .arbitrary_len::<u8>()?
+            .min(MAX_SIGNAL_DATA_LENGTH - data.len());
+        for _ in 0..data_length {
+            data.push(u.arbitrary()?);
+        }
+
+        Ok(Signal {
+            radio_reference_id: u.arbitrary()?,
+            radio_number: u.arbitrary()?,
+            encoding_scheme,
+            tdl_type: u.arbitrary()?,
+            sample_rate: u.arbitrary()?,
+            samples: u.arbitrary()?,
+            data,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for IffDataRecord {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(IffDataRecord {
+            record_type: u.arbitrary()?,
+            record_specific_fields: aligned_bytes(u, 6, 4)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Iff {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the information layers determine which layers are present,
+        // and the system type determines the format of the basic data of layers 3 and 4
+        let fundamental_operational_data: FundamentalOperationalData = u.arbitrary()?;
+        let layers: &InformationLayers = &fundamental_operational_data.information_layers;
+        let is_present = |layer: &LayersPresenceApplicability| {
+            *layer == LayersPresenceApplicability::PresentApplicable
+        };
+        let is_transponder = bool::arbitrary(u)?;
+
+        let mut system_id = SystemId::arbitrary(u)?;
+        if is_present(&layers.layer_3) || is_present(&layers.layer_4) {
+            system_id.system_type = if is_transponder {
+                *u.choose(&TRANSPONDERS)?
+            } else {
+                *u.choose(&INTERROGATORS)?
+            };
+        }
+
+        let layer_2 = if is_present(&layers.layer_2) {
+            Some(u.arbitrary()?)
+        } else {
+            None
+        };
+        let layer_3 = if is_present(&layers.layer_3) {
+            let mut layer_3 = IffLayer3::arbitrary(u)?;
+            layer_3.mode_5_basic_data = if is_transponder {
+                Mode5BasicData::Transponder(u.arbitrary()?)
+            } else {
+                Mode5BasicData::Interrogator(u.arbitrary()?)
+            };
+            Some(layer_3)
+        } else {
+            None
+        };
+        let layer_4 = if is_present(&layers.layer_4) {
+            let mut layer_4 = IffLayer4::arbitrary(u)?;
+            layer_4.mode_s_basic_data = if is_transponder {
+                ModeSBasicData::Transponder(u.arbitrary()?)
+            } else {
+                ModeSBasicData::Interrogator(u.arbitrary()?)
+            };
+            Some(layer_4)
+        } else {
+            None
+        };
+        let layer_5 = if is_present(&layers.layer_5) {
+            Some(u.arbitrary()?)
+        } else {
+            None
+        };
+
+        Ok(Iff {
+            emitting_entity_id: u.arbitrary()?,
+            event_id: u.arbitrary()?,
+            relative_antenna_location: u.arbitrary()?,
+            system_id,
+            system_designator: u.arbitrary()?,
+            system_specific_data: u.arbitrary()?,
+            fundamental_operational_data,
+            layer_2,
+            layer_3,
+            layer_4,
+            layer_5,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ModulationType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the major modulation determines the type of the detail field
+        Ok(ModulationType::new()
+            .with_spread_spectrum(u.arbitrary()?)
+            .with_major_modulation(TransmitterMajorModulation::new_from_bytes_with_detail(
+                u.arbitrary()?,
+                u.arbitrary()?,
+            ))
+            .with_radio_system(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for VariableTransmitterParameter {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(VariableTransmitterParameter::new()
+            .with_record_type(u.arbitrary()?)
+            .with_fields(aligned_bytes(u, 6, 8)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Transmitter {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the length of the modulation parameters is an 8-bit field, where zero means not present
+        let modulation_parameters = if bool::arbitrary(u)? {
+            let length = u.int_in_range(1..=u8::MAX)?;
+            Some(
+                (0..length)
+                    .map(|_| u8::arbitrary(u))
+                    .collect::<arbitrary::Result<Vec<u8>>>()?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Transmitter {
+            radio_reference_id: u.arbitrary()?,
+            radio_number: u.arbitrary()?,
+            radio_type: u.arbitrary()?,
+            transmit_state: u.arbitrary()?,
+            input_source: u.arbitrary()?,
+            antenna_location: u.arbitrary()?,
+            relative_antenna_location: u.arbitrary()?,
+            antenna_pattern_type: u.arbitrary()?,
+            frequency: u.arbitrary()?,
+            transmit_frequency_bandwidth: u.arbitrary()?,
+            power: u.arbitrary()?,
+            modulation_type: u.arbitrary()?,
+            crypto_system: u.arbitrary()?,
+            crypto_key_id: u.arbitrary()?,
+            modulation_parameters,
+            antenna_pattern: u.arbitrary()?,
+            variable_transmitter_parameters: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AttributeRecord {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(AttributeRecord::new()
+            .with_record_type(u.arbitrary()?)
+            .with_specific_fields(aligned_bytes(u, 6, 8)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for APA {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the parameter index is a 14-bit field, followed by the 2-bit status
+        Ok(APA::default()
+            .with_parameter(UAAdditionalPassiveActivityParameterIndex::from(
+                u.int_in_range(0..=0x3FFFu16)?,
+            ))
+            .with_status(APAStatus::from(u.int_in_range(0..=3u8)?))
+            .with_value(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for PropulsionPlantConfiguration {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // the configuration is a 7-bit field, followed by the hull mounted masker bit
+        Ok(PropulsionPlantConfiguration::default()
+            .with_configuration(UAPropulsionPlantConfiguration::from(
+                u.int_in_range(0..=0x7Fu8)?,
+            ))
+            .with_hull_mounted_masker(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for PduBody {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // only the PDU types that are supported by the crate, having a model
+        Ok(match u.choose_index(53)? {
+            0 => PduBody::Other(u.arbitrary()?),
+            1 => PduBody::EntityState(u.arbitrary()?),
+            2 => PduBody::Fire(u.arbitrary()?),
+            3 => PduBody::Detonation(u.arbitrary()?),
+            4 => PduBody::Collision(u.arbitrary()?),
+            5 => PduBody::ServiceRequest(u.arbitrary()?),
+            6 => PduBody::ResupplyOffer(u.arbitrary()?),
+            7 => PduBody::ResupplyReceived(u.arbitrary()?),
+            8 => PduBody::ResupplyCancel(u.arbitrary()?),
+            9 => PduBody::RepairComplete(u.arbitrary()?),
+            10 => PduBody::RepairResponse(u.arbitrary()?),
+            11 => PduBody::CreateEntity(u.arbitrary()?),
+            12 => PduBody::RemoveEntity(u.arbitrary()?),
+            13 => PduBody::StartResume(u.arbitrary()?),
+            14 => PduBody::StopFreeze(u.arbitrary()?),
+            15 => PduBody::Acknowledge(u.arbitrary()?),
+            16 => PduBody::ActionRequest(u.arbitrary()?),
+            17 => PduBody::ActionResponse(u.arbitrary()?),
+            18 => PduBody::DataQuery(u.arbitrary()?),
+            19 => PduBody::SetData(u.arbitrary()?),
+            20 => PduBody::Data(u.arbitrary()?),
+            21 => PduBody::EventReport(u.arbitrary()?),
+            22 => PduBody::Comment(u.arbitrary()?),
+            23 => PduBody::ElectromagneticEmission(u.arbitrary()?),
+            24 => PduBody::Designator(u.arbitrary()?),
+            25 => PduBody::Transmitter(u.arbitrary()?),
+            26 => PduBody::Signal(u.arbitrary()?),
+            27 => PduBody::Receiver(u.arbitrary()?),
+            28 => PduBody::IFF(u.arbitrary()?),
+            29 => PduBody::UnderwaterAcoustic(u.arbitrary()?),
+            30 => PduBody::SupplementalEmissionEntityState(u.arbitrary()?),
+            31 => PduBody::AggregateState(u.arbitrary()?),
+            32 => PduBody::IsGroupOf(u.arbitrary()?),
+            33 => PduBody::TransferOwnership(u.arbitrary()?),
+            34 => PduBody::IsPartOf(u.arbitrary()?),
+            35 => PduBody::CreateEntityR(u.arbitrary()?),
+            36 => PduBody::RemoveEntityR(u.arbitrary()?),
+            37 => PduBody::StartResumeR(u.arbitrary()?),
+            38 => PduBody::StopFreezeR(u.arbitrary()?),
+            39 => PduBody::AcknowledgeR(u.arbitrary()?),
+            40 => PduBody::ActionRequestR(u.arbitrary()?),
+            41 => PduBody::ActionResponseR(u.arbitrary()?),
+            42 => PduBody::DataQueryR(u.arbitrary()?),
+            43 => PduBody::SetDataR(u.arbitrary()?),
+            44 => PduBody::DataR(u.arbitrary()?),
+            45 => PduBody::EventReportR(u.arbitrary()?),
+            46 => PduBody::CommentR(u.arbitrary()?),
+            47 => PduBody::RecordR(u.arbitrary()?),
+            48 => PduBody::SetRecordR(u.arbitrary()?),
+            49 => PduBody::RecordQueryR(u.arbitrary()?),
+            50 => PduBody::CollisionElastic(u.arbitrary()?),
+            51 => PduBody::EntityStateUpdate(u.arbitrary()?),
+            _ => PduBody::Attribute(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Pdu {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let body = PduBody::arbitrary(u)?;
+        let pdu_type = body.body_type();
+
+        // the PDU status of the header determines how the descriptor of Fire and Detonation PDUs is parsed
+        let pdu_status = parse_pdu_status_fields(pdu_type.into(), u.arbitrary()?);
+        let pdu_status = match &body {
+            PduBody::Fire(fire) => pdu_status.with_fire_type_indicator(match fire.descriptor {
+                FireDescriptor::Munition(_) => FireTypeIndicator::Munition,
+                FireDescriptor::Expendable(_) => FireTypeIndicator::Expendable,
+            }),
+            PduBody::Detonation(detonation) => {
+                pdu_status.with_detonation_type_indicator(match detonation.descriptor {
+                    DetonationDescriptor::Munition(_) => DetonationTypeIndicator::Munition,
+                    DetonationDescriptor::Explosion(_) => {
+                        DetonationTypeIndicator::NonmunitionExplosion
+                    }
+                    DetonationDescriptor::Expendable(_) => DetonationTypeIndicator::Expendable,
+                })
+            }
+            _ => pdu_status,
+        };
+
+        let header = PduHeader::new_v7(u.arbitrary()?, pdu_type).with_pdu_status(pdu_status);
+        let pdu = Pdu::finalize_from_parts(header, body, u.arbitrary()?);
+
+        // PDUs are generated for both supported protocol versions, as far as the PDU type exists in v6
+        if bool::arbitrary(u)? {
+            if let Ok(converted) = pdu.convert_to(ProtocolVersion::IEEE1278_1A1998) {
+                return Ok(converted.pdu);
+            }
+        }
+        Ok(pdu)
+    }
+}
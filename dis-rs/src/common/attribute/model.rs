@@ -16,6 +16,7 @@
 /// 7.2.6 Attribute PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Attribute {
     pub originating_simulation_address: SimulationAddress,
     pub record_pdu_type: PduType,
@@ -79,6 +80,7 @@ fn receiver(&self) -> Option<&EntityId> {
 /// 5.3.6.3
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AttributeRecordSet {
     pub entity_id: EntityId,
     pub attribute_records: Vec<AttributeRecord>,
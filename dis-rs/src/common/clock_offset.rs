@@ -0,0 +1,358 @@
+//! Estimation of the clock offset and drift of senders, to convert their (relative) timestamps into local time.
+//!
+//! Relative timestamps are generated from the clock of the sending host, which is not synchronised to other hosts.
+//! The [`ClockOffsetEstimator`] observes the local time at which PDUs are received together with their timestamps,
+//! and learns the relation between the clock of each sender and the local clock with a linear fit over a window of samples.
+//!
+//! Network jitter only ever delays a PDU, so the fit is placed at the lower envelope of the samples:
+//! a converted timestamp is the local time at which the PDU would have arrived with the lowest observed latency.
+//!
+//! Like the [`ReliabilityManager`](crate::simulation_management::reliability::ReliabilityManager),
+//! local time is passed in by the caller as a `Duration` since an arbitrary (but fixed) epoch.
+use crate::common::Interaction;
+use crate::common::model::{Pdu, SimulationAddress};
+use crate::common::timestamp::{TIME_UNITS_PER_HOUR, Timestamp};
+use crate::common::unwrapped_timestamp::{TimestampUnwrapper, UnwrappedTimestamp};
+use alloc::collections::{BTreeMap, VecDeque};
+use core::time::Duration;
+
+const SECONDS_PER_TIME_UNIT: f64 = 3_600.0 / TIME_UNITS_PER_HOUR as f64;
+const UNITS_PER_HOUR: i64 = TIME_UNITS_PER_HOUR as i64;
+
+/// Settings of the estimation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ClockOffsetSettings {
+    /// Number of most recent samples per sender used for the estimate.
+    pub window: usize,
+    /// Minimum time spanned by the samples before the drift of a sender is estimated; the drift is zero until then.
+    pub min_drift_span: Duration,
+}
+
+impl ClockOffsetSettings {
+    #[must_use]
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    #[must_use]
+    pub fn with_min_drift_span(mut self, min_drift_span: Duration) -> Self {
+        self.min_drift_span = min_drift_span;
+        self
+    }
+}
+
+impl Default for ClockOffsetSettings {
+    fn default() -> Self {
+        Self {
+            window: 64,
+            min_drift_span: Duration::from_secs(10),
+        }
+    }
+}
+
+/// The estimated relation between the clock of a sender and the local clock:
+/// `local = remote * (1 + drift) + offset`, in seconds.
+///
+/// `remote` counts from the start of the first hour observed for the sender (see [`UnwrappedTimestamp`]),
+/// `local` from the epoch of the local time passed to the estimator.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ClockEstimate {
+    /// Offset of the local clock relative to the clock of the sender, in seconds.
+    pub offset: f64,
+    /// Relative rate difference of the local clock with respect to the clock of the sender (e.g. `1e-4` for 100 ppm).
+    pub drift: f64,
+}
+
+impl ClockEstimate {
+    /// Converts a remote time in seconds into local time in seconds.
+    #[must_use]
+    pub fn to_local_seconds(&self, remote: f64) -> f64 {
+        remote * (1.0 + self.drift) + self.offset
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct SenderSamples {
+    /// Pairs of (remote, local) time in seconds.
+    samples: VecDeque<(f64, f64)>,
+    latest: UnwrappedTimestamp,
+    estimate: ClockEstimate,
+}
+
+impl SenderSamples {
+    fn update_estimate(&mut self, min_drift_span: f64) {
+        let count = self.samples.len() as f64;
+        let (remote_min, remote_max) = self
+            .samples
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(min, max), &(remote, _)| {
+                (min.min(remote), max.max(remote))
+            });
+
+        let slope = if remote_max - remote_min >= min_drift_span {
+            let remote_mean = self.samples.iter().map(|(remote, _)| remote).sum::<f64>() / count;
+            let local_mean = self.samples.iter().map(|(_, local)| local).sum::<f64>() / count;
+            let (covariance, variance) =
+                self.samples
+                    .iter()
+                    .fold((0.0, 0.0), |(covariance, variance), &(remote, local)| {
+                        let remote = remote - remote_mean;
+                        (
+                            covariance + remote * (local - local_mean),
+                            variance + remote * remote,
+                        )
+                    });
+            // Samples at a single remote time (possible with a zero `min_drift_span`) have no slope
+            if variance > 0.0 {
+                covariance / variance
+            } else {
+                1.0
+            }
+        } else {
+            1.0
+        };
+
+        let offset = self
+            .samples
+            .iter()
+            .map(|&(remote, local)| local - slope * remote)
+            .fold(f64::MAX, f64::min);
+
+        self.estimate = ClockEstimate {
+            offset,
+            drift: slope - 1.0,
+        };
+    }
+}
+
+/// Learns the clock offset and drift of each sender, identified by its `SimulationAddress`,
+/// from the local receive time and timestamp of its PDUs.
+///
+/// # Examples
+///
+/// ```
+/// # use core::time::Duration;
+/// # use dis_rs::clock_offset::ClockOffsetEstimator;
+/// # use dis_rs::model::{SimulationAddress, TimeUnits, Timestamp};
+/// let sender = SimulationAddress::new(1, 1);
+/// let mut estimator = ClockOffsetEstimator::default();
+///
+/// // The clock of the sender is at 10 s past the hour when the PDU arrives at 100 s local time
+/// let timestamp = Timestamp::Relative(TimeUnits::from_duration_past_hour(Duration::from_secs(10)));
+/// estimator.observe(sender, Duration::from_secs(100), timestamp);
+///
+/// let later = Timestamp::Relative(TimeUnits::from_duration_past_hour(Duration::from_secs(15)));
+/// let local = estimator.to_local(sender, later).unwrap();
+/// assert_eq!(local.as_secs_f64().round(), 105.0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ClockOffsetEstimator {
+    settings: ClockOffsetSettings,
+    unwrapper: TimestampUnwrapper<SimulationAddress>,
+    senders: BTreeMap<SimulationAddress, SenderSamples>,
+}
+
+impl ClockOffsetEstimator {
+    #[must_use]
+    pub fn new(settings: ClockOffsetSettings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn settings(&self) -> &ClockOffsetSettings {
+        &self.settings
+    }
+
+    /// Adds a sample for `sender`: a PDU with `timestamp` was received at local time `received`.
+    ///
+    /// Returns the timestamp converted to local time using the updated estimate.
+    pub fn observe(
+        &mut self,
+        sender: SimulationAddress,
+        received: Duration,
+        timestamp: Timestamp,
+    ) -> Option<Duration> {
+        let remote = self.unwrapper.unwrap_timestamp(sender, timestamp);
+        let entry = self.senders.entry(sender).or_default();
+
+        if entry.samples.len() >= self.settings.window {
+            entry.samples.pop_front();
+        }
+        entry
+            .samples
+            .push_back((seconds(remote), received.as_secs_f64()));
+        entry.latest = entry.latest.max(remote);
+        entry.update_estimate(self.settings.min_drift_span.as_secs_f64());
+
+        to_duration(entry.estimate.to_local_seconds(seconds(remote)))
+    }
+
+    /// Adds a sample for the originator of `pdu`, received at local time `received`.
+    ///
+    /// Returns `None` when the body of the PDU has no originating entity, or when the timestamp precedes the local epoch.
+    pub fn observe_pdu(&mut self, pdu: &Pdu, received: Duration) -> Option<Duration> {
+        let sender = pdu.originator()?.simulation_address;
+        self.observe(sender, received, pdu.header.timestamp)
+    }
+
+    /// Converts `timestamp` of `sender` into local time, without adding a sample.
+    ///
+    /// The timestamp is placed in the hour closest to the latest observed timestamp of the sender.
+    /// Returns `None` when no samples of the sender have been observed, or when the converted time precedes the local epoch.
+    #[must_use]
+    pub fn to_local(&self, sender: SimulationAddress, timestamp: Timestamp) -> Option<Duration> {
+        let entry = self.senders.get(&sender)?;
+        let remote = nearest(entry.latest, timestamp);
+        to_duration(entry.estimate.to_local_seconds(seconds(remote)))
+    }
+
+    /// Converts `pdu`'s timestamp into local time, using the simulation address of the originator as sender.
+    #[must_use]
+    pub fn pdu_to_local(&self, pdu: &Pdu) -> Option<Duration> {
+        let sender = pdu.originator()?.simulation_address;
+        self.to_local(sender, pdu.header.timestamp)
+    }
+
+    /// Returns the current estimate for `sender`, if any samples have been observed.
+    #[must_use]
+    pub fn estimate(&self, sender: SimulationAddress) -> Option<ClockEstimate> {
+        self.senders.get(&sender).map(|entry| entry.estimate)
+    }
+
+    /// Forgets the samples of `sender`, for instance when it leaves the exercise.
+    pub fn remove(&mut self, sender: SimulationAddress) {
+        self.unwrapper.remove(&sender);
+        self.senders.remove(&sender);
+    }
+
+    /// Forgets the samples of all senders.
+    pub fn clear(&mut self) {
+        self.unwrapper.clear();
+        self.senders.clear();
+    }
+}
+
+fn seconds(timestamp: UnwrappedTimestamp) -> f64 {
+    timestamp.total_time_units() as f64 * SECONDS_PER_TIME_UNIT
+}
+
+fn to_duration(seconds: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Places `timestamp` in the hour that is closest to `latest`.
+fn nearest(latest: UnwrappedTimestamp, timestamp: Timestamp) -> UnwrappedTimestamp {
+    let candidate = UnwrappedTimestamp::new(latest.hour(), timestamp.time_units());
+    let difference = candidate.total_time_units() - latest.total_time_units();
+    if difference > UNITS_PER_HOUR / 2 {
+        UnwrappedTimestamp::new(latest.hour() - 1, timestamp.time_units())
+    } else if difference < -UNITS_PER_HOUR / 2 {
+        UnwrappedTimestamp::new(latest.hour() + 1, timestamp.time_units())
+    } else {
+        candidate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::timestamp::TimeUnits;
+
+    const SENDER: SimulationAddress = SimulationAddress {
+        site_id: 1,
+        application_id: 1,
+    };
+
+    fn remote_at(seconds: f64) -> Timestamp {
+        Timestamp::Relative(TimeUnits::from_duration_past_hour(Duration::from_secs_f64(
+            seconds,
+        )))
+    }
+
+    #[test]
+    fn offset_uses_the_lowest_latency() {
+        let mut estimator = ClockOffsetEstimator::default();
+        let latencies = [0.030, 0.012, 0.050, 0.010, 0.021];
+
+        for (i, latency) in latencies.iter().enumerate() {
+            let remote = 1_000.0 + i as f64 * 0.1;
+            estimator.observe(
+                SENDER,
+                Duration::from_secs_f64(remote + 500.0 + latency),
+                remote_at(remote),
+            );
+        }
+
+        let estimate = estimator.estimate(SENDER).unwrap();
+        assert_eq!(estimate.drift, 0.0);
+        assert!((estimate.offset - 500.010).abs() < 1e-6);
+
+        let local = estimator.to_local(SENDER, remote_at(1_010.0)).unwrap();
+        assert!((local.as_secs_f64() - 1_510.010).abs() < 1e-6);
+    }
+
+    #[test]
+    fn drift_is_estimated() {
+        let mut estimator =
+            ClockOffsetEstimator::new(ClockOffsetSettings::default().with_window(16));
+        let drift = 1e-4;
+
+        for i in 0..32 {
+            let remote = f64::from(i) * 20.0;
+            let jitter = f64::from(i % 3) * 0.005;
+            estimator.observe(
+                SENDER,
+                Duration::from_secs_f64(remote * (1.0 + drift) + 20.0 + jitter),
+                remote_at(remote),
+            );
+        }
+
+        let estimate = estimator.estimate(SENDER).unwrap();
+        assert!((estimate.drift - drift).abs() < 1e-5);
+        assert!((estimate.offset - 20.0).abs() < 5e-3);
+    }
+
+    #[test]
+    fn zero_min_drift_span() {
+        let mut estimator = ClockOffsetEstimator::new(
+            ClockOffsetSettings::default().with_min_drift_span(Duration::ZERO),
+        );
+
+        estimator.observe(SENDER, Duration::from_secs(110), remote_at(10.0));
+        estimator.observe(SENDER, Duration::from_secs(111), remote_at(10.0));
+
+        let estimate = estimator.estimate(SENDER).unwrap();
+        assert_eq!(estimate.drift, 0.0);
+        assert!((estimate.offset - 100.0).abs() < 1e-3);
+        let local = estimator.to_local(SENDER, remote_at(15.0)).unwrap();
+        assert!((local.as_secs_f64() - 105.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn conversion_across_hour_rollover() {
+        let mut estimator = ClockOffsetEstimator::default();
+
+        estimator.observe(SENDER, Duration::from_secs(10), remote_at(3_590.0));
+        let local = estimator
+            .observe(SENDER, Duration::from_secs(30), remote_at(3_610.0))
+            .unwrap();
+
+        assert!((local.as_secs_f64() - 30.0).abs() < 1e-3);
+        let late = estimator.to_local(SENDER, remote_at(3_595.0)).unwrap();
+        assert!((late.as_secs_f64() - 15.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn unknown_sender() {
+        let mut estimator = ClockOffsetEstimator::default();
+        assert_eq!(estimator.to_local(SENDER, remote_at(1.0)), None);
+
+        estimator.observe(SENDER, Duration::from_secs(10), remote_at(1.0));
+        estimator.remove(SENDER);
+        assert_eq!(estimator.estimate(SENDER), None);
+    }
+}
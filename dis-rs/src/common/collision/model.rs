@@ -10,6 +10,7 @@
 
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Collision {
     pub issuing_entity_id: EntityId,
     pub colliding_entity_id: EntityId,
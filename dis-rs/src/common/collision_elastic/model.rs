@@ -13,6 +13,7 @@
 /// 7.2.4 Collision-Elastic PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CollisionElastic {
     pub issuing_entity_id: EntityId,
     pub colliding_entity_id: EntityId,
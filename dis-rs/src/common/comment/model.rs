@@ -17,6 +17,7 @@
 /// 7.5.13 Comment PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Comment {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -17,6 +17,7 @@
 /// 7.11.13 Comment-R PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CommentR {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
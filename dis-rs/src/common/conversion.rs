@@ -0,0 +1,503 @@
+//! Explicit conversion of PDUs between the supported protocol versions (IEEE 1278.1a-1998 and IEEE 1278.1-2012).
+//!
+//! The model of this crate follows v7, and v6 PDUs are stored in the same model.
+//! Which fields are written to the wire is determined by the `protocol_version` of the `PduHeader`.
+//! Converting a PDU to another version using [`Pdu::convert_to`] adapts the fields that differ between the versions,
+//! and reports every field that is dropped, approximated or defaulted as a [`ConversionLoss`].
+use crate::common::errors::DisError;
+use crate::common::model::{Pdu, PduBody};
+use crate::common::{BodyInfo, SupportedVersion};
+use crate::enumerations::{PduType, ProtocolVersion};
+use crate::v7::model::PduStatus;
+use alloc::vec::Vec;
+
+/// A field of a PDU that could not be converted to the target protocol version without loss.
+///
+/// Fields are identified by their path in the model, e.g. `header.pdu_status` or `transmitter.variable_transmitter_parameters`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConversionLoss {
+    /// The field has no equivalent in the target version, and its value is discarded.
+    Dropped(&'static str),
+    /// The field is replaced by the closest equivalent in the target version, losing (part of) its information.
+    Approximated(&'static str),
+    /// The field does not exist in the source version, and is set to its default value.
+    Defaulted(&'static str),
+}
+
+impl ConversionLoss {
+    /// The path of the affected field.
+    #[must_use]
+    pub fn field(&self) -> &'static str {
+        match self {
+            ConversionLoss::Dropped(field)
+            | ConversionLoss::Approximated(field)
+            | ConversionLoss::Defaulted(field) => field,
+        }
+    }
+}
+
+/// The result of converting a PDU to another protocol version.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvertedPdu {
+    pub pdu: Pdu,
+    pub losses: Vec<ConversionLoss>,
+}
+
+impl ConvertedPdu {
+    /// Indicates whether the conversion preserved all information.
+    #[must_use]
+    pub fn is_lossless(&self) -> bool {
+        self.losses.is_empty()
+    }
+}
+
+impl Pdu {
+    /// Converts this PDU to protocol version `version`.
+    ///
+    /// Returns the converted PDU, together with the fields that are lost, approximated or defaulted in the process.
+    /// Converting to the current version of the PDU results in an identical PDU, without losses.
+    ///
+    /// # Errors
+    /// Returns `DisError::UnsupportedProtocolVersion` when `version` is not supported by this crate,
+    /// and `DisError::UnsupportedPduType` when the PDU type is not defined in `version`.
+    pub fn convert_to(&self, version: ProtocolVersion) -> Result<ConvertedPdu, DisError> {
+        let target = SupportedVersion::from(version);
+        if matches!(target, SupportedVersion::Unsupported) {
+            return Err(DisError::UnsupportedProtocolVersion(version));
+        }
+
+        let mut pdu = self.clone();
+        let mut losses = Vec::new();
+
+        if self.header.protocol_version != version {
+            match target {
+                SupportedVersion::V6 => {
+                    if is_v7_only(self.header.pdu_type) {
+                        return Err(DisError::UnsupportedPduType(self.header.pdu_type, version));
+                    }
+                    if let Some(status) = pdu.header.pdu_status.take() {
+                        pdu_status_losses(&status, &mut losses);
+                    }
+                }
+                SupportedVersion::V7 => {
+                    if pdu.header.pdu_status.is_none() {
+                        pdu.header.pdu_status = Some(PduStatus::default());
+                        losses.push(ConversionLoss::Defaulted("header.pdu_status"));
+                    }
+                }
+                SupportedVersion::Unsupported => {}
+            }
+
+            match &mut pdu.body {
+                PduBody::EntityState(body) => body.convert_to(&target, &mut losses),
+                PduBody::Fire(body) => body.convert_to(&target, &mut losses),
+                PduBody::Detonation(body) => body.convert_to(&target, &mut losses),
+                PduBody::Transmitter(body) => body.convert_to(&target, &mut losses),
+                // The remaining bodies are encoded the same in both versions, or are not modelled by this crate
+                // (`Other` and the body-less variants) and keep their content as is.
+                PduBody::Other(_)
+                | PduBody::Collision(_)
+                | PduBody::ServiceRequest(_)
+                | PduBody::ResupplyOffer(_)
+                | PduBody::ResupplyReceived(_)
+                | PduBody::ResupplyCancel(_)
+                | PduBody::RepairComplete(_)
+                | PduBody::RepairResponse(_)
+                | PduBody::CreateEntity(_)
+                | PduBody::RemoveEntity(_)
+                | PduBody::StartResume(_)
+                | PduBody::StopFreeze(_)
+                | PduBody::Acknowledge(_)
+                | PduBody::ActionRequest(_)
+                | PduBody::ActionResponse(_)
+                | PduBody::DataQuery(_)
+                | PduBody::SetData(_)
+                | PduBody::Data(_)
+                | PduBody::EventReport(_)
+                | PduBody::Comment(_)
+                | PduBody::ElectromagneticEmission(_)
+                | PduBody::Designator(_)
+                | PduBody::Signal(_)
+                | PduBody::Receiver(_)
+                | PduBody::IFF(_)
+                | PduBody::UnderwaterAcoustic(_)
+                | PduBody::SupplementalEmissionEntityState(_)
+                | PduBody::IntercomSignal
+                | PduBody::IntercomControl
+                | PduBody::AggregateState(_)
+                | PduBody::IsGroupOf(_)
+                | PduBody::TransferOwnership(_)
+                | PduBody::IsPartOf(_)
+                | PduBody::MinefieldState
+                | PduBody::MinefieldQuery
+                | PduBody::MinefieldData
+                | PduBody::MinefieldResponseNACK
+                | PduBody::EnvironmentalProcess
+                | PduBody::GriddedData
+                | PduBody::PointObjectState
+                | PduBody::LinearObjectState
+                | PduBody::ArealObjectState
+                | PduBody::TSPI
+                | PduBody::Appearance
+                | PduBody::ArticulatedParts
+                | PduBody::LEFire
+                | PduBody::LEDetonation
+                | PduBody::CreateEntityR(_)
+                | PduBody::RemoveEntityR(_)
+                | PduBody::StartResumeR(_)
+                | PduBody::StopFreezeR(_)
+                | PduBody::AcknowledgeR(_)
+                | PduBody::ActionRequestR(_)
+                | PduBody::ActionResponseR(_)
+                | PduBody::DataQueryR(_)
+                | PduBody::SetDataR(_)
+                | PduBody::DataR(_)
+                | PduBody::EventReportR(_)
+                | PduBody::CommentR(_)
+                | PduBody::RecordR(_)
+                | PduBody::SetRecordR(_)
+                | PduBody::RecordQueryR(_)
+                | PduBody::CollisionElastic(_)
+                | PduBody::EntityStateUpdate(_)
+                | PduBody::DirectedEnergyFire
+                | PduBody::EntityDamageStatus
+                | PduBody::InformationOperationsAction
+                | PduBody::InformationOperationsReport
+                | PduBody::Attribute(_)
+                | PduBody::Custom(_) => {}
+            }
+
+            pdu.header.protocol_version = version;
+            pdu.header = pdu.header.with_length(pdu.body.body_length());
+        }
+
+        Ok(ConvertedPdu { pdu, losses })
+    }
+}
+
+/// Indicates whether `pdu_type` is only defined in v7, based on the header rather than the body
+/// so that PDUs received as an `Other` body are covered as well.
+fn is_v7_only(pdu_type: PduType) -> bool {
+    matches!(
+        pdu_type,
+        PduType::DirectedEnergyFire
+            | PduType::EntityDamageStatus
+            | PduType::InformationOperationsAction
+            | PduType::InformationOperationsReport
+            | PduType::Attribute
+    )
+}
+
+/// Reports the indicators of a v7 `PduStatus` that are dropped when converting to v6.
+///
+/// The Fire and Detonation Type Indicators are left out, as they follow from the descriptor of the body,
+/// which is converted (and reported) separately.
+fn pdu_status_losses(status: &PduStatus, losses: &mut Vec<ConversionLoss>) {
+    let PduStatus {
+        transferred_entity_indicator,
+        lvc_indicator,
+        coupled_extension_indicator,
+        fire_type_indicator: _,
+        detonation_type_indicator: _,
+        radio_attached_indicator,
+        intercom_attached_indicator,
+        iff_simulation_mode,
+        active_interrogation_indicator,
+    } = status;
+    let indicators = [
+        (
+            transferred_entity_indicator.is_some(),
+            "header.pdu_status.transferred_entity_indicator",
+        ),
+        (lvc_indicator.is_some(), "header.pdu_status.lvc_indicator"),
+        (
+            coupled_extension_indicator.is_some(),
+            "header.pdu_status.coupled_extension_indicator",
+        ),
+        (
+            radio_attached_indicator.is_some(),
+            "header.pdu_status.radio_attached_indicator",
+        ),
+        (
+            intercom_attached_indicator.is_some(),
+            "header.pdu_status.intercom_attached_indicator",
+        ),
+        (
+            iff_simulation_mode.is_some(),
+            "header.pdu_status.iff_simulation_mode",
+        ),
+        (
+            active_interrogation_indicator.is_some(),
+            "header.pdu_status.active_interrogation_indicator",
+        ),
+    ];
+    losses.extend(
+        indicators
+            .into_iter()
+            .filter(|(is_set, _)| *is_set)
+            .map(|(_, field)| ConversionLoss::Dropped(field)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyRaw;
+    use crate::common::attribute::model::Attribute;
+    use crate::common::detonation::model::{Detonation, DetonationDescriptor};
+    use crate::common::entity_state::model::EntityState;
+    use crate::common::fire::model::{Fire, FireDescriptor};
+    use crate::common::model::{
+        EntityType, ExpendableDescriptor, ExplosionDescriptor, MunitionDescriptor, PduHeader,
+        TimeUnits, Timestamp,
+    };
+    use crate::common::other::model::Other;
+    use crate::common::signal::model::Signal;
+    use crate::common::transmitter::model::{Transmitter, VariableTransmitterParameter};
+    use crate::enumerations::{
+        DetonationTypeIndicator, EntityCapabilities, EntityKind, FireTypeIndicator, LvcIndicator,
+        PlatformDomain, RadioAttachedIndicator, VariableRecordType,
+    };
+
+    fn timestamp() -> Timestamp {
+        Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap())
+    }
+
+    #[test]
+    fn same_version_is_lossless() {
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::EntityState),
+            EntityState::builder().build().into_pdu_body(),
+            timestamp(),
+        );
+
+        let converted = pdu.convert_to(ProtocolVersion::IEEE1278_12012).unwrap();
+        assert!(converted.is_lossless());
+        assert_eq!(converted.pdu, pdu);
+    }
+
+    #[test]
+    fn v7_to_v6_drops_v7_only_fields() {
+        let header = PduHeader::new_v7(1, PduType::Transmitter)
+            .with_pdu_status(PduStatus::default().with_lvc_indicator(LvcIndicator::Live));
+        let body = Transmitter::builder()
+            .with_variable_transmitter_parameter(
+                VariableTransmitterParameter::default()
+                    .with_record_type(VariableRecordType::Age_34100)
+                    .with_fields(vec![0xFF, 0xEE]),
+            )
+            .build()
+            .into_pdu_body();
+        let pdu = Pdu::finalize_from_parts(header, body, timestamp());
+
+        let converted = pdu.convert_to(ProtocolVersion::IEEE1278_1A1998).unwrap();
+
+        assert_eq!(
+            converted.losses,
+            vec![
+                ConversionLoss::Dropped("header.pdu_status.lvc_indicator"),
+                ConversionLoss::Dropped("transmitter.variable_transmitter_parameters"),
+            ]
+        );
+        assert_eq!(
+            converted.pdu.header.protocol_version,
+            ProtocolVersion::IEEE1278_1A1998
+        );
+        assert!(converted.pdu.header.pdu_status.is_none());
+        assert_eq!(converted.pdu.header.pdu_length, converted.pdu.pdu_length());
+        assert!(converted.pdu.header.pdu_length < pdu.header.pdu_length);
+    }
+
+    #[test]
+    fn v6_to_v7_defaults_v7_only_fields() {
+        let entity_type = EntityType::default()
+            .with_kind(EntityKind::Platform)
+            .with_domain(PlatformDomain::Air);
+        let body = EntityState::builder()
+            .with_entity_type(entity_type)
+            .with_capabilities_flags(true, true, false, false)
+            .build()
+            .into_pdu_body();
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v6(1, PduType::EntityState),
+            body,
+            timestamp(),
+        );
+
+        let converted = pdu.convert_to(ProtocolVersion::IEEE1278_12012).unwrap();
+
+        assert_eq!(
+            converted.losses,
+            vec![ConversionLoss::Defaulted("header.pdu_status")]
+        );
+        let PduBody::EntityState(body) = converted.pdu.body else {
+            panic!("Expected an EntityState body");
+        };
+        let EntityCapabilities::AirPlatformEntityCapabilities(capabilities) =
+            body.entity_capabilities
+        else {
+            panic!("Expected air platform capabilities");
+        };
+        assert!(capabilities.ammunition_supply);
+        assert!(capabilities.fuel_supply);
+        assert!(!capabilities.recovery);
+    }
+
+    #[test]
+    fn v7_only_pdu_type_to_v6() {
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::Attribute),
+            Attribute::builder().build().into_pdu_body(),
+            timestamp(),
+        );
+
+        assert_eq!(
+            pdu.convert_to(ProtocolVersion::IEEE1278_1A1998),
+            Err(DisError::UnsupportedPduType(
+                PduType::Attribute,
+                ProtocolVersion::IEEE1278_1A1998
+            ))
+        );
+    }
+
+    #[test]
+    fn v7_only_pdu_type_in_other_body_to_v6() {
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::InformationOperationsReport),
+            Other::builder().build().into_pdu_body(),
+            timestamp(),
+        );
+
+        assert_eq!(
+            pdu.convert_to(ProtocolVersion::IEEE1278_1A1998),
+            Err(DisError::UnsupportedPduType(
+                PduType::InformationOperationsReport,
+                ProtocolVersion::IEEE1278_1A1998
+            ))
+        );
+    }
+
+    #[test]
+    fn v7_to_v6_drops_pdu_status_of_other_pdu_types() {
+        let header = PduHeader::new_v7(1, PduType::Signal).with_pdu_status(
+            PduStatus::default()
+                .with_lvc_indicator(LvcIndicator::Virtual)
+                .with_radio_attached_indicator(RadioAttachedIndicator::Attached),
+        );
+        let pdu = Pdu::finalize_from_parts(
+            header,
+            Signal::builder().build().into_pdu_body(),
+            timestamp(),
+        );
+
+        let converted = pdu.convert_to(ProtocolVersion::IEEE1278_1A1998).unwrap();
+
+        assert_eq!(
+            converted.losses,
+            vec![
+                ConversionLoss::Dropped("header.pdu_status.lvc_indicator"),
+                ConversionLoss::Dropped("header.pdu_status.radio_attached_indicator"),
+            ]
+        );
+        assert_eq!(converted.pdu.body, pdu.body);
+    }
+
+    #[test]
+    fn fire_expendable_descriptor_to_v6() {
+        let entity_type = EntityType::default()
+            .with_kind(EntityKind::Expendable)
+            .with_domain(PlatformDomain::Air);
+        let header = PduHeader::new_v7(1, PduType::Fire).with_pdu_status(
+            PduStatus::default().with_fire_type_indicator(FireTypeIndicator::Expendable),
+        );
+        let body = Fire::builder()
+            .with_descriptor(FireDescriptor::Expendable(
+                ExpendableDescriptor::default().with_entity_type(entity_type),
+            ))
+            .build()
+            .into_pdu_body();
+        let pdu = Pdu::finalize_from_parts(header, body, timestamp());
+
+        let converted = pdu.convert_to(ProtocolVersion::IEEE1278_1A1998).unwrap();
+
+        assert_eq!(
+            converted.losses,
+            vec![ConversionLoss::Approximated("fire.descriptor")]
+        );
+        let PduBody::Fire(fire) = converted.pdu.body else {
+            panic!("Expected a Fire body");
+        };
+        assert_eq!(
+            fire.descriptor,
+            FireDescriptor::Munition(MunitionDescriptor::default().with_entity_type(entity_type))
+        );
+    }
+
+    #[test]
+    fn detonation_explosion_descriptor_to_v6() {
+        let entity_type = EntityType::default()
+            .with_kind(EntityKind::Munition)
+            .with_domain(PlatformDomain::Land);
+        let header = PduHeader::new_v7(1, PduType::Detonation).with_pdu_status(
+            PduStatus::default()
+                .with_detonation_type_indicator(DetonationTypeIndicator::NonmunitionExplosion),
+        );
+        let body = Detonation::builder()
+            .with_descriptor(DetonationDescriptor::Explosion(
+                ExplosionDescriptor::default()
+                    .with_entity_type(entity_type)
+                    .with_explosive_force(2.5),
+            ))
+            .build()
+            .into_pdu_body();
+        let pdu = Pdu::finalize_from_parts(header, body, timestamp());
+
+        let converted = pdu.convert_to(ProtocolVersion::IEEE1278_1A1998).unwrap();
+
+        assert_eq!(
+            converted.losses,
+            vec![ConversionLoss::Approximated("detonation.descriptor")]
+        );
+        let PduBody::Detonation(detonation) = converted.pdu.body else {
+            panic!("Expected a Detonation body");
+        };
+        assert_eq!(
+            detonation.descriptor,
+            DetonationDescriptor::Munition(
+                MunitionDescriptor::default().with_entity_type(entity_type)
+            )
+        );
+    }
+
+    #[test]
+    fn detonation_munition_descriptor_to_v6_is_lossless() {
+        let header = PduHeader::new_v7(1, PduType::Detonation).with_pdu_status(
+            PduStatus::default().with_detonation_type_indicator(DetonationTypeIndicator::Munition),
+        );
+        let pdu = Pdu::finalize_from_parts(
+            header,
+            Detonation::builder().build().into_pdu_body(),
+            timestamp(),
+        );
+
+        let converted = pdu.convert_to(ProtocolVersion::IEEE1278_1A1998).unwrap();
+
+        assert!(converted.is_lossless());
+        assert_eq!(converted.pdu.body, pdu.body);
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::EntityState),
+            EntityState::builder().build().into_pdu_body(),
+            timestamp(),
+        );
+        let version = ProtocolVersion::from(5);
+
+        assert_eq!(
+            pdu.convert_to(version),
+            Err(DisError::UnsupportedProtocolVersion(version))
+        );
+    }
+}
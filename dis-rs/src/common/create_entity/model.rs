@@ -13,6 +13,7 @@
 /// 7.5.2 Create Entity PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CreateEntity {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
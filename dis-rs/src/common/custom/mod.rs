@@ -0,0 +1,422 @@
+//! User-defined PDU types, in the range of PDU types reserved for experimentation (129-255).
+//!
+//! The body of a custom PDU type implements [`CustomPdu`](model::CustomPdu), and is registered for its PDU type
+//! in a [`CustomPduRegistry`](registry::CustomPduRegistry). PDUs parsed using [`parse_with_registry`](crate::parse_with_registry)
+//! then have a `PduBody::Custom` body, which is serialized and queried (`BodyInfo`, `Interaction`) like any other body.
+//!
+//! ```
+//! # use bytes::{BufMut, BytesMut};
+//! # use dis_rs::custom::model::CustomPdu;
+//! # use dis_rs::custom::registry::CustomPduRegistry;
+//! # use dis_rs::enumerations::PduType;
+//! # use dis_rs::errors::DisError;
+//! # use dis_rs::model::{EntityId, Pdu, PduBody, PduHeader, Timestamp};
+//! # use dis_rs::{BodyInfo, Interaction, SerializePdu, SupportedVersion};
+//! #[derive(Clone, Debug, PartialEq)]
+//! struct Heartbeat {
+//!     sequence: u32,
+//! }
+//!
+//! impl BodyInfo for Heartbeat {
+//!     fn body_length(&self) -> u16 {
+//!         4
+//!     }
+//!
+//!     fn body_type(&self) -> PduType {
+//!         PduType::from(200u8)
+//!     }
+//! }
+//!
+//! impl Interaction for Heartbeat {
+//!     fn originator(&self) -> Option<&EntityId> {
+//!         None
+//!     }
+//!
+//!     fn receiver(&self) -> Option<&EntityId> {
+//!         None
+//!     }
+//! }
+//!
+//! impl SerializePdu for Heartbeat {
+//!     fn serialize_pdu(&self, _version: SupportedVersion, buf: &mut BytesMut) -> u16 {
+//!         buf.put_u32(self.sequence);
+//!         4
+//!     }
+//! }
+//!
+//! impl CustomPdu for Heartbeat {
+//!     fn parse_body(_header: &PduHeader, input: &[u8]) -> Result<Self, DisError> {
+//!         let sequence = input
+//!             .try_into()
+//!             .map(u32::from_be_bytes)
+//!             .map_err(|_| DisError::ParseError("Invalid Heartbeat PDU".to_string()))?;
+//!         Ok(Self { sequence })
+//!     }
+//! }
+//!
+//! let mut registry = CustomPduRegistry::new();
+//! registry.register::<Heartbeat>(PduType::from(200u8)).unwrap();
+//!
+//! let pdu = Pdu::finalize_from_parts(
+//!     PduHeader::new_v7(1, PduType::from(200u8)),
+//!     PduBody::Custom(Box::new(Heartbeat { sequence: 7 })),
+//!     Timestamp::new(0),
+//! );
+//! let mut buf = BytesMut::with_capacity(pdu.pdu_length() as usize);
+//! pdu.serialize(&mut buf).unwrap();
+//!
+//! let parsed = dis_rs::parse_with_registry(&buf, &registry).unwrap();
+//! let PduBody::Custom(body) = &parsed[0].body else {
+//!     panic!("Expected a custom body");
+//! };
+//! assert_eq!(body.downcast_ref::<Heartbeat>(), Some(&Heartbeat { sequence: 7 }));
+//! ```
+pub mod model;
+pub mod registry;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::custom::model::{CustomPdu, CustomVariableParameter};
+    use crate::common::custom::registry::CustomPduRegistry;
+    use crate::common::entity_state::model::EntityState;
+    use crate::common::model::{
+        EntityId, Pdu, PduBody, PduHeader, TimeUnits, Timestamp, VariableParameter,
+    };
+    use crate::common::parser::entity_id;
+    use crate::common::remapping::IdVisitor;
+    use crate::common::{
+        BodyInfo, BodyRaw, Interaction, Serialize, SerializePdu, SupportedVersion,
+    };
+    use crate::constants::FIFTEEN_OCTETS;
+    use crate::enumerations::{PduType, VariableParameterRecordType};
+    use crate::errors::DisError;
+    use alloc::boxed::Box;
+    use alloc::string::ToString;
+    use bytes::{BufMut, BytesMut};
+    use nom::number::complete::be_u32;
+
+    const TRACK_PDU_TYPE: u8 = 201;
+    const FORMATION_RECORD_TYPE: u8 = 240;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Track {
+        reporting_entity_id: EntityId,
+        tracked_entity_id: EntityId,
+        track_number: u32,
+    }
+
+    impl BodyInfo for Track {
+        fn body_length(&self) -> u16 {
+            16
+        }
+
+        fn body_type(&self) -> PduType {
+            PduType::from(TRACK_PDU_TYPE)
+        }
+    }
+
+    impl Interaction for Track {
+        fn originator(&self) -> Option<&EntityId> {
+            Some(&self.reporting_entity_id)
+        }
+
+        fn receiver(&self) -> Option<&EntityId> {
+            Some(&self.tracked_entity_id)
+        }
+    }
+
+    impl SerializePdu for Track {
+        fn serialize_pdu(&self, _version: SupportedVersion, buf: &mut BytesMut) -> u16 {
+            let reporting_bytes = self.reporting_entity_id.serialize(buf);
+            let tracked_bytes = self.tracked_entity_id.serialize(buf);
+            buf.put_u32(self.track_number);
+            reporting_bytes + tracked_bytes + 4
+        }
+    }
+
+    impl CustomPdu for Track {
+        fn parse_body(_header: &PduHeader, input: &[u8]) -> Result<Self, DisError> {
+            if input.len() != 16 {
+                return Err(DisError::ParseError("Invalid Track PDU".to_string()));
+            }
+            let (input, reporting_entity_id) =
+                entity_id(input).map_err(|err| DisError::ParseError(err.to_string()))?;
+            let (input, tracked_entity_id) =
+                entity_id(input).map_err(|err| DisError::ParseError(err.to_string()))?;
+            let (_input, track_number) = be_u32::<&[u8], nom::error::Error<&[u8]>>(input)
+                .map_err(|err| DisError::ParseError(err.to_string()))?;
+            Ok(Self {
+                reporting_entity_id,
+                tracked_entity_id,
+                track_number,
+            })
+        }
+
+        fn visit_ids_mut(&mut self, visitor: &mut dyn IdVisitor) {
+            visitor.visit_entity_id(&mut self.reporting_entity_id);
+            visitor.visit_entity_id(&mut self.tracked_entity_id);
+        }
+    }
+
+    /// The position of an entity in a formation, led by another entity.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Formation {
+        leader_id: EntityId,
+        position: u8,
+    }
+
+    impl CustomVariableParameter for Formation {
+        fn record_type(&self) -> VariableParameterRecordType {
+            VariableParameterRecordType::from(FORMATION_RECORD_TYPE)
+        }
+
+        fn parse_record(
+            _record_type: VariableParameterRecordType,
+            input: &[u8; FIFTEEN_OCTETS],
+        ) -> Result<Self, DisError> {
+            let (input, leader_id) =
+                entity_id(input).map_err(|err| DisError::ParseError(err.to_string()))?;
+            Ok(Self {
+                leader_id,
+                position: input[0],
+            })
+        }
+
+        fn serialize_record(&self) -> [u8; FIFTEEN_OCTETS] {
+            let mut buf = BytesMut::with_capacity(FIFTEEN_OCTETS);
+            self.leader_id.serialize(&mut buf);
+            buf.put_u8(self.position);
+            buf.put_bytes(0, FIFTEEN_OCTETS - buf.len());
+            let mut record = [0u8; FIFTEEN_OCTETS];
+            record.copy_from_slice(&buf);
+            record
+        }
+
+        fn visit_ids_mut(&mut self, visitor: &mut dyn IdVisitor) {
+            visitor.visit_entity_id(&mut self.leader_id);
+        }
+    }
+
+    fn formation() -> Formation {
+        Formation {
+            leader_id: EntityId::new(1, 2, 3),
+            position: 4,
+        }
+    }
+
+    fn serialized_formation_entity_state() -> BytesMut {
+        let body = EntityState::builder()
+            .with_entity_id(EntityId::new(1, 2, 5))
+            .with_variable_parameter(VariableParameter::Custom(Box::new(formation())))
+            .build();
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::EntityState),
+            body.into_pdu_body(),
+            Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap()),
+        );
+        let mut buf = BytesMut::with_capacity(pdu.pdu_length() as usize);
+        pdu.serialize(&mut buf).unwrap();
+        buf
+    }
+
+    fn track() -> Track {
+        Track {
+            reporting_entity_id: EntityId::new(1, 2, 3),
+            tracked_entity_id: EntityId::new(4, 5, 6),
+            track_number: 42,
+        }
+    }
+
+    fn serialized_track_pdu() -> BytesMut {
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::from(TRACK_PDU_TYPE)),
+            PduBody::Custom(Box::new(track())),
+            Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap()),
+        );
+        let mut buf = BytesMut::with_capacity(pdu.pdu_length() as usize);
+        pdu.serialize(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn custom_pdu_round_trip() {
+        let mut registry = CustomPduRegistry::new();
+        registry
+            .register::<Track>(PduType::from(TRACK_PDU_TYPE))
+            .unwrap();
+
+        let buf = serialized_track_pdu();
+        assert_eq!(buf.len(), 28);
+
+        let pdus = crate::parse_with_registry(&buf, &registry).unwrap();
+        let pdu = pdus.first().unwrap();
+        assert_eq!(pdu.header.pdu_type, PduType::from(TRACK_PDU_TYPE));
+        assert_eq!(pdu.header.pdu_length, 28);
+        assert_eq!(pdu.body.body_length(), 16);
+        assert_eq!(pdu.originator(), Some(&EntityId::new(1, 2, 3)));
+        assert_eq!(pdu.receiver(), Some(&EntityId::new(4, 5, 6)));
+        assert_eq!(pdu.body, PduBody::Custom(Box::new(track())));
+
+        let PduBody::Custom(body) = &pdu.body else {
+            panic!("Expected a custom body");
+        };
+        assert_eq!(body.downcast_ref::<Track>(), Some(&track()));
+    }
+
+    #[test]
+    fn unregistered_type_is_other() {
+        let buf = serialized_track_pdu();
+
+        let pdus = crate::parse(&buf).unwrap();
+        let PduBody::Other(body) = &pdus.first().unwrap().body else {
+            panic!("Expected an Other body");
+        };
+        assert_eq!(body.body.len(), 16);
+
+        let pdus = crate::parse_with_registry(&buf, &CustomPduRegistry::new()).unwrap();
+        assert!(matches!(pdus.first().unwrap().body, PduBody::Other(_)));
+    }
+
+    #[test]
+    fn invalid_custom_body() {
+        let mut buf = serialized_track_pdu();
+        // shorten the body to 12 bytes, so that the track number is missing
+        buf.truncate(24);
+        buf[8..10].copy_from_slice(&24u16.to_be_bytes());
+
+        let mut registry = CustomPduRegistry::new();
+        registry
+            .register::<Track>(PduType::from(TRACK_PDU_TYPE))
+            .unwrap();
+        assert_eq!(
+            crate::parse_with_registry(&buf, &registry).err(),
+            Some(DisError::ParseError("Invalid Track PDU".to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid_custom_body_in_datagram() {
+        let mut invalid_track = serialized_track_pdu();
+        invalid_track.truncate(24);
+        invalid_track[8..10].copy_from_slice(&24u16.to_be_bytes());
+        let mut buf = serialized_formation_entity_state();
+        buf.extend_from_slice(&invalid_track);
+
+        let mut registry = CustomPduRegistry::new();
+        registry
+            .register::<Track>(PduType::from(TRACK_PDU_TYPE))
+            .unwrap();
+        // a single invalid PDU fails the whole datagram
+        assert_eq!(
+            crate::parse_with_registry(&buf, &registry).err(),
+            Some(DisError::ParseError("Invalid Track PDU".to_string()))
+        );
+
+        let mut pdus = crate::parse(&buf).unwrap();
+        assert_eq!(pdus.len(), 2);
+        assert!(registry.resolve(&mut pdus[0]).is_ok());
+        assert!(matches!(pdus[0].body, PduBody::EntityState(_)));
+        assert_eq!(
+            registry.resolve(&mut pdus[1]).err(),
+            Some(DisError::ParseError("Invalid Track PDU".to_string()))
+        );
+        assert!(matches!(pdus[1].body, PduBody::Other(_)));
+    }
+
+    #[test]
+    fn register_only_experimental_types() {
+        let mut registry = CustomPduRegistry::new();
+        assert_eq!(
+            registry.register::<Track>(PduType::EntityState).err(),
+            Some(DisError::NotExperimentalPduType(PduType::EntityState))
+        );
+        assert!(registry.register::<Track>(PduType::from(129u8)).is_ok());
+        assert!(registry.is_registered(PduType::from(129u8)));
+        assert!(!registry.is_registered(PduType::from(128u8)));
+    }
+
+    #[test]
+    fn remap_custom_body_ids() {
+        let mut body = PduBody::Custom(Box::new(track()));
+        body.map_entity_ids(|id| {
+            EntityId::new(9, id.simulation_address.application_id, id.entity_id)
+        });
+
+        assert_eq!(body.originator(), Some(&EntityId::new(9, 2, 3)));
+        assert_eq!(body.receiver(), Some(&EntityId::new(9, 5, 6)));
+    }
+
+    #[test]
+    fn custom_variable_parameter_round_trip() {
+        let mut registry = CustomPduRegistry::new();
+        registry
+            .register_variable_parameter::<Formation>(VariableParameterRecordType::from(
+                FORMATION_RECORD_TYPE,
+            ))
+            .unwrap();
+
+        let buf = serialized_formation_entity_state();
+        let pdus = crate::parse_with_registry(&buf, &registry).unwrap();
+        let PduBody::EntityState(body) = &pdus.first().unwrap().body else {
+            panic!("Expected an EntityState body");
+        };
+        let Some(VariableParameter::Custom(record)) = body.variable_parameters.first() else {
+            panic!("Expected a custom variable parameter");
+        };
+        assert_eq!(record.downcast_ref::<Formation>(), Some(&formation()));
+
+        let pdus = crate::parse(&buf).unwrap();
+        let PduBody::EntityState(body) = &pdus.first().unwrap().body else {
+            panic!("Expected an EntityState body");
+        };
+        assert_eq!(
+            body.variable_parameters.first(),
+            Some(&VariableParameter::Unspecified(
+                FORMATION_RECORD_TYPE,
+                formation().serialize_record()
+            ))
+        );
+    }
+
+    #[test]
+    fn register_only_non_standard_variable_parameter_types() {
+        let mut registry = CustomPduRegistry::new();
+        assert_eq!(
+            registry
+                .register_variable_parameter::<Formation>(
+                    VariableParameterRecordType::ArticulatedPart
+                )
+                .err(),
+            Some(DisError::StandardVariableParameterType(
+                VariableParameterRecordType::ArticulatedPart
+            ))
+        );
+        assert!(
+            !registry.is_variable_parameter_registered(VariableParameterRecordType::from(
+                FORMATION_RECORD_TYPE
+            ))
+        );
+    }
+
+    #[test]
+    fn remap_custom_variable_parameter_ids() {
+        let mut body = EntityState::builder()
+            .with_variable_parameter(VariableParameter::Custom(Box::new(formation())))
+            .build()
+            .into_pdu_body();
+        body.map_entity_ids(|id| {
+            EntityId::new(9, id.simulation_address.application_id, id.entity_id)
+        });
+
+        let PduBody::EntityState(body) = &body else {
+            panic!("Expected an EntityState body");
+        };
+        let Some(VariableParameter::Custom(record)) = body.variable_parameters.first() else {
+            panic!("Expected a custom variable parameter");
+        };
+        assert_eq!(
+            record.downcast_ref::<Formation>().unwrap().leader_id,
+            EntityId::new(9, 2, 3)
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::common::model::PduHeader;
+use crate::common::remapping::IdVisitor;
+use crate::common::{BodyInfo, Interaction, SerializePdu};
+use crate::constants::FIFTEEN_OCTETS;
+use crate::enumerations::VariableParameterRecordType;
+use crate::errors::DisError;
+use alloc::boxed::Box;
+use core::any::Any;
+use core::fmt::Debug;
+
+/// The body of a user-defined PDU type, in the range reserved for experimentation (129-255).
+///
+/// Like the bodies of the PDU types supported by the crate, a custom body implements [`BodyInfo`],
+/// [`Interaction`] and [`SerializePdu`], so that its length, type and the entities involved can be queried
+/// and it can be serialized as part of a `Pdu`. `BodyInfo::body_type` must return the (experimental) PDU type of the body.
+///
+/// Custom bodies are parsed when their PDU type is registered in a [`CustomPduRegistry`](crate::custom::registry::CustomPduRegistry).
+/// Implementations that are `Clone` and `PartialEq` get [`CustomPduObject`] for free.
+pub trait CustomPdu:
+    BodyInfo + Interaction + SerializePdu + CustomPduObject + Any + Debug + Send + Sync
+{
+    /// Parses the body of a PDU with header `header`. `input` contains exactly the body of the PDU,
+    /// as indicated by the PDU length field of the header.
+    ///
+    /// # Errors
+    /// Returns a `DisError` when `input` is not a valid body.
+    fn parse_body(header: &PduHeader, input: &[u8]) -> Result<Self, DisError>
+    where
+        Self: Sized;
+
+    /// Calls `visitor` for each identifier embedded in the body, see [`PduBody::visit_ids_mut`](crate::model::PduBody::visit_ids_mut).
+    ///
+    /// The default implementation visits nothing.
+    fn visit_ids_mut(&mut self, _visitor: &mut dyn IdVisitor) {}
+}
+
+/// Cloning and comparison of custom bodies behind a `Box<dyn CustomPdu>`.
+///
+/// Implemented for all `CustomPdu` types that are `Clone` and `PartialEq`.
+pub trait CustomPduObject {
+    fn clone_box(&self) -> Box<dyn CustomPdu>;
+    fn eq_dyn(&self, other: &dyn CustomPdu) -> bool;
+}
+
+impl<T: CustomPdu + Clone + PartialEq> CustomPduObject for T {
+    fn clone_box(&self) -> Box<dyn CustomPdu> {
+        Box::new(self.clone())
+    }
+
+    fn eq_dyn(&self, other: &dyn CustomPdu) -> bool {
+        other.downcast_ref::<T>().is_some_and(|other| self == other)
+    }
+}
+
+impl dyn CustomPdu {
+    /// Returns a reference to the body as a `T`, if it is of type `T`.
+    #[must_use]
+    pub fn downcast_ref<T: CustomPdu>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// Returns a mutable reference to the body as a `T`, if it is of type `T`.
+    pub fn downcast_mut<T: CustomPdu>(&mut self) -> Option<&mut T> {
+        (self as &mut dyn Any).downcast_mut()
+    }
+}
+
+impl Clone for Box<dyn CustomPdu> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl PartialEq for dyn CustomPdu {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_dyn(other)
+    }
+}
+
+/// A Variable Parameter record (6.2.94) of a record type that is not defined in SISO-REF-010,
+/// such as the record types of a federation agreement.
+///
+/// The record is 16 octets long: the record type designator, followed by 15 octets of record specific fields.
+/// Custom records are parsed when their record type is registered in a [`CustomPduRegistry`](crate::custom::registry::CustomPduRegistry).
+/// Implementations that are `Clone` and `PartialEq` get [`CustomVariableParameterObject`] for free.
+pub trait CustomVariableParameter:
+    CustomVariableParameterObject + Any + Debug + Send + Sync
+{
+    /// The record type designator of the record.
+    fn record_type(&self) -> VariableParameterRecordType;
+
+    /// Parses the record specific fields of a record of type `record_type`, following the record type designator.
+    ///
+    /// # Errors
+    /// Returns a `DisError` when `input` is not a valid record.
+    fn parse_record(
+        record_type: VariableParameterRecordType,
+        input: &[u8; FIFTEEN_OCTETS],
+    ) -> Result<Self, DisError>
+    where
+        Self: Sized;
+
+    /// Serializes the record specific fields, following the record type designator.
+    fn serialize_record(&self) -> [u8; FIFTEEN_OCTETS];
+
+    /// Calls `visitor` for each identifier embedded in the record.
+    ///
+    /// The default implementation visits nothing.
+    fn visit_ids_mut(&mut self, _visitor: &mut dyn IdVisitor) {}
+}
+
+/// Cloning and comparison of custom records behind a `Box<dyn CustomVariableParameter>`.
+///
+/// Implemented for all `CustomVariableParameter` types that are `Clone` and `PartialEq`.
+pub trait CustomVariableParameterObject {
+    fn clone_box(&self) -> Box<dyn CustomVariableParameter>;
+    fn eq_dyn(&self, other: &dyn CustomVariableParameter) -> bool;
+}
+
+impl<T: CustomVariableParameter + Clone + PartialEq> CustomVariableParameterObject for T {
+    fn clone_box(&self) -> Box<dyn CustomVariableParameter> {
+        Box::new(self.clone())
+    }
+
+    fn eq_dyn(&self, other: &dyn CustomVariableParameter) -> bool {
+        other.downcast_ref::<T>().is_some_and(|other| self == other)
+    }
+}
+
+impl dyn CustomVariableParameter {
+    /// Returns a reference to the record as a `T`, if it is of type `T`.
+    #[must_use]
+    pub fn downcast_ref<T: CustomVariableParameter>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    /// Returns a mutable reference to the record as a `T`, if it is of type `T`.
+    pub fn downcast_mut<T: CustomVariableParameter>(&mut self) -> Option<&mut T> {
+        (self as &mut dyn Any).downcast_mut()
+    }
+}
+
+impl Clone for Box<dyn CustomVariableParameter> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl PartialEq for dyn CustomVariableParameter {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_dyn(other)
+    }
+}
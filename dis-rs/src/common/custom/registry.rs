@@ -0,0 +1,146 @@
+use crate::common::custom::model::{CustomPdu, CustomVariableParameter};
+use crate::common::model::{Pdu, PduBody, PduHeader, VariableParameter};
+use crate::constants::FIFTEEN_OCTETS;
+use crate::enumerations::{PduType, VariableParameterRecordType};
+use crate::errors::DisError;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::ops::RangeInclusive;
+
+/// The range of PDU types reserved for experimentation, which can be registered as custom PDU types.
+pub const EXPERIMENTAL_PDU_TYPES: RangeInclusive<u8> = 129..=255;
+
+type CustomPduParser = fn(&PduHeader, &[u8]) -> Result<Box<dyn CustomPdu>, DisError>;
+type CustomVariableParameterParser = fn(
+    VariableParameterRecordType,
+    &[u8; FIFTEEN_OCTETS],
+) -> Result<Box<dyn CustomVariableParameter>, DisError>;
+
+/// Maps experimental PDU types to the [`CustomPdu`] type of their body,
+/// and non-standard variable parameter record types to their [`CustomVariableParameter`] type.
+///
+/// PDUs with a registered type are parsed as `PduBody::Custom` by [`parse_with_registry`](crate::parse_with_registry),
+/// all other PDUs are parsed as usual. Unregistered experimental PDU types are parsed as `PduBody::Other`.
+/// Likewise, variable parameter records with a registered record type are parsed as `VariableParameter::Custom`,
+/// in all PDUs that have variable parameters (Entity State, Entity State Update and Detonation).
+#[derive(Clone, Debug, Default)]
+pub struct CustomPduRegistry {
+    parsers: BTreeMap<u8, CustomPduParser>,
+    variable_parameters: BTreeMap<u8, CustomVariableParameterParser>,
+}
+
+impl CustomPduRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` as the body of PDUs of type `pdu_type`, replacing an earlier registration of `pdu_type`.
+    ///
+    /// # Errors
+    /// Returns `DisError::NotExperimentalPduType` when `pdu_type` is not in the experimental range (129-255).
+    pub fn register<T: CustomPdu>(&mut self, pdu_type: PduType) -> Result<&mut Self, DisError> {
+        let type_number = u8::from(pdu_type);
+        if !EXPERIMENTAL_PDU_TYPES.contains(&type_number) {
+            return Err(DisError::NotExperimentalPduType(pdu_type));
+        }
+        self.parsers.insert(type_number, parse_boxed::<T>);
+        Ok(self)
+    }
+
+    /// Indicates whether a body type is registered for `pdu_type`.
+    #[must_use]
+    pub fn is_registered(&self, pdu_type: PduType) -> bool {
+        self.parsers.contains_key(&u8::from(pdu_type))
+    }
+
+    /// Parses the `PduBody::Other` body of `pdu` as `PduBody::Custom` when its PDU type is registered,
+    /// and resolves the custom variable parameters of the body.
+    ///
+    /// This is applied by [`parse_with_registry`](crate::parse_with_registry) to all PDUs of the input.
+    ///
+    /// # Errors
+    /// Returns the `DisError` of the `CustomPdu` or `CustomVariableParameter` parser when the body is not valid.
+    /// An invalid body is left as `PduBody::Other`.
+    pub fn resolve(&self, pdu: &mut Pdu) -> Result<(), DisError> {
+        if let PduBody::Other(other) = &pdu.body
+            && let Some(parser) = self.parsers.get(&u8::from(pdu.header.pdu_type))
+        {
+            let body = parser(&pdu.header, &other.body)?;
+            pdu.body = PduBody::Custom(body);
+        }
+        self.resolve_variable_parameters(&mut pdu.body)
+    }
+
+    /// Registers `T` as the record of variable parameters of type `record_type`,
+    /// replacing an earlier registration of `record_type`.
+    ///
+    /// # Errors
+    /// Returns `DisError::StandardVariableParameterType` when `record_type` is defined by SISO-REF-010.
+    pub fn register_variable_parameter<T: CustomVariableParameter>(
+        &mut self,
+        record_type: VariableParameterRecordType,
+    ) -> Result<&mut Self, DisError> {
+        let VariableParameterRecordType::Unspecified(type_number) = record_type else {
+            return Err(DisError::StandardVariableParameterType(record_type));
+        };
+        self.variable_parameters
+            .insert(type_number, parse_variable_parameter_boxed::<T>);
+        Ok(self)
+    }
+
+    /// Indicates whether a record type is registered for `record_type`.
+    #[must_use]
+    pub fn is_variable_parameter_registered(
+        &self,
+        record_type: VariableParameterRecordType,
+    ) -> bool {
+        self.variable_parameters
+            .contains_key(&u8::from(record_type))
+    }
+
+    /// Replaces the `VariableParameter::Unspecified` records of `body` that have a registered record type
+    /// with the corresponding `VariableParameter::Custom` record.
+    ///
+    /// This is applied by [`parse_with_registry`](crate::parse_with_registry), and can be used
+    /// for bodies obtained otherwise, such as decoded C-DIS PDUs.
+    ///
+    /// # Errors
+    /// Returns a `DisError` when a record with a registered type is not valid.
+    pub fn resolve_variable_parameters(&self, body: &mut PduBody) -> Result<(), DisError> {
+        if self.variable_parameters.is_empty() {
+            return Ok(());
+        }
+        let variable_parameters = match body {
+            PduBody::EntityState(body) => &mut body.variable_parameters,
+            PduBody::EntityStateUpdate(body) => &mut body.variable_parameters,
+            PduBody::Detonation(body) => &mut body.variable_parameters,
+            _ => return Ok(()),
+        };
+        for parameter in variable_parameters.iter_mut() {
+            if let VariableParameter::Unspecified(type_number, record) = parameter
+                && let Some(parser) = self.variable_parameters.get(type_number)
+            {
+                let record = parser(VariableParameterRecordType::from(*type_number), record)?;
+                *parameter = VariableParameter::Custom(record);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_boxed<T: CustomPdu>(
+    header: &PduHeader,
+    input: &[u8],
+) -> Result<Box<dyn CustomPdu>, DisError> {
+    let body = T::parse_body(header, input)?;
+    Ok(Box::new(body))
+}
+
+fn parse_variable_parameter_boxed<T: CustomVariableParameter>(
+    record_type: VariableParameterRecordType,
+    input: &[u8; FIFTEEN_OCTETS],
+) -> Result<Box<dyn CustomVariableParameter>, DisError> {
+    let record = T::parse_record(record_type, input)?;
+    Ok(Box::new(record))
+}
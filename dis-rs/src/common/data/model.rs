@@ -15,6 +15,7 @@
 
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Data {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
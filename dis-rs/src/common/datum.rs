@@ -0,0 +1,765 @@
+//! Typed access to the values of Fixed Datum (6.2.37) and Variable Datum (6.2.93) records.
+//!
+//! Datum records only carry a `VariableRecordType` and a raw value (a `u32` or a byte buffer).
+//! The [`DatumRegistry`] maps datum IDs to a [`DatumCodec`] that decodes and encodes the values of the datum as a Rust type.
+//! Types implementing [`DatumValue`] are registered with their default codec using [`DatumRegistry::register`],
+//! other encodings (e.g. bit-packed values, see [`BitPackedCodec`]) using [`DatumRegistry::register_codec`].
+//!
+//! ```
+//! # use dis_rs::enumerations::VariableRecordType;
+//! # use dis_rs::model::FixedDatum;
+//! let datum = FixedDatum::try_from_typed(VariableRecordType::AirSpeed_240054, &125.5f32).unwrap();
+//! assert_eq!(datum.typed::<f32>(), Ok(125.5));
+//! assert!(datum.typed::<u32>().is_err());
+//! ```
+use crate::common::model::{FixedDatum, VariableDatum};
+use crate::enumerations::VariableRecordType;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::{Any, type_name};
+use core::marker::PhantomData;
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum DatumError {
+    #[error("Datum {0} has no registered codec.")]
+    UnregisteredDatum(VariableRecordType),
+    #[error("Datum {id} is registered as {registered}, but requested as {requested}.")]
+    TypeMismatch {
+        id: VariableRecordType,
+        registered: &'static str,
+        requested: &'static str,
+    },
+    #[error("A {0} value cannot be represented in a Fixed Datum record.")]
+    NotFixedSize(&'static str),
+    #[error("Expected a value of {expected} bytes, found {found} bytes.")]
+    InvalidLength { expected: usize, found: usize },
+    #[error("The value is not valid UTF-8 text.")]
+    InvalidText,
+    #[error("Bit fields with a total width of {0} bits do not fit in a 32-bit value.")]
+    BitFieldsTooWide(u32),
+    #[error("Expected {expected} bit fields, found {found}.")]
+    BitFieldCount { expected: usize, found: usize },
+    #[error("Value {value} of bit field {field} does not fit in {width} bits.")]
+    BitFieldOverflow { field: usize, value: u32, width: u8 },
+}
+
+/// Decodes and encodes the values of a datum as a `T`.
+///
+/// Values are encoded in network byte order (big-endian).
+/// In a Fixed Datum record, values of less than 32 bits are right-aligned in the 32-bit value field.
+pub trait DatumCodec<T>: Send + Sync {
+    /// Decodes the value of a Fixed Datum record.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when `value` is not a valid encoding, or `T` cannot be represented in a Fixed Datum record.
+    fn decode_fixed(&self, value: u32) -> Result<T, DatumError>;
+
+    /// Encodes `value` for a Fixed Datum record.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when `value` cannot be represented in a Fixed Datum record.
+    fn encode_fixed(&self, value: &T) -> Result<u32, DatumError>;
+
+    /// Decodes the value of a Variable Datum record.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when `value` is not a valid encoding.
+    fn decode_variable(&self, value: &[u8]) -> Result<T, DatumError>;
+
+    /// Encodes `value` for a Variable Datum record.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when `value` cannot be encoded.
+    fn encode_variable(&self, value: &T) -> Result<Vec<u8>, DatumError>;
+}
+
+/// A Rust type with a default encoding as a datum value, see [`ValueCodec`].
+///
+/// Implement this trait to register application specific types using [`DatumRegistry::register`].
+pub trait DatumValue: Sized {
+    /// Decodes the value of a Fixed Datum record.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the value cannot be represented in a Fixed Datum record.
+    fn from_fixed(value: u32) -> Result<Self, DatumError>;
+
+    /// Encodes the value for a Fixed Datum record.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the value cannot be represented in a Fixed Datum record.
+    fn to_fixed(&self) -> Result<u32, DatumError>;
+
+    /// Decodes the value of a Variable Datum record.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when `value` is not a valid encoding of this type.
+    fn from_variable(value: &[u8]) -> Result<Self, DatumError>;
+
+    /// Encodes the value for a Variable Datum record.
+    fn to_variable(&self) -> Vec<u8>;
+}
+
+fn fixed_size<const N: usize>(value: &[u8]) -> Result<[u8; N], DatumError> {
+    value.try_into().map_err(|_| DatumError::InvalidLength {
+        expected: N,
+        found: value.len(),
+    })
+}
+
+impl DatumValue for u32 {
+    fn from_fixed(value: u32) -> Result<Self, DatumError> {
+        Ok(value)
+    }
+
+    fn to_fixed(&self) -> Result<u32, DatumError> {
+        Ok(*self)
+    }
+
+    fn from_variable(value: &[u8]) -> Result<Self, DatumError> {
+        Ok(u32::from_be_bytes(fixed_size(value)?))
+    }
+
+    fn to_variable(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+}
+
+impl DatumValue for i32 {
+    fn from_fixed(value: u32) -> Result<Self, DatumError> {
+        Ok(value as i32)
+    }
+
+    fn to_fixed(&self) -> Result<u32, DatumError> {
+        Ok(*self as u32)
+    }
+
+    fn from_variable(value: &[u8]) -> Result<Self, DatumError> {
+        Ok(i32::from_be_bytes(fixed_size(value)?))
+    }
+
+    fn to_variable(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+}
+
+impl DatumValue for f32 {
+    fn from_fixed(value: u32) -> Result<Self, DatumError> {
+        Ok(f32::from_bits(value))
+    }
+
+    fn to_fixed(&self) -> Result<u32, DatumError> {
+        Ok(self.to_bits())
+    }
+
+    fn from_variable(value: &[u8]) -> Result<Self, DatumError> {
+        Ok(f32::from_be_bytes(fixed_size(value)?))
+    }
+
+    fn to_variable(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+}
+
+impl DatumValue for f64 {
+    fn from_fixed(_value: u32) -> Result<Self, DatumError> {
+        Err(DatumError::NotFixedSize(type_name::<Self>()))
+    }
+
+    fn to_fixed(&self) -> Result<u32, DatumError> {
+        Err(DatumError::NotFixedSize(type_name::<Self>()))
+    }
+
+    fn from_variable(value: &[u8]) -> Result<Self, DatumError> {
+        Ok(f64::from_be_bytes(fixed_size(value)?))
+    }
+
+    fn to_variable(&self) -> Vec<u8> {
+        self.to_be_bytes().to_vec()
+    }
+}
+
+/// Text, padded with trailing zero bytes; only available in Variable Datum records.
+impl DatumValue for String {
+    fn from_fixed(_value: u32) -> Result<Self, DatumError> {
+        Err(DatumError::NotFixedSize(type_name::<Self>()))
+    }
+
+    fn to_fixed(&self) -> Result<u32, DatumError> {
+        Err(DatumError::NotFixedSize(type_name::<Self>()))
+    }
+
+    fn from_variable(value: &[u8]) -> Result<Self, DatumError> {
+        let end = value
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |last| last + 1);
+        String::from_utf8(value[..end].to_vec()).map_err(|_| DatumError::InvalidText)
+    }
+
+    fn to_variable(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+}
+
+/// Uninterpreted octets.
+impl DatumValue for Vec<u8> {
+    fn from_fixed(value: u32) -> Result<Self, DatumError> {
+        Ok(value.to_be_bytes().to_vec())
+    }
+
+    fn to_fixed(&self) -> Result<u32, DatumError> {
+        Ok(u32::from_be_bytes(fixed_size(self)?))
+    }
+
+    fn from_variable(value: &[u8]) -> Result<Self, DatumError> {
+        Ok(value.to_vec())
+    }
+
+    fn to_variable(&self) -> Vec<u8> {
+        self.clone()
+    }
+}
+
+/// The codec of a [`DatumValue`] type, using the encoding defined by its `DatumValue` impl.
+#[derive(Debug)]
+pub struct ValueCodec<T>(PhantomData<fn() -> T>);
+
+impl<T> ValueCodec<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for ValueCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DatumValue> DatumCodec<T> for ValueCodec<T> {
+    fn decode_fixed(&self, value: u32) -> Result<T, DatumError> {
+        T::from_fixed(value)
+    }
+
+    fn encode_fixed(&self, value: &T) -> Result<u32, DatumError> {
+        value.to_fixed()
+    }
+
+    fn decode_variable(&self, value: &[u8]) -> Result<T, DatumError> {
+        T::from_variable(value)
+    }
+
+    fn encode_variable(&self, value: &T) -> Result<Vec<u8>, DatumError> {
+        Ok(value.to_variable())
+    }
+}
+
+/// The values of the bit fields of a bit-packed datum value, see [`BitPackedCodec`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitPacked(pub Vec<u32>);
+
+/// A codec for datum values that consist of bit fields packed in a 32-bit value.
+///
+/// Fields are packed most significant field first, and right-aligned in the 32-bit value.
+/// In Variable Datum records the value is encoded as 4 octets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitPackedCodec {
+    widths: Vec<u8>,
+}
+
+impl BitPackedCodec {
+    /// Creates a codec for bit fields of `widths` bits, most significant field first.
+    ///
+    /// # Errors
+    /// Returns `DatumError::BitFieldsTooWide` when the fields do not fit in 32 bits.
+    pub fn new(widths: impl Into<Vec<u8>>) -> Result<Self, DatumError> {
+        let widths = widths.into();
+        let total_width: u32 = widths.iter().map(|width| u32::from(*width)).sum();
+        if total_width > u32::BITS {
+            return Err(DatumError::BitFieldsTooWide(total_width));
+        }
+        Ok(Self { widths })
+    }
+
+    fn mask(width: u8) -> u32 {
+        u32::MAX
+            .checked_shr(u32::BITS - u32::from(width))
+            .unwrap_or(0)
+    }
+
+    fn unpack(&self, value: u32) -> BitPacked {
+        let mut shift: u32 = self.widths.iter().map(|width| u32::from(*width)).sum();
+        BitPacked(
+            self.widths
+                .iter()
+                .map(|width| {
+                    shift -= u32::from(*width);
+                    value.checked_shr(shift).unwrap_or(0) & Self::mask(*width)
+                })
+                .collect(),
+        )
+    }
+
+    fn pack(&self, value: &BitPacked) -> Result<u32, DatumError> {
+        if value.0.len() != self.widths.len() {
+            return Err(DatumError::BitFieldCount {
+                expected: self.widths.len(),
+                found: value.0.len(),
+            });
+        }
+        let mut packed = 0u64;
+        for (field, (value, width)) in value.0.iter().zip(&self.widths).enumerate() {
+            if *value > Self::mask(*width) {
+                return Err(DatumError::BitFieldOverflow {
+                    field,
+                    value: *value,
+                    width: *width,
+                });
+            }
+            packed = (packed << width) | u64::from(*value);
+        }
+        Ok(packed as u32)
+    }
+}
+
+impl DatumCodec<BitPacked> for BitPackedCodec {
+    fn decode_fixed(&self, value: u32) -> Result<BitPacked, DatumError> {
+        Ok(self.unpack(value))
+    }
+
+    fn encode_fixed(&self, value: &BitPacked) -> Result<u32, DatumError> {
+        self.pack(value)
+    }
+
+    fn decode_variable(&self, value: &[u8]) -> Result<BitPacked, DatumError> {
+        Ok(self.unpack(u32::from_be_bytes(fixed_size(value)?)))
+    }
+
+    fn encode_variable(&self, value: &BitPacked) -> Result<Vec<u8>, DatumError> {
+        Ok(self.pack(value)?.to_be_bytes().to_vec())
+    }
+}
+
+// The built-in datum IDs are a minimal sample of SISO-REF-010 datum IDs whose value encoding is unambiguous,
+// not a curated set of common datum IDs. Text, `f64`, bit-packed and other datum IDs are registered by the
+// application, using `DatumRegistry::register` or `DatumRegistry::register_codec`.
+
+/// Built-in datum IDs with an unsigned integer value.
+const BUILTIN_UNSIGNED_INTEGERS: [u32; 6] = [
+    11130, // Country (Entity Type)
+    24005, // 7.62mm M62 Quantity
+    24008, // 7.62mm M80 Quantity
+    24010, // 7.62mm Quantity
+    26000, // Vehicle Mass
+    34100, // Age
+];
+
+/// Built-in datum IDs with a 32-bit floating point value.
+const BUILTIN_FLOATS: [u32; 4] = [
+    37000,   // Acceleration
+    52340,   // Azimuth
+    240_054, // Air Speed
+    610_026, // Angle Of Attack
+];
+
+/// A registered codec, stored as a `Box<dyn DatumCodec<T>>` for the registered type `T`.
+#[derive(Clone, Debug)]
+struct DatumEntry {
+    value_type: &'static str,
+    codec: Arc<dyn Any + Send + Sync>,
+}
+
+/// Maps datum IDs to the codec of their values.
+///
+/// A registry created using `DatumRegistry::default()` contains the codecs of a small, built-in sample of datum IDs
+/// with an unsigned integer or 32-bit floating point value (e.g. Country, Vehicle Mass, Age, Air Speed),
+/// registered in the same way as application specific datum IDs. All other datum IDs, including those with text,
+/// `f64` or bit-packed values, have to be registered by the application. Registering a datum ID again replaces its codec,
+/// which also allows overriding the built-in codecs.
+#[derive(Clone, Debug)]
+pub struct DatumRegistry {
+    codecs: BTreeMap<u32, DatumEntry>,
+}
+
+impl Default for DatumRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for id in BUILTIN_UNSIGNED_INTEGERS {
+            registry.register::<u32>(VariableRecordType::from(id));
+        }
+        for id in BUILTIN_FLOATS {
+            registry.register::<f32>(VariableRecordType::from(id));
+        }
+        registry
+    }
+}
+
+impl DatumRegistry {
+    /// Creates a registry without the built-in codecs.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            codecs: BTreeMap::new(),
+        }
+    }
+
+    /// Registers (or overrides) datum `id` as a `T`, using the default codec of `T`.
+    pub fn register<T: DatumValue + 'static>(&mut self, id: VariableRecordType) -> &mut Self {
+        self.register_codec(id, ValueCodec::<T>::new())
+    }
+
+    /// Registers (or overrides) datum `id` as a `T`, decoded and encoded using `codec`.
+    pub fn register_codec<T: 'static>(
+        &mut self,
+        id: VariableRecordType,
+        codec: impl DatumCodec<T> + 'static,
+    ) -> &mut Self {
+        let codec: Box<dyn DatumCodec<T>> = Box::new(codec);
+        self.codecs.insert(
+            u32::from(id),
+            DatumEntry {
+                value_type: type_name::<T>(),
+                codec: Arc::new(codec),
+            },
+        );
+        self
+    }
+
+    /// Indicates whether a codec is registered for datum `id`.
+    #[must_use]
+    pub fn is_registered(&self, id: VariableRecordType) -> bool {
+        self.codecs.contains_key(&u32::from(id))
+    }
+
+    /// The name of the type datum `id` is registered as.
+    #[must_use]
+    pub fn value_type(&self, id: VariableRecordType) -> Option<&'static str> {
+        self.codecs
+            .get(&u32::from(id))
+            .map(|entry| entry.value_type)
+    }
+
+    fn codec<T: 'static>(&self, id: VariableRecordType) -> Result<&dyn DatumCodec<T>, DatumError> {
+        let entry = self
+            .codecs
+            .get(&u32::from(id))
+            .ok_or(DatumError::UnregisteredDatum(id))?;
+        entry
+            .codec
+            .downcast_ref::<Box<dyn DatumCodec<T>>>()
+            .map(|codec| &**codec)
+            .ok_or(DatumError::TypeMismatch {
+                id,
+                registered: entry.value_type,
+                requested: type_name::<T>(),
+            })
+    }
+}
+
+/// Calls `f` with the default registry, which is created once when the `std` feature is enabled.
+fn with_default_registry<R>(f: impl FnOnce(&DatumRegistry) -> R) -> R {
+    #[cfg(feature = "std")]
+    {
+        static DEFAULT_REGISTRY: std::sync::LazyLock<DatumRegistry> =
+            std::sync::LazyLock::new(DatumRegistry::default);
+        f(&DEFAULT_REGISTRY)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        f(&DatumRegistry::default())
+    }
+}
+
+impl FixedDatum {
+    /// Decodes the value of this datum as a `T`, using the built-in datum codecs.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the datum ID is unknown, it is not registered as a `T`, or the value cannot be decoded.
+    pub fn typed<T: 'static>(&self) -> Result<T, DatumError> {
+        with_default_registry(|registry| self.typed_with(registry))
+    }
+
+    /// Decodes the value of this datum as a `T`, using the codecs in `registry`.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the datum ID is not in `registry`, it is not registered as a `T`, or the value cannot be decoded.
+    pub fn typed_with<T: 'static>(&self, registry: &DatumRegistry) -> Result<T, DatumError> {
+        registry
+            .codec::<T>(self.datum_id)?
+            .decode_fixed(self.datum_value)
+    }
+
+    /// Creates a datum from a typed value, using the built-in datum codecs.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the datum ID is unknown, it is not registered as a `T`, or the value cannot be encoded.
+    pub fn try_from_typed<T: 'static>(
+        datum_id: VariableRecordType,
+        value: &T,
+    ) -> Result<Self, DatumError> {
+        with_default_registry(|registry| Self::try_from_typed_with(datum_id, value, registry))
+    }
+
+    /// Creates a datum from a typed value, using the codecs in `registry`.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the datum ID is not in `registry`, it is not registered as a `T`, or the value cannot be encoded.
+    pub fn try_from_typed_with<T: 'static>(
+        datum_id: VariableRecordType,
+        value: &T,
+        registry: &DatumRegistry,
+    ) -> Result<Self, DatumError> {
+        let value = registry.codec::<T>(datum_id)?.encode_fixed(value)?;
+        Ok(Self::new(datum_id, value))
+    }
+}
+
+impl VariableDatum {
+    /// Decodes the value of this datum as a `T`, using the built-in datum codecs.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the datum ID is unknown, it is not registered as a `T`, or the value cannot be decoded.
+    pub fn typed<T: 'static>(&self) -> Result<T, DatumError> {
+        with_default_registry(|registry| self.typed_with(registry))
+    }
+
+    /// Decodes the value of this datum as a `T`, using the codecs in `registry`.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the datum ID is not in `registry`, it is not registered as a `T`, or the value cannot be decoded.
+    pub fn typed_with<T: 'static>(&self, registry: &DatumRegistry) -> Result<T, DatumError> {
+        registry
+            .codec::<T>(self.datum_id)?
+            .decode_variable(&self.datum_value)
+    }
+
+    /// Creates a datum from a typed value, using the built-in datum codecs.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the datum ID is unknown, it is not registered as a `T`, or the value cannot be encoded.
+    pub fn try_from_typed<T: 'static>(
+        datum_id: VariableRecordType,
+        value: &T,
+    ) -> Result<Self, DatumError> {
+        with_default_registry(|registry| Self::try_from_typed_with(datum_id, value, registry))
+    }
+
+    /// Creates a datum from a typed value, using the codecs in `registry`.
+    ///
+    /// # Errors
+    /// Returns a `DatumError` when the datum ID is not in `registry`, it is not registered as a `T`, or the value cannot be encoded.
+    pub fn try_from_typed_with<T: 'static>(
+        datum_id: VariableRecordType,
+        value: &T,
+        registry: &DatumRegistry,
+    ) -> Result<Self, DatumError> {
+        let value = registry.codec::<T>(datum_id)?.encode_variable(value)?;
+        Ok(Self::new(datum_id, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn fixed_datum_round_trip() {
+        let datum =
+            FixedDatum::try_from_typed(VariableRecordType::Azimuth_52340, &45.5f32).unwrap();
+        assert_eq!(datum.datum_value, 45.5f32.to_bits());
+        assert_eq!(datum.typed::<f32>(), Ok(45.5));
+    }
+
+    #[test]
+    fn variable_datum_round_trip() {
+        let datum =
+            VariableDatum::try_from_typed(VariableRecordType::_7_62mm_Quantity_24010, &120u32)
+                .unwrap();
+        assert_eq!(datum.datum_value, vec![0, 0, 0, 120]);
+        assert_eq!(datum.typed::<u32>(), Ok(120));
+    }
+
+    #[test]
+    fn type_mismatch() {
+        let datum = FixedDatum::new(VariableRecordType::Country_11130, 153);
+        assert_eq!(
+            datum.typed::<f32>(),
+            Err(DatumError::TypeMismatch {
+                id: VariableRecordType::Country_11130,
+                registered: type_name::<u32>(),
+                requested: type_name::<f32>(),
+            })
+        );
+    }
+
+    #[test]
+    fn unregistered_datum() {
+        let id = VariableRecordType::from(1);
+        let datum = VariableDatum::new(id, vec![1, 2, 3, 4]);
+        assert_eq!(datum.typed::<u32>(), Err(DatumError::UnregisteredDatum(id)));
+        assert!(DatumRegistry::default().is_registered(VariableRecordType::Azimuth_52340));
+        assert!(!DatumRegistry::empty().is_registered(VariableRecordType::Azimuth_52340));
+    }
+
+    #[test]
+    fn builtin_datums() {
+        let registry = DatumRegistry::default();
+        for id in BUILTIN_UNSIGNED_INTEGERS {
+            assert_eq!(
+                registry.value_type(VariableRecordType::from(id)),
+                Some(type_name::<u32>())
+            );
+        }
+        for id in BUILTIN_FLOATS {
+            assert_eq!(
+                registry.value_type(VariableRecordType::from(id)),
+                Some(type_name::<f32>())
+            );
+        }
+        assert_eq!(registry.codecs.len(), 10);
+    }
+
+    #[test]
+    fn user_registered_datums() {
+        let id = VariableRecordType::from(1);
+        let mut registry = DatumRegistry::default();
+        registry.register::<String>(id);
+        assert_eq!(registry.value_type(id), Some(type_name::<String>()));
+
+        let datum =
+            VariableDatum::try_from_typed_with(id, &"Alpha".to_string(), &registry).unwrap();
+        assert_eq!(
+            datum.typed_with::<String>(&registry),
+            Ok("Alpha".to_string())
+        );
+
+        let padded = VariableDatum::new(id, b"Bravo\0\0\0".to_vec());
+        assert_eq!(
+            padded.typed_with::<String>(&registry),
+            Ok("Bravo".to_string())
+        );
+
+        registry.register::<f64>(id);
+        let datum = VariableDatum::try_from_typed_with(id, &1.5f64, &registry).unwrap();
+        assert_eq!(datum.typed_with::<f64>(&registry), Ok(1.5));
+        assert_eq!(
+            FixedDatum::try_from_typed_with(id, &1.5f64, &registry),
+            Err(DatumError::NotFixedSize(type_name::<f64>()))
+        );
+    }
+
+    /// Fuel level in hundredths of a percent.
+    struct FuelLevelCodec;
+
+    impl DatumCodec<f64> for FuelLevelCodec {
+        fn decode_fixed(&self, value: u32) -> Result<f64, DatumError> {
+            Ok(f64::from(value) / 100.0)
+        }
+
+        fn encode_fixed(&self, value: &f64) -> Result<u32, DatumError> {
+            Ok((value * 100.0).round() as u32)
+        }
+
+        fn decode_variable(&self, value: &[u8]) -> Result<f64, DatumError> {
+            self.decode_fixed(u32::from_variable(value)?)
+        }
+
+        fn encode_variable(&self, value: &f64) -> Result<Vec<u8>, DatumError> {
+            Ok(self.encode_fixed(value)?.to_variable())
+        }
+    }
+
+    #[test]
+    fn user_codec() {
+        let id = VariableRecordType::from(2);
+        let mut registry = DatumRegistry::empty();
+        registry.register_codec(id, FuelLevelCodec);
+
+        let datum = FixedDatum::try_from_typed_with(id, &87.25f64, &registry).unwrap();
+        assert_eq!(datum.datum_value, 8725);
+        assert_eq!(datum.typed_with::<f64>(&registry), Ok(87.25));
+
+        let datum = VariableDatum::new(id, vec![0, 0, 0x27, 0x10]);
+        assert_eq!(datum.typed_with::<f64>(&registry), Ok(100.0));
+    }
+
+    #[test]
+    fn override_builtin_codec() {
+        let mut registry = DatumRegistry::default();
+        registry.register::<i32>(VariableRecordType::Azimuth_52340);
+
+        let datum = FixedDatum::new(VariableRecordType::Azimuth_52340, u32::MAX);
+        assert_eq!(datum.typed_with::<i32>(&registry), Ok(-1));
+        assert!(datum.typed_with::<f32>(&registry).is_err());
+    }
+
+    #[test]
+    fn bit_packed_round_trip() {
+        let id = VariableRecordType::from(3);
+        let mut registry = DatumRegistry::empty();
+        registry.register_codec(id, BitPackedCodec::new([4, 12, 16]).unwrap());
+
+        let fields = BitPacked(vec![0x1, 0x234, 0x5678]);
+        let datum = FixedDatum::try_from_typed_with(id, &fields, &registry).unwrap();
+        assert_eq!(datum.datum_value, 0x1234_5678);
+        assert_eq!(datum.typed_with::<BitPacked>(&registry), Ok(fields.clone()));
+
+        let datum = VariableDatum::try_from_typed_with(id, &fields, &registry).unwrap();
+        assert_eq!(datum.datum_value, vec![0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(datum.typed_with::<BitPacked>(&registry), Ok(fields));
+    }
+
+    #[test]
+    fn bit_packed_right_aligned() {
+        let codec = BitPackedCodec::new([1, 3]).unwrap();
+        assert_eq!(codec.decode_fixed(0b1_011), Ok(BitPacked(vec![1, 0b011])));
+        assert_eq!(codec.encode_fixed(&BitPacked(vec![1, 0b011])), Ok(0b1_011));
+
+        let codec = BitPackedCodec::new([32]).unwrap();
+        assert_eq!(codec.decode_fixed(u32::MAX), Ok(BitPacked(vec![u32::MAX])));
+        assert_eq!(codec.encode_fixed(&BitPacked(vec![u32::MAX])), Ok(u32::MAX));
+    }
+
+    #[test]
+    fn invalid_bit_packed() {
+        assert_eq!(
+            BitPackedCodec::new([16, 16, 1]),
+            Err(DatumError::BitFieldsTooWide(33))
+        );
+
+        let codec = BitPackedCodec::new([4, 4]).unwrap();
+        assert_eq!(
+            codec.encode_fixed(&BitPacked(vec![1])),
+            Err(DatumError::BitFieldCount {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            codec.encode_fixed(&BitPacked(vec![1, 16])),
+            Err(DatumError::BitFieldOverflow {
+                field: 1,
+                value: 16,
+                width: 4
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_length() {
+        let datum = VariableDatum::new(VariableRecordType::AirSpeed_240054, vec![0, 1]);
+        assert_eq!(
+            datum.typed::<f32>(),
+            Err(DatumError::InvalidLength {
+                expected: 4,
+                found: 2
+            })
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub mod builder;
 pub mod model;
 pub mod parser;
+pub mod spot;
 pub mod writer;
 
 #[cfg(test)]
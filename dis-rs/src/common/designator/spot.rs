@@ -0,0 +1,146 @@
+//! Geometry of the designator spot.
+//!
+//! A Designator PDU locates the spot both relative to the designated entity (in entity coordinates)
+//! and in world coordinates. The world location is dead reckoned using the Dead Reckoning Algorithm and the
+//! linear acceleration of the spot. The PDU has no spot velocity, so the spot starts from rest unless
+//! the caller provides a velocity, for instance estimated from the spot locations of successive PDUs.
+use crate::common::designator::model::Designator;
+use crate::common::entity_state::dead_reckoning::{dead_reckon, entity_to_world, world_to_entity};
+use crate::common::entity_state::model::DrParameters;
+use crate::common::model::{Location, Orientation, VectorF32};
+use core::time::Duration;
+
+impl Designator {
+    /// Returns the world location of the spot, extrapolated `elapsed` after the time of this PDU,
+    /// using the dead reckoning algorithm and linear acceleration of the spot.
+    #[must_use]
+    pub fn dead_reckon_spot(&self, elapsed: Duration) -> Location {
+        self.dead_reckon_spot_with_velocity(&VectorF32::default(), elapsed)
+    }
+
+    /// Returns the world location of the spot, extrapolated `elapsed` after the time of this PDU,
+    /// moving with `velocity` (in world coordinates) and the linear acceleration of the spot.
+    ///
+    /// The spot has no orientation, so the body axis algorithms extrapolate the same as their world axis counterparts.
+    #[must_use]
+    pub fn dead_reckon_spot_with_velocity(
+        &self,
+        velocity: &VectorF32,
+        elapsed: Duration,
+    ) -> Location {
+        let parameters = DrParameters::default()
+            .with_algorithm(self.dead_reckoning_algorithm)
+            .with_linear_acceleration(self.linear_acceleration);
+        let (location, _) = dead_reckon(
+            &self.spot_location,
+            &Orientation::default(),
+            velocity,
+            &parameters,
+            elapsed,
+        );
+        location
+    }
+
+    /// Converts the spot location relative to the designated entity into world coordinates,
+    /// for the designated entity at `entity_location` with `entity_orientation`.
+    #[must_use]
+    pub fn spot_in_world(
+        &self,
+        entity_location: &Location,
+        entity_orientation: &Orientation,
+    ) -> Location {
+        entity_to_world(
+            entity_location,
+            entity_orientation,
+            &self.spot_wrt_designated_entity,
+        )
+    }
+
+    /// Converts the world spot location into coordinates relative to the designated entity,
+    /// for the designated entity at `entity_location` with `entity_orientation`.
+    #[must_use]
+    pub fn spot_relative_to_entity(
+        &self,
+        entity_location: &Location,
+        entity_orientation: &Orientation,
+    ) -> VectorF32 {
+        world_to_entity(entity_location, entity_orientation, &self.spot_location)
+    }
+
+    /// Sets both spot locations from a spot at `spot_location` in world coordinates,
+    /// on the designated entity at `entity_location` with `entity_orientation`.
+    #[must_use]
+    pub fn with_spot_in_world(
+        mut self,
+        spot_location: Location,
+        entity_location: &Location,
+        entity_orientation: &Orientation,
+    ) -> Self {
+        self.spot_location = spot_location;
+        self.spot_wrt_designated_entity =
+            world_to_entity(entity_location, entity_orientation, &spot_location);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::entity_state::dead_reckoning::location_difference;
+    use crate::enumerations::DeadReckoningAlgorithm;
+    use core::f32::consts::PI;
+
+    #[test]
+    fn spot_accelerates_from_rest() {
+        let designator = Designator {
+            spot_location: Location::new(10.0, 20.0, 30.0),
+            dead_reckoning_algorithm: DeadReckoningAlgorithm::DRM_FVW_HighSpeedOrManeuveringEntity,
+            linear_acceleration: VectorF32::new(2.0, 0.0, 0.0),
+            ..Default::default()
+        };
+
+        let spot = designator.dead_reckon_spot(Duration::from_secs(3));
+        assert!(location_difference(&spot, &Location::new(19.0, 20.0, 30.0)) < 1e-9);
+
+        let spot = designator
+            .dead_reckon_spot_with_velocity(&VectorF32::new(0.0, 1.0, 0.0), Duration::from_secs(3));
+        assert!(location_difference(&spot, &Location::new(19.0, 23.0, 30.0)) < 1e-9);
+    }
+
+    #[test]
+    fn static_spot_does_not_move() {
+        let designator = Designator {
+            spot_location: Location::new(10.0, 20.0, 30.0),
+            dead_reckoning_algorithm: DeadReckoningAlgorithm::StaticNonmovingEntity,
+            linear_acceleration: VectorF32::new(2.0, 0.0, 0.0),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            designator.dead_reckon_spot(Duration::from_secs(3)),
+            designator.spot_location
+        );
+    }
+
+    #[test]
+    fn spot_relative_and_world_locations() {
+        let entity_location = Location::new(1_000.0, 2_000.0, 3_000.0);
+        // heading south
+        let entity_orientation = Orientation::new(PI, 0.0, 0.0);
+
+        let designator = Designator::default().with_spot_in_world(
+            Location::new(995.0, 2_000.0, 3_001.0),
+            &entity_location,
+            &entity_orientation,
+        );
+        let relative = designator.spot_wrt_designated_entity;
+        assert!((relative.first_vector_component - 5.0).abs() < 1e-4);
+        assert!(relative.second_vector_component.abs() < 1e-4);
+
+        let world = designator.spot_in_world(&entity_location, &entity_orientation);
+        assert!(location_difference(&world, &designator.spot_location) < 1e-4);
+
+        let relative = designator.spot_relative_to_entity(&entity_location, &entity_orientation);
+        assert!((relative.third_vector_component - 1.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,30 @@
+use crate::common::SupportedVersion;
+use crate::common::conversion::ConversionLoss;
+use crate::common::detonation::model::{Detonation, DetonationDescriptor};
+use crate::common::model::MunitionDescriptor;
+use alloc::vec::Vec;
+
+impl Detonation {
+    /// Adapts the fields of the body that differ between protocol versions to `version`.
+    ///
+    /// V6 only defines the Munition Descriptor record; Explosion and Expendable Descriptors are approximated
+    /// by a Munition Descriptor for the same entity type.
+    pub(crate) fn convert_to(
+        &mut self,
+        version: &SupportedVersion,
+        losses: &mut Vec<ConversionLoss>,
+    ) {
+        if !matches!(version, SupportedVersion::V6) {
+            return;
+        }
+        let entity_type = match &self.descriptor {
+            DetonationDescriptor::Munition(_) => return,
+            DetonationDescriptor::Explosion(explosion) => explosion.entity_type,
+            DetonationDescriptor::Expendable(expendable) => expendable.entity_type,
+        };
+        self.descriptor = DetonationDescriptor::Munition(
+            MunitionDescriptor::default().with_entity_type(entity_type),
+        );
+        losses.push(ConversionLoss::Approximated("detonation.descriptor"));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod builder;
+mod compatibility;
 pub mod model;
 pub mod parser;
 pub mod writer;
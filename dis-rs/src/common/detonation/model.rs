@@ -15,6 +15,7 @@
 
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Detonation {
     pub source_entity_id: EntityId,
     pub target_entity_id: EntityId,
@@ -25,6 +26,7 @@ pub struct Detonation {
     pub descriptor: DetonationDescriptor,
     pub location_in_entity_coordinates: VectorF32,
     pub detonation_result: DetonationResult,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub variable_parameters: Vec<VariableParameter>,
 }
 
@@ -68,6 +70,7 @@ fn receiver(&self) -> Option<&EntityId> {
 /// 6.2.19 Detonation Descriptor record
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DetonationDescriptor {
     #[cfg_attr(feature = "serde", serde(rename = "munition"))]
     Munition(MunitionDescriptor),
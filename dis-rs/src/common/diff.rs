@@ -0,0 +1,1018 @@
+//! Field-level comparison of PDUs, for instance to compare recorded traffic with regenerated traffic,
+//! or to check the fidelity of an encode/decode round trip.
+//!
+//! [`Pdu::diff`] and [`PduBody::diff`] list each field that differs, identified by its path in the model
+//! (e.g. `body.entity_location.x_coordinate` or `body.variable_parameters[1].parent_entity_id.entity_id`),
+//! with the old and new value. Floating point fields are compared using a [`Tolerance`],
+//! which can be configured per record type such as `Location`, `Orientation` or `VectorF32` through [`DiffOptions`].
+//!
+//! The comparison is based on the `serde` representation of the model, and requires the `serde` feature;
+//! crates using the diff must enable the `serde` feature of `dis-rs` themselves.
+//! Custom bodies and variable parameter records have no `serde` representation. These are reported as a single
+//! [`FieldValue::Custom`] field, which [`Pdu::diff`] and [`PduBody::diff`] compare using their `PartialEq` implementation.
+use crate::common::model::{Pdu, PduBody, VariableParameter};
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use serde::Serialize;
+use serde::ser::{
+    SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+
+/// Allowed difference between two floating point values.
+///
+/// Two values are considered equal when their difference is at most the `absolute` tolerance,
+/// or at most the `relative` tolerance times the largest magnitude of the two values.
+/// The default tolerance requires values to be exactly equal.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Tolerance {
+    #[must_use]
+    pub fn new_absolute(absolute: f64) -> Self {
+        Self {
+            absolute,
+            relative: 0.0,
+        }
+    }
+
+    #[must_use]
+    pub fn new_relative(relative: f64) -> Self {
+        Self {
+            absolute: 0.0,
+            relative,
+        }
+    }
+
+    #[must_use]
+    pub fn with_absolute(mut self, absolute: f64) -> Self {
+        self.absolute = absolute;
+        self
+    }
+
+    #[must_use]
+    pub fn with_relative(mut self, relative: f64) -> Self {
+        self.relative = relative;
+        self
+    }
+
+    /// Checks whether `old` and `new` are equal within the tolerance. Two NaN values are considered equal.
+    #[must_use]
+    pub fn accepts(self, old: f64, new: f64) -> bool {
+        #[allow(clippy::float_cmp)]
+        if old == new || (old.is_nan() && new.is_nan()) {
+            return true;
+        }
+        let difference = (new - old).abs();
+        difference <= self.absolute || difference <= self.relative * old.abs().max(new.abs())
+    }
+}
+
+/// Options for comparing PDUs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiffOptions {
+    pub float_tolerance: Tolerance,
+    pub record_tolerances: BTreeMap<&'static str, Tolerance>,
+    pub ignored_paths: Vec<String>,
+}
+
+impl DiffOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the tolerance for floating point fields that have no record specific tolerance.
+    #[must_use]
+    pub fn with_float_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.float_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the tolerance for the floating point fields of the record type named `record`,
+    /// such as `"Location"`, `"Orientation"` or `"VectorF32"`.
+    #[must_use]
+    pub fn with_record_tolerance(mut self, record: &'static str, tolerance: Tolerance) -> Self {
+        self.record_tolerances.insert(record, tolerance);
+        self
+    }
+
+    /// Excludes the field at `path`, including all its nested fields, from the comparison.
+    /// For example `header.timestamp`.
+    #[must_use]
+    pub fn with_ignored_path(mut self, path: &str) -> Self {
+        self.ignored_paths.push(String::from(path));
+        self
+    }
+
+    fn is_ignored(&self, path: &str) -> bool {
+        self.ignored_paths.iter().any(|ignored| {
+            path.strip_prefix(ignored.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']))
+        })
+    }
+
+    fn tolerance(&self, record: Option<&'static str>) -> Tolerance {
+        record
+            .and_then(|record| self.record_tolerances.get(record))
+            .copied()
+            .unwrap_or(self.float_tolerance)
+    }
+}
+
+/// The value of a single (leaf) field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    /// An absent optional field.
+    None,
+    Unit,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    /// A variant of an enumeration without associated data.
+    Variant(&'static str),
+    /// A value without a `serde` representation, such as a custom PDU body or variable parameter record.
+    Custom,
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FieldValue::None => write!(f, "None"),
+            FieldValue::Unit => write!(f, "()"),
+            FieldValue::Bool(value) => write!(f, "{value}"),
+            FieldValue::Integer(value) => write!(f, "{value}"),
+            FieldValue::Float(value) => write!(f, "{value}"),
+            FieldValue::Text(value) => write!(f, "\"{value}\""),
+            FieldValue::Bytes(value) => write!(f, "{value:02X?}"),
+            FieldValue::Variant(value) => write!(f, "{value}"),
+            FieldValue::Custom => write!(f, "<custom>"),
+        }
+    }
+}
+
+/// A field that differs between two PDUs.
+///
+/// `old` or `new` is `None` when the field is only present on one side,
+/// for example for an element beyond the end of a shorter list, or a field of a different enumeration variant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDiff {
+    pub path: String,
+    pub old: Option<FieldValue>,
+    pub new: Option<FieldValue>,
+}
+
+impl FieldDiff {
+    /// The difference `new - old` when both values are numeric.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn delta(&self) -> Option<f64> {
+        let as_f64 = |value: &FieldValue| match value {
+            FieldValue::Integer(value) => Some(*value as f64),
+            FieldValue::Float(value) => Some(*value),
+            _ => None,
+        };
+        Some(as_f64(self.new.as_ref()?)? - as_f64(self.old.as_ref()?)?)
+    }
+}
+
+impl Display for FieldDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let describe = |value: &Option<FieldValue>| {
+            value
+                .as_ref()
+                .map_or_else(|| String::from("<absent>"), ToString::to_string)
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            describe(&self.old),
+            describe(&self.new)
+        )
+    }
+}
+
+impl Pdu {
+    /// Lists the fields of `self` that differ from `other`, in the order of the model.
+    #[must_use]
+    pub fn diff(&self, other: &Pdu, options: &DiffOptions) -> Vec<FieldDiff> {
+        diff_with(self, other, options, |path| {
+            path.strip_prefix("body").is_some_and(|path| {
+                custom_values_equal(
+                    &self.body,
+                    &other.body,
+                    path.strip_prefix('.').unwrap_or(path),
+                )
+            })
+        })
+    }
+}
+
+impl PduBody {
+    /// Lists the fields of `self` that differ from `other`, in the order of the model.
+    /// The `type` field denotes the PDU type of the bodies.
+    ///
+    /// A custom body (`PduBody::Custom`) is a single field with an empty path.
+    #[must_use]
+    pub fn diff(&self, other: &PduBody, options: &DiffOptions) -> Vec<FieldDiff> {
+        diff_with(self, other, options, |path| {
+            custom_values_equal(self, other, path)
+        })
+    }
+}
+
+/// Lists the fields of `old` that differ from `new`, for any part of the model such as a single record.
+///
+/// Values without a `serde` representation cannot be compared, so [`FieldValue::Custom`] fields are always listed.
+#[must_use]
+pub fn diff<T: Serialize + ?Sized>(old: &T, new: &T, options: &DiffOptions) -> Vec<FieldDiff> {
+    diff_with(old, new, options, |_path| false)
+}
+
+/// Compares the custom values found at `path` (relative to the bodies) in `old` and `new`.
+fn custom_values_equal(old: &PduBody, new: &PduBody, path: &str) -> bool {
+    fn variable_parameters(body: &PduBody) -> &[VariableParameter] {
+        match body {
+            PduBody::EntityState(body) => &body.variable_parameters,
+            PduBody::EntityStateUpdate(body) => &body.variable_parameters,
+            PduBody::Detonation(body) => &body.variable_parameters,
+            _ => &[],
+        }
+    }
+
+    if path.is_empty() {
+        return match (old, new) {
+            (PduBody::Custom(old), PduBody::Custom(new)) => old.eq_dyn(new.as_ref()),
+            _ => false,
+        };
+    }
+    let Some(index) = path
+        .strip_prefix("variable_parameters[")
+        .and_then(|index| index.strip_suffix(']'))
+        .and_then(|index| index.parse::<usize>().ok())
+    else {
+        return false;
+    };
+    match (
+        variable_parameters(old).get(index),
+        variable_parameters(new).get(index),
+    ) {
+        (Some(VariableParameter::Custom(old)), Some(VariableParameter::Custom(new))) => {
+            old.eq_dyn(new.as_ref())
+        }
+        _ => false,
+    }
+}
+
+/// Lists the fields of `old` that differ from `new`, comparing `FieldValue::Custom` fields using `custom_eq`,
+/// which is given the path of the field.
+fn diff_with<T: Serialize + ?Sized>(
+    old: &T,
+    new: &T,
+    options: &DiffOptions,
+    custom_eq: impl Fn(&str) -> bool,
+) -> Vec<FieldDiff> {
+    let old = flatten(old);
+    let new = flatten(new);
+    let mut unmatched = new
+        .iter()
+        .map(|field| (field.path.as_str(), field))
+        .collect::<BTreeMap<&str, &Field>>();
+
+    let mut diffs = Vec::new();
+    let mut compare = |old: Option<&Field>, new: Option<&Field>| {
+        let Some(path) = old.or(new).map(|field| field.path.as_str()) else {
+            return;
+        };
+        if options.is_ignored(path) {
+            return;
+        }
+        let is_equal = match (old, new) {
+            (Some(old), Some(new)) => match (&old.value, &new.value) {
+                (FieldValue::Float(old_value), FieldValue::Float(new_value)) => options
+                    .tolerance(new.record)
+                    .accepts(*old_value, *new_value),
+                (FieldValue::Custom, FieldValue::Custom) => custom_eq(path),
+                (old_value, new_value) => old_value == new_value,
+            },
+            _ => false,
+        };
+        if !is_equal {
+            diffs.push(FieldDiff {
+                path: String::from(path),
+                old: old.map(|field| field.value.clone()),
+                new: new.map(|field| field.value.clone()),
+            });
+        }
+    };
+
+    for field in &old {
+        compare(Some(field), unmatched.remove(field.path.as_str()));
+    }
+    for field in new
+        .iter()
+        .filter(|field| unmatched.contains_key(field.path.as_str()))
+    {
+        compare(None, Some(field));
+    }
+
+    diffs
+}
+
+/// A leaf field of the model.
+struct Field {
+    path: String,
+    value: FieldValue,
+    /// Name of the record (struct) that directly contains the field.
+    record: Option<&'static str>,
+}
+
+fn flatten<T: Serialize + ?Sized>(value: &T) -> Vec<Field> {
+    let mut flattener = Flattener::default();
+    // The Flattener itself never fails, only the variants skipped by `serde` (the custom values) do.
+    let result = value
+        .serialize(&mut flattener)
+        .or_else(|FlattenError| flattener.leaf(FieldValue::Custom));
+    match result {
+        Ok(()) | Err(FlattenError) => flattener.fields,
+    }
+}
+
+#[derive(Debug)]
+struct FlattenError;
+
+impl Display for FlattenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Failed to flatten value")
+    }
+}
+
+impl serde::ser::StdError for FlattenError {}
+
+impl serde::ser::Error for FlattenError {
+    fn custom<T: Display>(_msg: T) -> Self {
+        FlattenError
+    }
+}
+
+/// A `serde` Serializer that collects the leaf fields of a value with their paths.
+#[derive(Default)]
+struct Flattener {
+    fields: Vec<Field>,
+    path: String,
+    record: Option<&'static str>,
+}
+
+impl Flattener {
+    /// Adds a leaf field, returning a `Result` to be used directly by the `Serializer` methods.
+    #[allow(clippy::unnecessary_wraps)]
+    fn leaf(&mut self, value: FieldValue) -> Result<(), FlattenError> {
+        self.fields.push(Field {
+            path: String::from(self.path.strip_prefix('.').unwrap_or(&self.path)),
+            value,
+            record: self.record,
+        });
+        Ok(())
+    }
+
+    /// Serializes `value` as the nested field `segment`, e.g. `.name` or `[0]`.
+    /// A value that cannot be serialized becomes a `FieldValue::Custom` leaf.
+    fn nested<T: Serialize + ?Sized>(
+        &mut self,
+        segment: &str,
+        value: &T,
+    ) -> Result<(), FlattenError> {
+        let length = self.path.len();
+        let record = self.record;
+        self.path.push_str(segment);
+        let result = value.serialize(&mut *self).or_else(|FlattenError| {
+            self.record = record;
+            self.leaf(FieldValue::Custom)
+        });
+        self.path.truncate(length);
+        result
+    }
+
+    /// Starts a compound value, of which the fields are nested in `record`.
+    /// The path is restored to `path_length` when the compound ends.
+    fn compound(&mut self, record: Option<&'static str>, path_length: usize) -> Compound<'_> {
+        let parent_record = core::mem::replace(&mut self.record, record);
+        Compound {
+            flattener: self,
+            parent_record,
+            path_length,
+            index: 0,
+            key: None,
+        }
+    }
+}
+
+struct Compound<'a> {
+    flattener: &'a mut Flattener,
+    parent_record: Option<&'static str>,
+    path_length: usize,
+    index: usize,
+    key: Option<String>,
+}
+
+impl Compound<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FlattenError> {
+        let segment = alloc::format!("[{}]", self.index);
+        self.index += 1;
+        self.flattener.nested(&segment, value)
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, name: &str, value: &T) -> Result<(), FlattenError> {
+        self.flattener.nested(&alloc::format!(".{name}"), value)
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn end(self) -> Result<(), FlattenError> {
+        self.flattener.record = self.parent_record;
+        self.flattener.path.truncate(self.path_length);
+        Ok(())
+    }
+}
+
+impl<'a> Serializer for &'a mut Flattener {
+    type Ok = ();
+    type Error = FlattenError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v.into()))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Integer(v.into()))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), FlattenError> {
+        // Values beyond i128::MAX do not occur in the model, saturate for completeness.
+        self.leaf(FieldValue::Integer(i128::try_from(v).unwrap_or(i128::MAX)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Float(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Text(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Text(String::from(v)))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Bytes(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), FlattenError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Unit)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), FlattenError> {
+        self.leaf(FieldValue::Variant(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), FlattenError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), FlattenError> {
+        self.nested(&alloc::format!(".{variant}"), value)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, FlattenError> {
+        let length = self.path.len();
+        Ok(self.compound(None, length))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a>, FlattenError> {
+        let length = self.path.len();
+        Ok(self.compound(None, length))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, FlattenError> {
+        let length = self.path.len();
+        Ok(self.compound(Some(name), length))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, FlattenError> {
+        self.path.push('.');
+        self.path.push_str(variant);
+        let length = self.path.len();
+        Ok(self.compound(None, length))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, FlattenError> {
+        let length = self.path.len();
+        Ok(self.compound(None, length))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, FlattenError> {
+        let length = self.path.len();
+        Ok(self.compound(Some(name), length))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, FlattenError> {
+        let length = self.path.len();
+        self.path.push('.');
+        self.path.push_str(variant);
+        Ok(self.compound(Some(variant), length))
+    }
+}
+
+impl SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = FlattenError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FlattenError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), FlattenError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = FlattenError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FlattenError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), FlattenError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = FlattenError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FlattenError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), FlattenError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = FlattenError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FlattenError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), FlattenError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = FlattenError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), FlattenError> {
+        let mut key_flattener = Flattener::default();
+        key.serialize(&mut key_flattener)?;
+        let key = key_flattener
+            .fields
+            .iter()
+            .map(|field| match &field.value {
+                FieldValue::Text(text) => text.clone(),
+                value => value.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(".");
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FlattenError> {
+        let key = self.key.take().unwrap_or_default();
+        self.flattener.nested(&alloc::format!("[{key}]"), value)
+    }
+
+    fn end(self) -> Result<(), FlattenError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = FlattenError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FlattenError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), FlattenError> {
+        Compound::end(self)
+    }
+}
+
+impl SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = FlattenError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FlattenError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), FlattenError> {
+        Compound::end(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyRaw;
+    use crate::common::custom::model::{CustomPdu, CustomVariableParameter};
+    use crate::common::entity_state::model::EntityState;
+    use crate::common::model::{
+        EntityId, Location, Orientation, PduHeader, SeparationParameter, TimeUnits, Timestamp,
+    };
+    use crate::common::{BodyInfo, Interaction, SerializePdu, SupportedVersion};
+    use crate::constants::FIFTEEN_OCTETS;
+    use crate::enumerations::{PduType, VariableParameterRecordType};
+    use crate::errors::DisError;
+    use alloc::boxed::Box;
+    use bytes::{BufMut, BytesMut};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Ping(u32);
+
+    impl BodyInfo for Ping {
+        fn body_length(&self) -> u16 {
+            4
+        }
+
+        fn body_type(&self) -> PduType {
+            PduType::from(201)
+        }
+    }
+
+    impl Interaction for Ping {
+        fn originator(&self) -> Option<&EntityId> {
+            None
+        }
+
+        fn receiver(&self) -> Option<&EntityId> {
+            None
+        }
+    }
+
+    impl SerializePdu for Ping {
+        fn serialize_pdu(&self, _version: SupportedVersion, buf: &mut BytesMut) -> u16 {
+            buf.put_u32(self.0);
+            4
+        }
+    }
+
+    impl CustomPdu for Ping {
+        fn parse_body(_header: &PduHeader, _input: &[u8]) -> Result<Self, DisError> {
+            Ok(Self(0))
+        }
+    }
+
+    impl CustomVariableParameter for Ping {
+        fn record_type(&self) -> VariableParameterRecordType {
+            VariableParameterRecordType::from(240)
+        }
+
+        fn parse_record(
+            _record_type: VariableParameterRecordType,
+            _input: &[u8; FIFTEEN_OCTETS],
+        ) -> Result<Self, DisError> {
+            Ok(Self(0))
+        }
+
+        fn serialize_record(&self) -> [u8; FIFTEEN_OCTETS] {
+            [0; FIFTEEN_OCTETS]
+        }
+    }
+
+    fn entity_state(x: f64, psi: f32) -> EntityState {
+        EntityState::builder()
+            .with_entity_id(EntityId::new(1, 1, 1))
+            .with_location(Location::new(x, 2.0, 3.0))
+            .with_orientation(Orientation::new(psi, 0.0, 0.0))
+            .build()
+    }
+
+    fn pdu(body: EntityState, time_units: u32) -> Pdu {
+        Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::EntityState),
+            body.into_pdu_body(),
+            Timestamp::Absolute(TimeUnits::new(time_units).unwrap()),
+        )
+    }
+
+    #[test]
+    fn tolerance() {
+        assert!(Tolerance::default().accepts(1.0, 1.0));
+        assert!(!Tolerance::default().accepts(1.0, 1.0001));
+        assert!(Tolerance::new_absolute(0.01).accepts(1.0, 1.005));
+        assert!(!Tolerance::new_absolute(0.01).accepts(1.0, 1.02));
+        assert!(Tolerance::new_relative(0.01).accepts(1000.0, 1009.0));
+        assert!(!Tolerance::new_relative(0.01).accepts(1.0, 1.02));
+        assert!(Tolerance::default().accepts(f64::NAN, f64::NAN));
+    }
+
+    #[test]
+    fn diff_equal_pdus() {
+        let pdu = pdu(entity_state(1.0, 0.5), 100);
+        assert!(pdu.diff(&pdu.clone(), &DiffOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn diff_fields() {
+        let old = pdu(entity_state(1.0, 0.5), 100);
+        let new = pdu(entity_state(1.5, 0.5), 200);
+
+        let timestamp = |pdu: &Pdu| Some(FieldValue::Integer(pdu.header.timestamp.to_u32().into()));
+
+        let diffs = old.diff(&new, &DiffOptions::default());
+        assert_eq!(
+            diffs,
+            vec![
+                FieldDiff {
+                    path: String::from("header.timestamp"),
+                    old: timestamp(&old),
+                    new: timestamp(&new),
+                },
+                FieldDiff {
+                    path: String::from("body.entity_location.x_coordinate"),
+                    old: Some(FieldValue::Float(1.0)),
+                    new: Some(FieldValue::Float(1.5)),
+                },
+            ]
+        );
+        assert_eq!(diffs[1].delta(), Some(0.5));
+        assert_eq!(
+            diffs[1].to_string(),
+            "body.entity_location.x_coordinate: 1 -> 1.5"
+        );
+    }
+
+    #[test]
+    fn diff_with_tolerances() {
+        let old = pdu(entity_state(1.0, 0.5), 100);
+        let new = pdu(entity_state(1.001, 0.6), 100);
+
+        let options = DiffOptions::new()
+            .with_record_tolerance("Location", Tolerance::new_absolute(0.01))
+            .with_float_tolerance(Tolerance::new_absolute(0.2));
+        assert!(old.diff(&new, &options).is_empty());
+
+        let options = DiffOptions::new()
+            .with_record_tolerance("Location", Tolerance::new_absolute(0.01))
+            .with_record_tolerance("Orientation", Tolerance::new_relative(0.01));
+        let diffs = old.diff(&new, &options);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, "body.entity_orientation.psi");
+    }
+
+    #[test]
+    fn diff_ignored_paths() {
+        let old = pdu(entity_state(1.0, 0.5), 100);
+        let new = pdu(entity_state(1.0, 0.5), 200);
+
+        let options = DiffOptions::new().with_ignored_path("header.time");
+        assert_eq!(old.diff(&new, &options).len(), 1);
+        let options = DiffOptions::new().with_ignored_path("header.timestamp");
+        assert!(old.diff(&new, &options).is_empty());
+        let options = DiffOptions::new().with_ignored_path("header");
+        assert!(old.diff(&new, &options).is_empty());
+    }
+
+    #[test]
+    fn diff_lists_and_types() {
+        let old = entity_state(1.0, 0.5).into_pdu_body();
+        let new = EntityState {
+            variable_parameters: vec![VariableParameter::Separation(
+                SeparationParameter::default().with_parent_entity_id(EntityId::new(1, 1, 2)),
+            )],
+            ..entity_state(1.0, 0.5)
+        }
+        .into_pdu_body();
+
+        let diffs = old.diff(&new, &DiffOptions::default());
+        assert!(!diffs.is_empty());
+        assert!(
+            diffs
+                .iter()
+                .all(|diff| diff.old.is_none() && diff.path.starts_with("variable_parameters[0]."))
+        );
+        assert!(diffs.iter().any(|diff| diff.path
+            == "variable_parameters[0].Separation.parent_entity_id.entity_id"
+            && diff.new == Some(FieldValue::Integer(2))));
+
+        let diffs = old.diff(&PduBody::TSPI, &DiffOptions::default());
+        assert_eq!(
+            diffs[0],
+            FieldDiff {
+                path: String::from("type"),
+                old: Some(FieldValue::Text(String::from("entity_state"))),
+                new: Some(FieldValue::Text(String::from("t_s_p_i"))),
+            }
+        );
+    }
+
+    #[test]
+    fn diff_custom_bodies() {
+        let ping = |value: u32| PduBody::Custom(Box::new(Ping(value)));
+
+        assert!(ping(1).diff(&ping(1), &DiffOptions::default()).is_empty());
+        assert_eq!(
+            ping(1).diff(&ping(2), &DiffOptions::default()),
+            vec![FieldDiff {
+                path: String::new(),
+                old: Some(FieldValue::Custom),
+                new: Some(FieldValue::Custom),
+            }]
+        );
+        assert!(
+            !ping(1)
+                .diff(&PduBody::TSPI, &DiffOptions::default())
+                .is_empty()
+        );
+
+        let pdu = |value: u32| {
+            Pdu::finalize_from_parts(
+                PduHeader::new_v7(1, PduType::from(201)),
+                ping(value),
+                Timestamp::Absolute(TimeUnits::new(100).unwrap()),
+            )
+        };
+        assert!(pdu(1).diff(&pdu(1), &DiffOptions::default()).is_empty());
+        let diffs = pdu(1).diff(&pdu(2), &DiffOptions::default());
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].path, "body");
+        assert_eq!(diffs[0].to_string(), "body: <custom> -> <custom>");
+
+        // Without the values at hand, custom values are never considered equal
+        assert_eq!(diff(&ping(1), &ping(1), &DiffOptions::default()).len(), 1);
+    }
+
+    #[test]
+    fn diff_custom_variable_parameters() {
+        let body = |value: u32| {
+            EntityState {
+                variable_parameters: vec![
+                    VariableParameter::Custom(Box::new(Ping(value))),
+                    VariableParameter::Separation(SeparationParameter::default()),
+                ],
+                ..entity_state(1.0, 0.5)
+            }
+            .into_pdu_body()
+        };
+
+        assert!(body(1).diff(&body(1), &DiffOptions::default()).is_empty());
+        assert_eq!(
+            body(1).diff(&body(2), &DiffOptions::default()),
+            vec![FieldDiff {
+                path: String::from("variable_parameters[0]"),
+                old: Some(FieldValue::Custom),
+                new: Some(FieldValue::Custom),
+            }]
+        );
+
+        let separation = EntityState {
+            variable_parameters: vec![
+                VariableParameter::Separation(SeparationParameter::default()),
+                VariableParameter::Separation(
+                    SeparationParameter::default().with_parent_entity_id(EntityId::new(1, 1, 2)),
+                ),
+            ],
+            ..entity_state(1.0, 0.5)
+        }
+        .into_pdu_body();
+        let diffs = body(1).diff(&separation, &DiffOptions::default());
+        assert!(
+            diffs
+                .iter()
+                .any(|diff| diff.path == "variable_parameters[0]"
+                    && diff.old == Some(FieldValue::Custom)
+                    && diff.new.is_none())
+        );
+        // The comparison continues after the custom record
+        assert!(diffs.iter().any(|diff| diff.path
+            == "variable_parameters[1].Separation.parent_entity_id.entity_id"
+            && diff.new == Some(FieldValue::Integer(2))));
+    }
+}
@@ -0,0 +1,342 @@
+//! Geometry of the beams of an Electromagnetic Emission PDU.
+//!
+//! A beam originates at the location of its emitter system, which is an offset in the entity coordinates of the emitting entity.
+//! It covers the directions within the azimuth and elevation sweep (half-angles, in radians) around the azimuth and elevation center
+//! of its Beam Data record. Azimuth is measured in the x-y plane of the emitting entity from the x-axis, positive towards the y-axis.
+//! Elevation is measured from the x-y plane, positive upwards (towards the negative z-axis).
+//!
+//! The power received at a target is estimated from the effective radiated power of the beam (in dBm)
+//! minus the free space path loss at the frequency of the beam.
+use crate::common::electromagnetic_emission::model::{
+    Beam, ElectromagneticEmission, EmitterSystem, TrackJam,
+};
+use crate::common::entity_state::dead_reckoning::{
+    entity_to_world, location_difference, world_to_entity,
+};
+use crate::common::model::{EntityId, Location, Orientation};
+use crate::enumerations::{BeamStatusBeamState, HighDensityTrackJam};
+use crate::math::{atan2, cos, sin, sqrt};
+use crate::radio_net::free_space_path_loss;
+use alloc::vec::Vec;
+
+/// Maximum number of Track/Jam records of a beam, as the Number of Targets field is 8 bits.
+const MAX_TRACK_JAMS: usize = u8::MAX as usize;
+
+/// Direction and distance of a point, as seen from an emitter system.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct BeamDirection {
+    /// Azimuth in radians, relative to the emitting entity.
+    pub azimuth: f32,
+    /// Elevation in radians, relative to the emitting entity.
+    pub elevation: f32,
+    /// Distance in meters.
+    pub distance: f64,
+}
+
+/// A target that is covered by a beam.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BeamCoverage {
+    pub entity_id: EntityId,
+    pub emitter_number: u8,
+    pub beam_number: u8,
+    pub direction: BeamDirection,
+    /// Estimated power received at the target, in dBm.
+    pub received_power: f32,
+}
+
+impl ElectromagneticEmission {
+    /// Returns the targets covered by the active beams of all emitter systems,
+    /// for the emitting entity at `entity_location` with `entity_orientation`.
+    ///
+    /// `targets` are entity IDs with their locations in world coordinates. The emitting entity itself is never covered.
+    #[must_use]
+    pub fn coverage(
+        &self,
+        entity_location: &Location,
+        entity_orientation: &Orientation,
+        targets: &[(EntityId, Location)],
+    ) -> Vec<BeamCoverage> {
+        let mut coverage = Vec::new();
+        for system in &self.emitter_systems {
+            for (entity_id, location) in targets {
+                if *entity_id == self.emitting_entity_id {
+                    continue;
+                }
+                let direction = system.direction(entity_location, entity_orientation, location);
+                coverage.extend(
+                    system
+                        .beams
+                        .iter()
+                        .filter(|beam| beam.covers(&direction))
+                        .map(|beam| BeamCoverage {
+                            entity_id: *entity_id,
+                            emitter_number: system.number,
+                            beam_number: beam.number,
+                            direction,
+                            received_power: beam.received_power(direction.distance),
+                        }),
+                );
+            }
+        }
+        coverage
+    }
+
+    /// Replaces the Track/Jam records of each beam with the targets that the beam covers,
+    /// for the emitting entity at `entity_location` with `entity_orientation`.
+    ///
+    /// The emitter and beam fields of the records are left zero, as for tracked targets.
+    /// A beam holds at most 255 Track/Jam records; when a beam covers more targets, only the first 255 are listed
+    /// and its High Density Track/Jam field is set to `Selected`.
+    #[must_use]
+    pub fn with_covered_track_jams(
+        mut self,
+        entity_location: &Location,
+        entity_orientation: &Orientation,
+        targets: &[(EntityId, Location)],
+    ) -> Self {
+        let covered = self.coverage(entity_location, entity_orientation, targets);
+        for system in &mut self.emitter_systems {
+            for beam in &mut system.beams {
+                let mut track_jams = covered
+                    .iter()
+                    .filter(|coverage| {
+                        coverage.emitter_number == system.number
+                            && coverage.beam_number == beam.number
+                    })
+                    .map(|coverage| TrackJam::new().with_entity_id(coverage.entity_id))
+                    .collect::<Vec<TrackJam>>();
+                beam.high_density_track_jam = if track_jams.len() > MAX_TRACK_JAMS {
+                    track_jams.truncate(MAX_TRACK_JAMS);
+                    HighDensityTrackJam::Selected
+                } else {
+                    HighDensityTrackJam::NotSelected
+                };
+                beam.track_jam_data = track_jams;
+            }
+        }
+        self
+    }
+}
+
+impl EmitterSystem {
+    /// Returns the location of the emitter system in world coordinates,
+    /// for the emitting entity at `entity_location` with `entity_orientation`.
+    #[must_use]
+    pub fn antenna_location(
+        &self,
+        entity_location: &Location,
+        entity_orientation: &Orientation,
+    ) -> Location {
+        entity_to_world(entity_location, entity_orientation, &self.location)
+    }
+
+    /// Returns the direction and distance of `point` (in world coordinates) as seen from the emitter system,
+    /// for the emitting entity at `entity_location` with `entity_orientation`.
+    #[must_use]
+    pub fn direction(
+        &self,
+        entity_location: &Location,
+        entity_orientation: &Orientation,
+        point: &Location,
+    ) -> BeamDirection {
+        let antenna = self.antenna_location(entity_location, entity_orientation);
+        let relative = world_to_entity(&antenna, entity_orientation, point);
+        let x = f64::from(relative.first_vector_component);
+        let y = f64::from(relative.second_vector_component);
+        let z = f64::from(relative.third_vector_component);
+
+        BeamDirection {
+            azimuth: atan2(y, x) as f32,
+            elevation: atan2(-z, sqrt(x * x + y * y)) as f32,
+            distance: location_difference(&antenna, point),
+        }
+    }
+}
+
+impl Beam {
+    /// Checks whether the beam is active and `direction` lies within its azimuth and elevation sweep.
+    #[must_use]
+    pub fn covers(&self, direction: &BeamDirection) -> bool {
+        if self.beam_status == BeamStatusBeamState::Deactivated {
+            return false;
+        }
+        let azimuth_offset = f64::from(direction.azimuth - self.beam_data.azimuth_center);
+        let azimuth_offset = atan2(sin(azimuth_offset), cos(azimuth_offset));
+        let elevation_offset = direction.elevation - self.beam_data.elevation_center;
+
+        azimuth_offset.abs() <= f64::from(self.beam_data.azimuth_sweep)
+            && elevation_offset.abs() <= self.beam_data.elevation_sweep
+    }
+
+    /// Estimates the power received at `distance` meters from the emitter, in dBm.
+    #[must_use]
+    pub fn received_power(&self, distance: f64) -> f32 {
+        let path_loss = free_space_path_loss(distance, f64::from(self.parameter_data.frequency));
+        (f64::from(self.parameter_data.effective_power) - path_loss) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::electromagnetic_emission::model::FundamentalParameterData;
+    use crate::common::model::{BeamData, VectorF32};
+    use alloc::vec;
+    use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn radar() -> ElectromagneticEmission {
+        ElectromagneticEmission {
+            emitting_entity_id: EntityId::new(1, 1, 1),
+            emitter_systems: vec![
+                EmitterSystem::new()
+                    .with_number(1)
+                    .with_location(VectorF32::new(0.0, 0.0, -10.0))
+                    .with_beam(
+                        Beam::new()
+                            .with_number(1)
+                            .with_parameter_data(
+                                FundamentalParameterData::new()
+                                    .with_frequency(3_000_000_000.0)
+                                    .with_effective_power(90.0),
+                            )
+                            // looking forward
+                            .with_beam_data(
+                                BeamData::new()
+                                    .with_azimuth_sweep(FRAC_PI_4)
+                                    .with_elevation_sweep(FRAC_PI_4),
+                            ),
+                    )
+                    .with_beam(
+                        Beam::new()
+                            .with_number(2)
+                            // looking backward
+                            .with_beam_data(
+                                BeamData::new()
+                                    .with_azimuth_center(PI)
+                                    .with_azimuth_sweep(0.1)
+                                    .with_elevation_sweep(0.1),
+                            )
+                            .with_beam_status(BeamStatusBeamState::Deactivated),
+                    ),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn direction_from_emitter() {
+        let system = EmitterSystem::new().with_location(VectorF32::new(0.0, 0.0, -10.0));
+        // heading along the world y-axis
+        let orientation = Orientation::new(FRAC_PI_2, 0.0, 0.0);
+
+        let antenna = system.antenna_location(&Location::default(), &orientation);
+        assert!(location_difference(&antenna, &Location::new(0.0, 0.0, -10.0)) < 1e-4);
+
+        let direction = system.direction(
+            &Location::default(),
+            &orientation,
+            &Location::new(100.0, 0.0, -10.0),
+        );
+        assert!((direction.azimuth + FRAC_PI_2).abs() < 1e-4);
+        assert!(direction.elevation.abs() < 1e-4);
+        assert!((direction.distance - 100.0).abs() < 1e-6);
+
+        let direction = system.direction(
+            &Location::default(),
+            &orientation,
+            &Location::new(0.0, 0.0, -20.0),
+        );
+        assert!((direction.elevation - FRAC_PI_2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn beam_coverage_of_targets() {
+        let targets = [
+            (EntityId::new(1, 1, 1), Location::new(1_000.0, 0.0, -10.0)),
+            (
+                EntityId::new(2, 1, 1),
+                Location::new(10_000.0, 5_000.0, -10.0),
+            ),
+            (
+                EntityId::new(2, 1, 2),
+                Location::new(10_000.0, 0.0, -20_000.0),
+            ),
+            (EntityId::new(2, 1, 3), Location::new(-10_000.0, 0.0, -10.0)),
+        ];
+
+        let coverage = radar().coverage(&Location::default(), &Orientation::default(), &targets);
+        assert_eq!(coverage.len(), 1);
+        assert_eq!(coverage[0].entity_id, EntityId::new(2, 1, 1));
+        assert_eq!(coverage[0].emitter_number, 1);
+        assert_eq!(coverage[0].beam_number, 1);
+        // 90 dBm minus the path loss over about 11.2 km at 3 GHz
+        assert!((coverage[0].received_power + 32.96).abs() < 0.01);
+    }
+
+    #[test]
+    fn track_jams_of_covered_targets() {
+        let targets = [
+            (
+                EntityId::new(2, 1, 1),
+                Location::new(10_000.0, 5_000.0, -10.0),
+            ),
+            (
+                EntityId::new(2, 1, 2),
+                Location::new(10_000.0, -5_000.0, -10.0),
+            ),
+            (EntityId::new(2, 1, 3), Location::new(-10_000.0, 0.0, -10.0)),
+        ];
+
+        let emission = radar().with_covered_track_jams(
+            &Location::default(),
+            &Orientation::default(),
+            &targets,
+        );
+        let beams = &emission.emitter_systems[0].beams;
+        assert_eq!(
+            beams[0].track_jam_data,
+            vec![
+                TrackJam::new().with_entity_id(EntityId::new(2, 1, 1)),
+                TrackJam::new().with_entity_id(EntityId::new(2, 1, 2)),
+            ]
+        );
+        assert!(beams[1].track_jam_data.is_empty());
+    }
+
+    #[test]
+    fn track_jams_of_high_density_beam() {
+        let targets = (1..=300)
+            .map(|number| {
+                (
+                    EntityId::new(2, 1, number),
+                    Location::new(10_000.0, f64::from(number), -10.0),
+                )
+            })
+            .collect::<Vec<(EntityId, Location)>>();
+
+        let emission = radar().with_covered_track_jams(
+            &Location::default(),
+            &Orientation::default(),
+            &targets,
+        );
+        let beam = &emission.emitter_systems[0].beams[0];
+        assert_eq!(beam.track_jam_data.len(), 255);
+        assert_eq!(beam.high_density_track_jam, HighDensityTrackJam::Selected);
+        assert_eq!(
+            beam.track_jam_data[254],
+            TrackJam::new().with_entity_id(EntityId::new(2, 1, 255))
+        );
+
+        let emission = emission.with_covered_track_jams(
+            &Location::default(),
+            &Orientation::default(),
+            &targets[..255],
+        );
+        let beam = &emission.emitter_systems[0].beams[0];
+        assert_eq!(beam.track_jam_data.len(), 255);
+        assert_eq!(
+            beam.high_density_track_jam,
+            HighDensityTrackJam::NotSelected
+        );
+    }
+}
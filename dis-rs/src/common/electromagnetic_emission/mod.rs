@@ -1,4 +1,5 @@
 pub mod builder;
+pub mod coverage;
 pub mod model;
 pub mod parser;
 pub mod writer;
@@ -21,10 +21,12 @@
 /// 7.6.2 Electromagnetic Emission (EE) PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ElectromagneticEmission {
     pub emitting_entity_id: EntityId,
     pub event_id: EventId,
     pub state_update_indicator: ElectromagneticEmissionStateUpdateIndicator,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub emitter_systems: Vec<EmitterSystem>,
 }
 
@@ -85,11 +87,13 @@ fn receiver(&self) -> Option<&EntityId> {
 /// 6.2.23 Emitter System record
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EmitterSystem {
     pub name: EmitterName,
     pub function: EmitterSystemFunction,
     pub number: u8,
     pub location: VectorF32,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub beams: Vec<Beam>,
 }
 
@@ -160,6 +164,7 @@ pub fn system_data_length_bytes(&self) -> u16 {
 
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Beam {
     pub number: u8,
     pub parameter_index: u16,
@@ -169,6 +174,7 @@ pub struct Beam {
     pub high_density_track_jam: HighDensityTrackJam,
     pub beam_status: BeamStatusBeamState,
     pub jamming_technique: JammingTechnique,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub track_jam_data: Vec<TrackJam>,
 }
 
@@ -263,6 +269,7 @@ pub fn beam_data_length_bytes(&self) -> u16 {
 /// 6.2.22 EE Fundamental Parameter Data record
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FundamentalParameterData {
     pub frequency: f32,
     pub frequency_range: f32,
@@ -317,6 +324,7 @@ pub fn with_pulse_width(mut self, pulse_width: f32) -> Self {
 /// 6.2.49 Jamming Technique record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct JammingTechnique {
     pub kind: u8,
     pub category: u8,
@@ -363,6 +371,7 @@ pub fn with_specific(mut self, specific: u8) -> Self {
 /// 6.2.90 Track/Jam Data record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TrackJam {
     pub entity_id: EntityId,
     pub emitter: u8,
@@ -1,3 +1,6 @@
+use crate::common::SupportedVersion;
+use crate::common::conversion::ConversionLoss;
+use crate::common::entity_state::model::EntityState;
 use crate::enumerations::{
     AirPlatformCapabilities, CulturalFeatureCapabilities,
     EntityCapabilities as EntityCapabilitiesV7, EnvironmentalCapabilities, ExpendableCapabilities,
@@ -6,6 +9,38 @@
     SupplyCapabilities, SurfacePlatformCapabilities,
 };
 use crate::v6::entity_state::model::EntityCapabilities;
+use crate::v7::entity_state::entity_capabilities_from_bytes;
+use alloc::vec::Vec;
+
+impl EntityState {
+    /// Adapts the fields of the body that differ between protocol versions to `version`.
+    ///
+    /// V6 only defines the ammunition supply, fuel supply, recovery and repair capabilities,
+    /// which are modelled as land platform capabilities. V7 defines the capabilities per entity kind.
+    pub(crate) fn convert_to(
+        &mut self,
+        version: &SupportedVersion,
+        losses: &mut Vec<ConversionLoss>,
+    ) {
+        let capabilities: EntityCapabilities = self.entity_capabilities.into();
+        let converted = entity_capabilities_from_bytes(
+            EntityCapabilitiesV7::from(capabilities).into(),
+            &self.entity_type,
+        );
+        let is_lossy = match version {
+            SupportedVersion::V6 => converted != self.entity_capabilities,
+            SupportedVersion::V7 => {
+                EntityCapabilities::from(converted)
+                    != EntityCapabilities::from(self.entity_capabilities)
+            }
+            SupportedVersion::Unsupported => false,
+        };
+        if is_lossy {
+            losses.push(ConversionLoss::Dropped("entity_state.entity_capabilities"));
+        }
+        self.entity_capabilities = converted;
+    }
+}
 
 impl From<EntityCapabilitiesV7> for EntityCapabilities {
     fn from(value: EntityCapabilitiesV7) -> Self {
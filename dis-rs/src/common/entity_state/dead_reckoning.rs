@@ -0,0 +1,432 @@
+//! Dead reckoning of entity location and orientation (Annex E).
+//!
+//! The location and orientation reported in an Entity State PDU are extrapolated using the
+//! Dead Reckoning Algorithm, Entity Linear Velocity and Dead Reckoning Parameters of the PDU.
+//! Velocity is reported in world coordinates for all algorithms. For the body axis algorithms (FPB, RPB, RVB and FVB)
+//! the linear acceleration and angular velocity are in body coordinates, and the motion follows the rotation of the body.
+use crate::common::entity_state::model::{DrParameters, EntityState};
+use crate::common::model::{Location, Orientation, VectorF32};
+use crate::enumerations::DeadReckoningAlgorithm;
+use crate::math::{acos, asin, atan2, cos, sin, sqrt};
+use core::time::Duration;
+
+type Vector = [f64; 3];
+type Matrix = [[f64; 3]; 3];
+
+/// Below this rotation angle (in radians) over the extrapolation interval, the series expansion of the rotation terms is used.
+const SMALL_ROTATION: f64 = 1e-3;
+
+const IDENTITY: Matrix = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+impl EntityState {
+    /// Returns the location and orientation of the entity, extrapolated `elapsed` after the time of this state,
+    /// using the dead reckoning algorithm of the state.
+    #[must_use]
+    pub fn dead_reckon(&self, elapsed: Duration) -> (Location, Orientation) {
+        dead_reckon(
+            &self.entity_location,
+            &self.entity_orientation,
+            &self.entity_linear_velocity,
+            &self.dead_reckoning_parameters,
+            elapsed,
+        )
+    }
+}
+
+/// Extrapolates `location` and `orientation` over `elapsed`, using `linear_velocity` (world coordinates)
+/// and the algorithm, linear acceleration and angular velocity of `parameters`.
+///
+/// The `Other` and `StaticNonmovingEntity` algorithms do not move the entity.
+#[must_use]
+pub fn dead_reckon(
+    location: &Location,
+    orientation: &Orientation,
+    linear_velocity: &VectorF32,
+    parameters: &DrParameters,
+    elapsed: Duration,
+) -> (Location, Orientation) {
+    let dt = elapsed.as_secs_f64();
+    let velocity = vector(linear_velocity);
+    let acceleration = vector(&parameters.linear_acceleration);
+    let angular_velocity = vector(&parameters.angular_velocity);
+
+    let (body_axis, rotates, accelerates) = match parameters.algorithm {
+        DeadReckoningAlgorithm::DRM_FPW_ConstantVelocityLowAccelerationLinearMotionEntity => {
+            (false, false, false)
+        }
+        DeadReckoningAlgorithm::DRM_RPW_ConstantVelocityLowAccelerationLinearMotionEntityWithExtrapolationOfOrientation => {
+            (false, true, false)
+        }
+        DeadReckoningAlgorithm::DRM_RVW_HighSpeedOrManeuveringEntityWithExtrapolationOfOrientation => {
+            (false, true, true)
+        }
+        DeadReckoningAlgorithm::DRM_FVW_HighSpeedOrManeuveringEntity => (false, false, true),
+        DeadReckoningAlgorithm::DRM_FPB_SimilarToFPWExceptInBodyCoordinates => {
+            (true, false, false)
+        }
+        DeadReckoningAlgorithm::DRM_RPB_SimilarToRPWExceptInBodyCoordinates => (true, true, false),
+        DeadReckoningAlgorithm::DRM_RVB_SimilarToRVWExceptInBodyCoordinates => (true, true, true),
+        DeadReckoningAlgorithm::DRM_FVB_SimilarToFVWExceptInBodyCoordinates => (true, false, true),
+        DeadReckoningAlgorithm::Other
+        | DeadReckoningAlgorithm::StaticNonmovingEntity
+        | DeadReckoningAlgorithm::Unspecified(_) => return (*location, *orientation),
+    };
+
+    let initial = orientation_matrix(orientation);
+    let displacement = if body_axis {
+        // E.7: velocity and acceleration in body coordinates, integrated along the rotating body axes
+        let (r1, r2) = integration_matrices(angular_velocity, dt);
+        let body_velocity = multiply_vector(&initial, velocity);
+        let body_acceleration = if accelerates { acceleration } else { [0.0; 3] };
+        let body_displacement = add(
+            multiply_vector(&r1, body_velocity),
+            multiply_vector(&r2, body_acceleration),
+        );
+        multiply_vector(&transpose(&initial), body_displacement)
+    } else {
+        let displacement = scale(velocity, dt);
+        if accelerates {
+            add(displacement, scale(acceleration, 0.5 * dt * dt))
+        } else {
+            displacement
+        }
+    };
+
+    let location = Location::new(
+        location.x_coordinate + displacement[0],
+        location.y_coordinate + displacement[1],
+        location.z_coordinate + displacement[2],
+    );
+    let orientation = if rotates {
+        orientation_from_matrix(&multiply(&rotation_matrix(angular_velocity, dt), &initial))
+    } else {
+        *orientation
+    };
+
+    (location, orientation)
+}
+
+/// Returns the distance in meters between two locations.
+#[must_use]
+pub fn location_difference(a: &Location, b: &Location) -> f64 {
+    magnitude([
+        a.x_coordinate - b.x_coordinate,
+        a.y_coordinate - b.y_coordinate,
+        a.z_coordinate - b.z_coordinate,
+    ])
+}
+
+/// Returns the angle in radians of the rotation between two orientations.
+#[must_use]
+pub fn orientation_difference(a: &Orientation, b: &Orientation) -> f64 {
+    let relative = multiply(&orientation_matrix(a), &transpose(&orientation_matrix(b)));
+    let trace = relative[0][0] + relative[1][1] + relative[2][2];
+    acos(((trace - 1.0) / 2.0).clamp(-1.0, 1.0))
+}
+
+/// Converts `offset`, in the entity coordinates of an entity at `location` with `orientation`, into world coordinates.
+#[must_use]
+pub fn entity_to_world(
+    location: &Location,
+    orientation: &Orientation,
+    offset: &VectorF32,
+) -> Location {
+    let world = multiply_vector(&transpose(&orientation_matrix(orientation)), vector(offset));
+    Location::new(
+        location.x_coordinate + world[0],
+        location.y_coordinate + world[1],
+        location.z_coordinate + world[2],
+    )
+}
+
+/// Converts `point`, in world coordinates, into the entity coordinates of an entity at `location` with `orientation`.
+#[must_use]
+pub fn world_to_entity(
+    location: &Location,
+    orientation: &Orientation,
+    point: &Location,
+) -> VectorF32 {
+    let body = multiply_vector(
+        &orientation_matrix(orientation),
+        [
+            point.x_coordinate - location.x_coordinate,
+            point.y_coordinate - location.y_coordinate,
+            point.z_coordinate - location.z_coordinate,
+        ],
+    );
+    VectorF32::new(body[0] as f32, body[1] as f32, body[2] as f32)
+}
+
+fn vector(value: &VectorF32) -> Vector {
+    [
+        f64::from(value.first_vector_component),
+        f64::from(value.second_vector_component),
+        f64::from(value.third_vector_component),
+    ]
+}
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(v: Vector, factor: f64) -> Vector {
+    [v[0] * factor, v[1] * factor, v[2] * factor]
+}
+
+fn magnitude(v: Vector) -> f64 {
+    sqrt(v[0] * v[0] + v[1] * v[1] + v[2] * v[2])
+}
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut result = [[0.0; 3]; 3];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    result
+}
+
+fn multiply_vector(m: &Matrix, v: Vector) -> Vector {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+fn transpose(m: &Matrix) -> Matrix {
+    [
+        [m[0][0], m[1][0], m[2][0]],
+        [m[0][1], m[1][1], m[2][1]],
+        [m[0][2], m[1][2], m[2][2]],
+    ]
+}
+
+/// Returns `a * w w^T + b * I + c * W`, where `W` is the skew-symmetric (cross product) matrix of `w`.
+fn combine(w: Vector, a: f64, b: f64, c: f64) -> Matrix {
+    [
+        [
+            a * w[0] * w[0] + b,
+            a * w[0] * w[1] - c * w[2],
+            a * w[0] * w[2] + c * w[1],
+        ],
+        [
+            a * w[1] * w[0] + c * w[2],
+            a * w[1] * w[1] + b,
+            a * w[1] * w[2] - c * w[0],
+        ],
+        [
+            a * w[2] * w[0] - c * w[1],
+            a * w[2] * w[1] + c * w[0],
+            a * w[2] * w[2] + b,
+        ],
+    ]
+}
+
+/// The dead reckoning rotation matrix (E.6), rotating the world-to-body orientation matrix over `dt`.
+fn rotation_matrix(angular_velocity: Vector, dt: f64) -> Matrix {
+    let omega = magnitude(angular_velocity);
+    if omega == 0.0 {
+        return IDENTITY;
+    }
+    let angle = omega * dt;
+    combine(
+        angular_velocity,
+        (1.0 - cos(angle)) / (omega * omega),
+        cos(angle),
+        -sin(angle) / omega,
+    )
+}
+
+/// The matrices `R1` and `R2` (E.7) that integrate a body velocity and body acceleration over `dt`.
+fn integration_matrices(angular_velocity: Vector, dt: f64) -> (Matrix, Matrix) {
+    let omega = magnitude(angular_velocity);
+    let angle = omega * dt;
+    if angle < SMALL_ROTATION {
+        return (
+            combine(angular_velocity, 0.0, dt, dt * dt / 2.0),
+            combine(angular_velocity, 0.0, dt * dt / 2.0, dt * dt * dt / 3.0),
+        );
+    }
+
+    let (sin_angle, cos_angle) = (sin(angle), cos(angle));
+    let omega_2 = omega * omega;
+    let omega_3 = omega_2 * omega;
+    let r1 = combine(
+        angular_velocity,
+        (angle - sin_angle) / omega_3,
+        sin_angle / omega,
+        (1.0 - cos_angle) / omega_2,
+    );
+    let r2 = combine(
+        angular_velocity,
+        (0.5 * angle * angle - cos_angle - angle * sin_angle + 1.0) / (omega_2 * omega_2),
+        (cos_angle + angle * sin_angle - 1.0) / omega_2,
+        (sin_angle - angle * cos_angle) / omega_3,
+    );
+    (r1, r2)
+}
+
+/// The world-to-body rotation matrix of `orientation` (Euler angles psi, theta, phi).
+#[allow(clippy::similar_names)]
+fn orientation_matrix(orientation: &Orientation) -> Matrix {
+    let (sin_psi, cos_psi) = (
+        sin(f64::from(orientation.psi)),
+        cos(f64::from(orientation.psi)),
+    );
+    let (sin_theta, cos_theta) = (
+        sin(f64::from(orientation.theta)),
+        cos(f64::from(orientation.theta)),
+    );
+    let (sin_phi, cos_phi) = (
+        sin(f64::from(orientation.phi)),
+        cos(f64::from(orientation.phi)),
+    );
+
+    [
+        [cos_theta * cos_psi, cos_theta * sin_psi, -sin_theta],
+        [
+            sin_phi * sin_theta * cos_psi - cos_phi * sin_psi,
+            sin_phi * sin_theta * sin_psi + cos_phi * cos_psi,
+            sin_phi * cos_theta,
+        ],
+        [
+            cos_phi * sin_theta * cos_psi + sin_phi * sin_psi,
+            cos_phi * sin_theta * sin_psi - sin_phi * cos_psi,
+            cos_phi * cos_theta,
+        ],
+    ]
+}
+
+fn orientation_from_matrix(m: &Matrix) -> Orientation {
+    Orientation::new(
+        atan2(m[0][1], m[0][0]) as f32,
+        asin((-m[0][2]).clamp(-1.0, 1.0)) as f32,
+        atan2(m[1][2], m[2][2]) as f32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::FRAC_PI_2;
+
+    fn parameters(algorithm: DeadReckoningAlgorithm) -> DrParameters {
+        DrParameters::default().with_algorithm(algorithm)
+    }
+
+    #[test]
+    fn static_entity_does_not_move() {
+        let location = Location::new(1.0, 2.0, 3.0);
+        let orientation = Orientation::new(0.1, 0.2, 0.3);
+        let (reckoned_location, reckoned_orientation) = dead_reckon(
+            &location,
+            &orientation,
+            &VectorF32::new(10.0, 0.0, 0.0),
+            &parameters(DeadReckoningAlgorithm::StaticNonmovingEntity),
+            Duration::from_secs(10),
+        );
+        assert_eq!(reckoned_location, location);
+        assert_eq!(reckoned_orientation, orientation);
+    }
+
+    #[test]
+    fn world_axis_with_acceleration() {
+        let (location, _) = dead_reckon(
+            &Location::default(),
+            &Orientation::default(),
+            &VectorF32::new(10.0, 0.0, -2.0),
+            &parameters(DeadReckoningAlgorithm::DRM_FVW_HighSpeedOrManeuveringEntity)
+                .with_linear_acceleration(VectorF32::new(0.0, 4.0, 0.0)),
+            Duration::from_secs(2),
+        );
+        assert!(location_difference(&location, &Location::new(20.0, 8.0, -4.0)) < 1e-9);
+    }
+
+    #[test]
+    fn world_axis_extrapolates_orientation() {
+        let (_, orientation) = dead_reckon(
+            &Location::default(),
+            &Orientation::default(),
+            &VectorF32::default(),
+            &parameters(
+                DeadReckoningAlgorithm::DRM_RPW_ConstantVelocityLowAccelerationLinearMotionEntityWithExtrapolationOfOrientation,
+            )
+            .with_angular_velocity(VectorF32::new(0.0, 0.0, 0.1)),
+            Duration::from_secs(5),
+        );
+        assert!((orientation.psi - 0.5).abs() < 1e-6);
+        assert!(orientation.theta.abs() < 1e-6);
+        assert!(orientation.phi.abs() < 1e-6);
+    }
+
+    #[test]
+    fn body_axis_turns_along_a_circle() {
+        // Turning a quarter circle at 10 m/s with a yaw rate of pi/20 rad/s, radius 200/pi m
+        let (location, orientation) = dead_reckon(
+            &Location::default(),
+            &Orientation::default(),
+            &VectorF32::new(10.0, 0.0, 0.0),
+            &parameters(DeadReckoningAlgorithm::DRM_RPB_SimilarToRPWExceptInBodyCoordinates)
+                .with_angular_velocity(VectorF32::new(0.0, 0.0, FRAC_PI_2 / 10.0)),
+            Duration::from_secs(10),
+        );
+        let radius = 200.0 / core::f64::consts::PI;
+        assert!(location_difference(&location, &Location::new(radius, radius, 0.0)) < 1e-3);
+        assert!((orientation.psi - FRAC_PI_2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn body_axis_small_rotation_matches_world_axis() {
+        let velocity = VectorF32::new(3.0, 4.0, 0.0);
+        let orientation = Orientation::new(0.3, 0.1, -0.2);
+        let (body, _) = dead_reckon(
+            &Location::default(),
+            &orientation,
+            &velocity,
+            &parameters(DeadReckoningAlgorithm::DRM_FPB_SimilarToFPWExceptInBodyCoordinates),
+            Duration::from_secs(4),
+        );
+        let (world, _) = dead_reckon(
+            &Location::default(),
+            &orientation,
+            &velocity,
+            &parameters(
+                DeadReckoningAlgorithm::DRM_FPW_ConstantVelocityLowAccelerationLinearMotionEntity,
+            ),
+            Duration::from_secs(4),
+        );
+        assert!(location_difference(&body, &world) < 1e-6);
+    }
+
+    #[test]
+    fn orientation_round_trip() {
+        let orientation = Orientation::new(1.2, -0.4, 2.5);
+        let round_trip = orientation_from_matrix(&orientation_matrix(&orientation));
+        assert!(orientation_difference(&orientation, &round_trip) < 1e-6);
+        assert!(
+            (orientation_difference(
+                &Orientation::new(0.0, 0.0, 0.0),
+                &Orientation::new(0.1, 0.0, 0.0)
+            ) - 0.1)
+                .abs()
+                < 1e-6
+        );
+    }
+
+    #[test]
+    fn entity_and_world_coordinates() {
+        let location = Location::new(100.0, 200.0, 300.0);
+        let orientation = Orientation::new(FRAC_PI_2, 0.0, 0.0);
+
+        // the x-axis of the entity points along the world y-axis
+        let world = entity_to_world(&location, &orientation, &VectorF32::new(10.0, 0.0, 2.0));
+        assert!(location_difference(&world, &Location::new(100.0, 210.0, 302.0)) < 1e-5);
+
+        let offset = world_to_entity(&location, &orientation, &world);
+        assert!((offset.first_vector_component - 10.0).abs() < 1e-5);
+        assert!(offset.second_vector_component.abs() < 1e-5);
+        assert!((offset.third_vector_component - 2.0).abs() < 1e-5);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod builder;
 mod compatibility;
+pub mod dead_reckoning;
 pub mod model;
 pub mod parser;
 pub mod writer;
@@ -1,9 +1,11 @@
+use crate::common::marking::{ENTITY_MARKING_LENGTH, validate_marking};
 use crate::common::model::{
     EntityId, EntityType, Location, Orientation, PduBody, VariableParameter, VectorF32,
 };
 use crate::common::{BodyInfo, Interaction};
 use crate::constants::{FOUR_OCTETS, TWELVE_OCTETS, VARIABLE_PARAMETER_RECORD_LENGTH};
 use crate::entity_state::builder::EntityStateBuilder;
+use crate::entity_state_update::model::EntityStateUpdate;
 use crate::enumerations::{
     AirPlatformAppearance, AppearanceEntityOrObjectState, CulturalFeatureAppearance,
     DeadReckoningAlgorithm, EntityCapabilities, EntityKind, EntityMarkingCharacterSet,
@@ -13,11 +15,7 @@
     SupplyAppearance, SurfacePlatformAppearance,
 };
 use crate::{BodyRaw, DisError};
-use alloc::{
-    format,
-    string::{String, ToString},
-    vec::Vec,
-};
+use alloc::{string::String, vec::Vec};
 use core::str::FromStr;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -66,6 +64,42 @@ fn into_pdu_body(self) -> PduBody {
     }
 }
 
+impl EntityState {
+    /// Creates the reduced Entity State Update for this state, containing the
+    /// Entity ID, linear velocity, location, orientation, appearance and variable parameters.
+    #[must_use]
+    pub fn to_update(&self) -> EntityStateUpdate {
+        EntityStateUpdate {
+            entity_id: self.entity_id,
+            entity_linear_velocity: self.entity_linear_velocity,
+            entity_location: self.entity_location,
+            entity_orientation: self.entity_orientation,
+            entity_appearance: self.entity_appearance,
+            variable_parameters: self.variable_parameters.clone(),
+        }
+    }
+
+    /// Merges the linear velocity, location, orientation, appearance and variable parameters of `update` into this state.
+    ///
+    /// The appearance of a parsed update is not typed, as the update does not contain the entity type.
+    /// It is decoded again using the entity type of this state.
+    ///
+    /// Returns `false`, without changing the state, when `update` is for another entity.
+    pub fn apply_update(&mut self, update: &EntityStateUpdate) -> bool {
+        if update.entity_id != self.entity_id {
+            return false;
+        }
+        self.entity_linear_velocity = update.entity_linear_velocity;
+        self.entity_location = update.entity_location;
+        self.entity_orientation = update.entity_orientation;
+        self.entity_appearance =
+            EntityAppearance::from_bytes(u32::from(&update.entity_appearance), &self.entity_type);
+        self.variable_parameters
+            .clone_from(&update.variable_parameters);
+        true
+    }
+}
+
 impl BodyInfo for EntityState {
     fn body_length(&self) -> u16 {
         BASE_ENTITY_STATE_BODY_LENGTH
@@ -183,6 +217,29 @@ pub fn state(&self) -> Option<AppearanceEntityOrObjectState> {
         }
     }
 
+    /// Sets the (Entity or Object) State field of the appearance.
+    /// An `Unspecified` appearance has no state field and is returned unchanged.
+    #[must_use]
+    pub fn with_state(mut self, state: AppearanceEntityOrObjectState) -> Self {
+        match &mut self {
+            EntityAppearance::LandPlatform(appearance) => appearance.state = state,
+            EntityAppearance::AirPlatform(appearance) => appearance.state = state,
+            EntityAppearance::SurfacePlatform(appearance) => appearance.state = state,
+            EntityAppearance::SubsurfacePlatform(appearance) => appearance.state = state,
+            EntityAppearance::SpacePlatform(appearance) => appearance.state = state,
+            EntityAppearance::Munition(appearance) => appearance.state = state,
+            EntityAppearance::LifeForms(appearance) => appearance.state = state,
+            EntityAppearance::Environmental(appearance) => appearance.state = state,
+            EntityAppearance::CulturalFeature(appearance) => appearance.state = state,
+            EntityAppearance::Supply(appearance) => appearance.state = state,
+            EntityAppearance::Radio(appearance) => appearance.state = state,
+            EntityAppearance::Expendable(appearance) => appearance.state = state,
+            EntityAppearance::SensorEmitter(appearance) => appearance.state = state,
+            EntityAppearance::Unspecified(_) => {}
+        }
+        self
+    }
+
     #[must_use]
     pub fn is_frozen(&self) -> Option<bool> {
         match self {
@@ -252,6 +309,33 @@ pub fn with_marking<S: Into<String>>(mut self, marking: S) -> Self {
         self
     }
 
+    /// Creates a marking, checking that `marking` can be encoded in `character_set`.
+    ///
+    /// # Errors
+    /// Returns a `DisError` when the character set is not supported, the marking is too long,
+    /// or contains a character that is not part of the character set.
+    pub fn try_new(
+        marking: impl Into<String>,
+        character_set: EntityMarkingCharacterSet,
+    ) -> Result<Self, DisError> {
+        let marking = marking.into();
+        validate_marking(&marking, character_set, ENTITY_MARKING_LENGTH)?;
+        Ok(Self::new(marking, character_set))
+    }
+
+    /// Checks that the marking string can be encoded in the marking character set.
+    ///
+    /// # Errors
+    /// Returns a `DisError` when the character set is not supported, the marking is too long,
+    /// or contains a character that is not part of the character set.
+    pub fn validate(&self) -> Result<(), DisError> {
+        validate_marking(
+            &self.marking_string,
+            self.marking_character_set,
+            ENTITY_MARKING_LENGTH,
+        )
+    }
+
     #[must_use]
     pub fn record_length(&self) -> u16 {
         TWELVE_OCTETS as u16
@@ -267,21 +351,14 @@ fn default() -> Self {
     }
 }
 
+/// Parses a marking in the ASCII character set.
+///
+/// Markings in another character set, such as Army Marking (CCTT) or Digit Chevron, are parsed using [`EntityMarking::try_new`].
 impl FromStr for EntityMarking {
     type Err = DisError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() <= 11 {
-            Ok(Self {
-                marking_character_set: EntityMarkingCharacterSet::ASCII,
-                marking_string: s.to_string(),
-            })
-        } else {
-            Err(DisError::ParseError(format!(
-                "String is too long for EntityMarking. Found {}, max 11 allowed.",
-                s.len()
-            )))
-        }
+        Self::try_new(s, EntityMarkingCharacterSet::ASCII)
     }
 }
 
@@ -324,6 +401,7 @@ pub fn with_angular_velocity(mut self, angular_velocity: VectorF32) -> Self {
 /// E.8 Use of the Other Parameters field in Dead Reckoning Parameters
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum DrOtherParameters {
     None([u8; 15]),
@@ -340,6 +418,7 @@ fn default() -> Self {
 /// Identical to Table 58—Euler Angles record / 6.2.32 Euler Angles record (which is modeled as `VectorF32`)
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DrEulerAngles {
     pub local_yaw: f32,
     pub local_pitch: f32,
@@ -369,6 +448,7 @@ pub fn with_local_roll(mut self, local_roll: f32) -> Self {
 /// Table E.3—World Orientation Quaternion Dead Reckoning Parameters (E.8.2.3 Rotating DRM entities)
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DrWorldOrientationQuaternion {
     pub nil: u16,
     pub x: f32,
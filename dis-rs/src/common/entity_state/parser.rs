@@ -3,9 +3,10 @@
     DrEulerAngles, DrOtherParameters, DrParameters, DrWorldOrientationQuaternion, EntityAppearance,
     EntityMarking, EntityState,
 };
+use crate::common::marking::decode_marking;
 use crate::common::model::{EntityType, PduBody, PduHeader};
 use crate::common::parser;
-use crate::common::parser::{entity_id, entity_type, sanitize_marking, vec3_f32};
+use crate::common::parser::{entity_id, entity_type, vec3_f32};
 use crate::enumerations::{
     DeadReckoningAlgorithm, EntityMarkingCharacterSet, ForceId, ProtocolVersion,
 };
@@ -87,16 +88,15 @@ pub(crate) fn entity_appearance(
 }
 
 /// Parses the marking portion of an `EntityState` PDU into an `EntityMarking` struct.
-/// It will convert the parsed bytes (always 11 bytes are present in the PDU) to UTF-8, and
-/// strip trailing whitespace and any trailing non-alphanumeric characters. In case the marking is less
-/// than 11 characters, the trailing bytes are typically 0x00 in the PDU, which in UTF-8 is a control character.
+/// The marking string ends at the first null octet (always 11 octets are present in the PDU), without trailing spaces.
+/// Octets that are not part of the character set are kept, see `EntityMarking::validate()`.
 pub(crate) fn entity_marking(input: &[u8]) -> IResult<&[u8], EntityMarking> {
     let mut buf: [u8; 11] = [0; 11];
     let (input, marking_character_set) = be_u8(input)?;
     let (input, ()) = nom::multi::fill(be_u8, &mut buf).parse(input)?;
 
     let marking_character_set = EntityMarkingCharacterSet::from(marking_character_set);
-    let marking_string = sanitize_marking(&buf[..]);
+    let marking_string = decode_marking(&buf[..]);
 
     Ok((
         input,
@@ -220,6 +220,7 @@ pub(crate) fn dr_other_parameters_quaternion(input: &[u8]) -> IResult<&[u8], DrO
 mod tests {
     use crate::common::entity_state::model::EntityAppearance;
     use crate::common::entity_state::parser::{entity_appearance, entity_marking};
+    use crate::common::errors::DisError;
     use crate::common::model::VariableParameter;
     use crate::common::model::{EntityType, PduBody};
     use crate::common::parser::{location, parse_pdu, variable_parameter};
@@ -376,6 +377,56 @@ fn parse_marking_trailing_control_chars() {
         assert!(input.is_empty());
     }
 
+    #[test]
+    fn parse_marking_army_marking() {
+        let bytes: [u8; 12] = [
+            0x02, 0x41, 0x31, 0x32, 0x20, 0x43, 0x33, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let (input, marking) = entity_marking(&bytes).unwrap();
+        assert_eq!(
+            marking.marking_character_set,
+            EntityMarkingCharacterSet::from(2)
+        );
+        assert_eq!(marking.marking_string, "A12 C3");
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn parse_marking_invalid_character() {
+        // lower case letters are not part of the Army Marking (CCTT) character set
+        let bytes: [u8; 12] = [
+            0x02, 0x41, 0x31, 0x32, 0x20, 0x63, 0x33, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let (input, marking) = entity_marking(&bytes).unwrap();
+        assert_eq!(marking.marking_string, "A12 c3");
+        assert_eq!(
+            marking.validate(),
+            Err(DisError::MarkingInvalidCharacter(
+                'c',
+                EntityMarkingCharacterSet::from(2)
+            ))
+        );
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn parse_marking_unsupported_character_set() {
+        let bytes: [u8; 12] = [
+            0x04, 0x45, 0x59, 0x45, 0x20, 0x31, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let (_input, marking) = entity_marking(&bytes).unwrap();
+        assert_eq!(marking.marking_string, "EYE 10");
+        assert_eq!(
+            marking.validate(),
+            Err(DisError::MarkingUnsupportedCharacterSet(
+                EntityMarkingCharacterSet::from(4)
+            ))
+        );
+    }
+
     #[test]
     fn parse_appearance_none() {
         let input: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
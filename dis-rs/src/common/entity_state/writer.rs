@@ -2,6 +2,7 @@
     DrEulerAngles, DrOtherParameters, DrWorldOrientationQuaternion, EntityAppearance,
 };
 use crate::common::entity_state::model::{DrParameters, EntityMarking, EntityState};
+use crate::common::marking::{ENTITY_MARKING_LENGTH, encode_marking};
 use crate::common::model::EntityType;
 use crate::common::{Serialize, SerializePdu, SupportedVersion};
 use crate::enumerations::{DrParametersType, ForceId};
@@ -141,10 +142,8 @@ fn serialize(&self, buf: &mut BytesMut) -> u16 {
 impl Serialize for EntityMarking {
     fn serialize(&self, buf: &mut BytesMut) -> u16 {
         buf.put_u8(self.marking_character_set.into());
-        let bytes = self.marking_string.as_bytes();
-        let len = bytes.len().min(11);
-        buf.put_slice(&bytes[..len]);
-        buf.put_bytes(0x20, 11 - len);
+        // validated against the character set by `Pdu::serialize`
+        buf.put_slice(&encode_marking(&self.marking_string, ENTITY_MARKING_LENGTH));
         12
     }
 }
@@ -156,6 +155,7 @@ mod tests {
     use crate::common::entity_state::model::{
         DrOtherParameters, DrParameters, EntityAppearance, EntityMarking, EntityState,
     };
+    use crate::common::errors::DisError;
     use crate::common::model::{
         ArticulatedPart, EntityId, EntityType, Location, Orientation, Pdu, PduHeader, TimeUnits,
         Timestamp, VariableParameter, VectorF32,
@@ -205,6 +205,42 @@ fn entity_marking_longer_than_field_is_clamped() {
         assert_eq!(buf.as_ref(), expected.as_ref());
     }
 
+    #[test]
+    fn entity_marking_outside_character_set() {
+        let army_marking = EntityMarkingCharacterSet::from(2);
+        let body = EntityState::builder()
+            .with_marking(EntityMarking::new("a1", army_marking))
+            .build()
+            .into_pdu_body();
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::EntityState),
+            body,
+            Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap()),
+        );
+        let mut buf = BytesMut::with_capacity(pdu.pdu_length() as usize);
+
+        assert_eq!(
+            pdu.serialize(&mut buf),
+            Err(DisError::MarkingInvalidCharacter('a', army_marking))
+        );
+    }
+
+    #[test]
+    fn entity_marking_too_long() {
+        let body = EntityState::builder()
+            .with_marking(EntityMarking::new_ascii("ABCDEFGHIJKLMN"))
+            .build()
+            .into_pdu_body();
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::EntityState),
+            body,
+            Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap()),
+        );
+        let mut buf = BytesMut::with_capacity(pdu.pdu_length() as usize);
+
+        assert_eq!(pdu.serialize(&mut buf), Err(DisError::StringTooLongError));
+    }
+
     #[test]
     fn articulated_part() {
         let articulated_part = VariableParameter::Articulated(ArticulatedPart {
@@ -6,13 +6,44 @@
 #[cfg(test)]
 mod tests {
     use crate::BodyRaw;
-    use crate::common::model::{Pdu, PduHeader, TimeUnits, Timestamp};
+    use crate::common::entity_state::model::{EntityAppearance, EntityState};
+    use crate::common::model::{
+        ArticulatedPart, Pdu, PduBody, PduHeader, TimeUnits, Timestamp, VariableParameter,
+    };
     use crate::common::parser::parse_pdu;
     use crate::entity_state_update::model::EntityStateUpdate;
-    use crate::enumerations::PduType;
-    use crate::model::{EntityId, Location, Orientation, VectorF32};
+    use crate::enumerations::{
+        ArticulatedPartsTypeClass, ArticulatedPartsTypeMetric, EntityKind, ForceId,
+        LandPlatformAppearance, PduType, PlatformDomain,
+    };
+    use crate::model::{EntityId, EntityType, Location, Orientation, VectorF32};
     use bytes::BytesMut;
 
+    fn tank() -> EntityState {
+        EntityState::builder()
+            .with_entity_id(EntityId::new(500, 900, 14))
+            .with_force_id(ForceId::Friendly)
+            .with_entity_type(
+                EntityType::default()
+                    .with_kind(EntityKind::Platform)
+                    .with_domain(PlatformDomain::Land),
+            )
+            .with_velocity(VectorF32::new(10.0, 0.0, 0.0))
+            .with_location(Location::new(4.0, 5.0, 6.0))
+            .with_orientation(Orientation::new(0.5, 0.0, 0.0))
+            .with_appearance(EntityAppearance::LandPlatform(
+                LandPlatformAppearance::default(),
+            ))
+            .with_variable_parameter(VariableParameter::Articulated(ArticulatedPart {
+                change_indicator: 1,
+                attachment_id: 0,
+                type_class: ArticulatedPartsTypeClass::PrimaryTurretNumber1,
+                type_metric: ArticulatedPartsTypeMetric::Azimuth,
+                parameter_value: 2.0,
+            }))
+            .build()
+    }
+
     #[test]
     fn entity_state_update_internal_consistency() {
         let header = PduHeader::new_v6(1, PduType::EntityStateUpdate);
@@ -48,4 +79,48 @@ fn entity_state_update_internal_consistency() {
             }
         }
     }
+
+    #[test]
+    fn entity_state_update_round_trip() {
+        let state = tank();
+        let update = state.to_update();
+        assert_eq!(update.entity_id, state.entity_id);
+        assert_eq!(update.variable_parameters, state.variable_parameters);
+
+        let pdu = Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, PduType::EntityStateUpdate),
+            update.into_pdu_body(),
+            Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap()),
+        );
+        let mut buf = BytesMut::with_capacity(pdu.header.pdu_length as usize);
+        pdu.serialize(&mut buf).unwrap();
+        let parsed = parse_pdu(&buf).unwrap();
+        let PduBody::EntityStateUpdate(update) = parsed.body else {
+            panic!("Expected an Entity State Update body");
+        };
+        // the update does not contain the entity type, so the appearance is parsed untyped
+        assert!(matches!(
+            update.entity_appearance,
+            EntityAppearance::Unspecified(_)
+        ));
+
+        let mut known = EntityState::builder()
+            .with_entity_id(state.entity_id)
+            .with_force_id(state.force_id)
+            .with_entity_type(state.entity_type)
+            .build();
+        assert!(known.apply_update(&update));
+        assert_eq!(known, state);
+    }
+
+    #[test]
+    fn apply_update_of_other_entity() {
+        let mut state = tank();
+        let mut update = state.to_update();
+        update.entity_id = EntityId::new(500, 900, 15);
+        update.entity_location = Location::new(1.0, 1.0, 1.0);
+
+        assert!(!state.apply_update(&update));
+        assert_eq!(state, tank());
+    }
 }
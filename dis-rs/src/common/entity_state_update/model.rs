@@ -18,12 +18,15 @@
 /// 7.2.5 Entity State Update PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EntityStateUpdate {
     pub entity_id: EntityId,
     pub entity_linear_velocity: VectorF32,
     pub entity_location: Location,
     pub entity_orientation: Orientation,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::unspecified_appearance))]
     pub entity_appearance: EntityAppearance,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub variable_parameters: Vec<VariableParameter>,
 }
 
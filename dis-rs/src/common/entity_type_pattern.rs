@@ -0,0 +1,342 @@
+//! Matching of Entity Type records (6.2.30) against patterns with wildcards and ranges.
+//!
+//! A pattern consists of the seven fields of an Entity Type record, separated by `.` (or `:`).
+//! Each field is either a wildcard `*`, a single value `225`, or an inclusive range `2-4`.
+//! Trailing fields that are omitted are wildcards, so `1.1.225` equals `1.1.225.*.*.*.*`.
+//!
+//! For example, `1.1.225.*.*.*.*` matches all US land platforms,
+//! and `2.*.*.2-4.*.*.*` matches munitions of categories 2 to 4.
+use crate::common::errors::DisError;
+use crate::common::model::EntityType;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const NUM_FIELDS: usize = 7;
+const WILDCARD: &str = "*";
+const RANGE_SEPARATOR: char = '-';
+const FIELD_NAMES: [&str; NUM_FIELDS] = [
+    "kind",
+    "domain",
+    "country",
+    "category",
+    "subcategory",
+    "specific",
+    "extra",
+];
+
+/// Pattern for a single field of an Entity Type record.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FieldPattern {
+    /// Matches any value.
+    #[default]
+    Any,
+    /// Matches precisely the value.
+    Value(u16),
+    /// Matches the values in the inclusive range `min` to `max`.
+    Range(u16, u16),
+}
+
+impl FieldPattern {
+    /// Checks whether `value` matches the pattern.
+    #[must_use]
+    pub fn matches(self, value: u16) -> bool {
+        match self {
+            FieldPattern::Any => true,
+            FieldPattern::Value(expected) => value == expected,
+            FieldPattern::Range(min, max) => (min..=max).contains(&value),
+        }
+    }
+
+    /// Parses a single field, which has a maximum value of `max_value`.
+    fn parse_field(s: &str, field_name: &str, max_value: u16) -> Result<Self, DisError> {
+        let parse_value = |value: &str| {
+            value
+                .trim()
+                .parse::<u16>()
+                .ok()
+                .filter(|value| *value <= max_value)
+                .ok_or_else(|| {
+                    DisError::ParseError(format!("Invalid {field_name} value '{value}'"))
+                })
+        };
+
+        let s = s.trim();
+        if s == WILDCARD {
+            Ok(FieldPattern::Any)
+        } else if let Some((min, max)) = s.split_once(RANGE_SEPARATOR) {
+            let (min, max) = (parse_value(min)?, parse_value(max)?);
+            if min > max {
+                return Err(DisError::ParseError(format!(
+                    "Invalid {field_name} range '{s}', the minimum exceeds the maximum"
+                )));
+            }
+            Ok(FieldPattern::Range(min, max))
+        } else {
+            Ok(FieldPattern::Value(parse_value(s)?))
+        }
+    }
+}
+
+impl Display for FieldPattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FieldPattern::Any => write!(f, "{WILDCARD}"),
+            FieldPattern::Value(value) => write!(f, "{value}"),
+            FieldPattern::Range(min, max) => write!(f, "{min}{RANGE_SEPARATOR}{max}"),
+        }
+    }
+}
+
+/// Pattern to match Entity Type records, such as `1.1.225.*.*.*.*` or `2.*.*.2-4.*.*.*`.
+///
+/// The default pattern matches any Entity Type.
+/// When the `serde` feature is enabled, the pattern (de)serializes from/to its string representation.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct EntityTypePattern {
+    pub kind: FieldPattern,
+    pub domain: FieldPattern,
+    pub country: FieldPattern,
+    pub category: FieldPattern,
+    pub subcategory: FieldPattern,
+    pub specific: FieldPattern,
+    pub extra: FieldPattern,
+}
+
+impl EntityTypePattern {
+    #[must_use]
+    pub fn with_kind(mut self, kind: FieldPattern) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    #[must_use]
+    pub fn with_domain(mut self, domain: FieldPattern) -> Self {
+        self.domain = domain;
+        self
+    }
+
+    #[must_use]
+    pub fn with_country(mut self, country: FieldPattern) -> Self {
+        self.country = country;
+        self
+    }
+
+    #[must_use]
+    pub fn with_category(mut self, category: FieldPattern) -> Self {
+        self.category = category;
+        self
+    }
+
+    #[must_use]
+    pub fn with_subcategory(mut self, subcategory: FieldPattern) -> Self {
+        self.subcategory = subcategory;
+        self
+    }
+
+    #[must_use]
+    pub fn with_specific(mut self, specific: FieldPattern) -> Self {
+        self.specific = specific;
+        self
+    }
+
+    #[must_use]
+    pub fn with_extra(mut self, extra: FieldPattern) -> Self {
+        self.extra = extra;
+        self
+    }
+
+    /// Checks whether `entity_type` matches the pattern.
+    #[must_use]
+    pub fn matches(&self, entity_type: &EntityType) -> bool {
+        self.kind.matches(u8::from(entity_type.kind).into())
+            && self.domain.matches(u8::from(entity_type.domain).into())
+            && self.country.matches(u16::from(entity_type.country))
+            && self.category.matches(entity_type.category.into())
+            && self.subcategory.matches(entity_type.subcategory.into())
+            && self.specific.matches(entity_type.specific.into())
+            && self.extra.matches(entity_type.extra.into())
+    }
+}
+
+impl From<EntityType> for EntityTypePattern {
+    /// Creates a pattern that matches precisely `value`.
+    fn from(value: EntityType) -> Self {
+        Self {
+            kind: FieldPattern::Value(u8::from(value.kind).into()),
+            domain: FieldPattern::Value(u8::from(value.domain).into()),
+            country: FieldPattern::Value(u16::from(value.country)),
+            category: FieldPattern::Value(value.category.into()),
+            subcategory: FieldPattern::Value(value.subcategory.into()),
+            specific: FieldPattern::Value(value.specific.into()),
+            extra: FieldPattern::Value(value.extra.into()),
+        }
+    }
+}
+
+impl Display for EntityTypePattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}.{}.{}.{}",
+            self.kind,
+            self.domain,
+            self.country,
+            self.category,
+            self.subcategory,
+            self.specific,
+            self.extra
+        )
+    }
+}
+
+impl FromStr for EntityTypePattern {
+    type Err = DisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ss = s.trim().split(['.', ':']).collect::<Vec<&str>>();
+        if ss.len() > NUM_FIELDS {
+            return Err(DisError::ParseError(format!(
+                "EntityTypePattern string contains more than {NUM_FIELDS} fields"
+            )));
+        }
+
+        let mut fields = [FieldPattern::Any; NUM_FIELDS];
+        for (index, field) in ss.iter().enumerate() {
+            let max_value = if index == 2 { u16::MAX } else { u8::MAX.into() };
+            fields[index] = FieldPattern::parse_field(field, FIELD_NAMES[index], max_value)?;
+        }
+        let [
+            kind,
+            domain,
+            country,
+            category,
+            subcategory,
+            specific,
+            extra,
+        ] = fields;
+
+        Ok(Self {
+            kind,
+            domain,
+            country,
+            category,
+            subcategory,
+            specific,
+            extra,
+        })
+    }
+}
+
+impl TryFrom<&str> for EntityTypePattern {
+    type Error = DisError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        EntityTypePattern::from_str(value)
+    }
+}
+
+impl TryFrom<String> for EntityTypePattern {
+    type Error = DisError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        TryFrom::<&str>::try_from(&value)
+    }
+}
+
+impl From<EntityTypePattern> for String {
+    fn from(value: EntityTypePattern) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enumerations::{Country, EntityKind, PlatformDomain};
+
+    fn us_tank() -> EntityType {
+        EntityType::default()
+            .with_kind(EntityKind::Platform)
+            .with_domain(PlatformDomain::Land)
+            .with_country(Country::from(225))
+            .with_category(1)
+            .with_subcategory(1)
+            .with_specific(3)
+    }
+
+    #[test]
+    fn parse_pattern() {
+        let pattern = EntityTypePattern::from_str("2.*.*.2-4.*.*.*").unwrap();
+        assert_eq!(
+            pattern,
+            EntityTypePattern::default()
+                .with_kind(FieldPattern::Value(2))
+                .with_category(FieldPattern::Range(2, 4))
+        );
+        assert_eq!(pattern.to_string(), "2.*.*.2-4.*.*.*");
+    }
+
+    #[test]
+    fn parse_pattern_omitted_fields() {
+        assert_eq!(
+            EntityTypePattern::from_str("1:1:225").unwrap(),
+            EntityTypePattern::from_str("1.1.225.*.*.*.*").unwrap()
+        );
+        assert_eq!(
+            EntityTypePattern::from_str("*").unwrap(),
+            EntityTypePattern::default()
+        );
+    }
+
+    #[test]
+    fn parse_pattern_errors() {
+        assert!(EntityTypePattern::from_str("1.1.225.*.*.*.*.*").is_err());
+        assert!(EntityTypePattern::from_str("256.*").is_err());
+        assert!(EntityTypePattern::from_str("1.1.65535").is_ok());
+        assert!(EntityTypePattern::from_str("1.1.x").is_err());
+        assert!(EntityTypePattern::from_str("1.1.225.4-2").is_err());
+    }
+
+    #[test]
+    fn match_pattern() {
+        let us_land_platforms = EntityTypePattern::from_str("1.1.225.*.*.*.*").unwrap();
+        let us_tanks = EntityTypePattern::from_str("1.1.225.1.1-2").unwrap();
+        let munitions = EntityTypePattern::from_str("2.*.*.2-4.*.*.*").unwrap();
+
+        assert!(us_land_platforms.matches(&us_tank()));
+        assert!(us_tanks.matches(&us_tank()));
+        assert!(!munitions.matches(&us_tank()));
+        assert!(!us_land_platforms.matches(&us_tank().with_domain(PlatformDomain::Air)));
+        assert!(EntityTypePattern::default().matches(&us_tank()));
+        assert!(EntityTypePattern::from(us_tank()).matches(&us_tank()));
+        assert!(!EntityTypePattern::from(us_tank()).matches(&us_tank().with_extra(1)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn pattern_serde_roundtrip() {
+        let expected = serde_json::Value::String(String::from("2.*.*.2-4.*.*.*"));
+
+        let pattern_de = serde_json::from_value::<EntityTypePattern>(expected.clone()).unwrap();
+        assert_eq!(
+            pattern_de,
+            EntityTypePattern::default()
+                .with_kind(FieldPattern::Value(2))
+                .with_category(FieldPattern::Range(2, 4))
+        );
+
+        let pattern_ser = serde_json::to_value(pattern_de).unwrap();
+        assert_eq!(pattern_ser, expected);
+
+        assert!(
+            serde_json::from_value::<EntityTypePattern>(serde_json::Value::from("1.x")).is_err()
+        );
+    }
+}
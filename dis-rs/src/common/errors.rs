@@ -1,10 +1,12 @@
 use crate::constants::PDU_HEADER_LEN_BYTES;
+use crate::enumerations::{
+    EntityMarkingCharacterSet, PduType, ProtocolVersion, VariableParameterRecordType,
+};
 use alloc::string::String;
 use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, Error)]
 pub enum DisError {
-    // UnsupportedProtocolVersion,
     #[error("{0}")]
     ParseError(String), // the parsing of a PDU resulted in an error
     #[error(
@@ -21,8 +23,20 @@ pub enum DisError {
     StringNotAsciiError, // the String value to serialize is not valid ASCII encoded
     #[error("Provided String is too long.")]
     StringTooLongError, // the String value to serialize is too large for the field specification
+    #[error("Character {0:?} is not part of the {1} marking character set.")]
+    MarkingInvalidCharacter(char, EntityMarkingCharacterSet), // the marking contains a character that cannot be encoded in the character set
+    #[error("Marking character set {0} is not supported.")]
+    MarkingUnsupportedCharacterSet(EntityMarkingCharacterSet), // the character set of the marking is not supported
     #[error("IFF PDU - Incorrect System Time provided.")]
     IffIncorrectSystemType, // the System Type in an IFF PDU is incorrect (to determine the type for parsing the basic data)
     #[error("IFF PDU - Undetermined System Time.")]
     IffUndeterminedSystemType, // the System Type in an IFF PDU does not determine whether it is an Interrogator or a Transponder
+    #[error("Protocol version {0} is not supported.")]
+    UnsupportedProtocolVersion(ProtocolVersion), // the requested protocol version is not supported by the crate
+    #[error("PDU type {0} is not defined in protocol version {1}.")]
+    UnsupportedPduType(PduType, ProtocolVersion), // the PDU type does not exist in the requested protocol version
+    #[error("PDU type {0} is not in the experimental range (129-255).")]
+    NotExperimentalPduType(PduType), // a custom PDU type must be in the range reserved for experimentation
+    #[error("Variable parameter record type {0} is defined by SISO-REF-010.")]
+    StandardVariableParameterType(VariableParameterRecordType), // a custom variable parameter record type must not be a standard record type
 }
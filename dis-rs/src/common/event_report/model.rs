@@ -21,6 +21,7 @@
 /// 7.5.12 Event Report PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EventReport {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
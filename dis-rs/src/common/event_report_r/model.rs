@@ -19,6 +19,7 @@
 /// 7.11.12 Event Report-R PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EventReportR {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -0,0 +1,26 @@
+use crate::common::SupportedVersion;
+use crate::common::conversion::ConversionLoss;
+use crate::common::fire::model::{Fire, FireDescriptor};
+use crate::common::model::MunitionDescriptor;
+use alloc::vec::Vec;
+
+impl Fire {
+    /// Adapts the fields of the body that differ between protocol versions to `version`.
+    ///
+    /// V6 only defines the Munition Descriptor record; an Expendable Descriptor is approximated
+    /// by a Munition Descriptor for the same entity type.
+    pub(crate) fn convert_to(
+        &mut self,
+        version: &SupportedVersion,
+        losses: &mut Vec<ConversionLoss>,
+    ) {
+        if let (SupportedVersion::V6, FireDescriptor::Expendable(expendable)) =
+            (version, &self.descriptor)
+        {
+            self.descriptor = FireDescriptor::Munition(
+                MunitionDescriptor::default().with_entity_type(expendable.entity_type),
+            );
+            losses.push(ConversionLoss::Approximated("fire.descriptor"));
+        }
+    }
+}
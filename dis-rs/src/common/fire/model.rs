@@ -15,6 +15,7 @@
 /// 7.3.2 Fire PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Fire {
     pub firing_entity_id: EntityId,
     pub target_entity_id: EntityId,
@@ -66,6 +67,7 @@ fn receiver(&self) -> Option<&EntityId> {
 /// 6.2.19 Fire Descriptor record
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum FireDescriptor {
     #[cfg_attr(feature = "serde", serde(rename = "munition"))]
     Munition(MunitionDescriptor),
@@ -0,0 +1,346 @@
+//! Dispatching of PDUs to handlers per PDU type.
+//!
+//! Implement [`PduHandler`] and override the methods for the PDU types of interest, then call [`dispatch`] for each received PDU.
+//! All methods default to doing nothing. [`AsyncPduHandler`] and [`dispatch_async`] are the asynchronous equivalents.
+//!
+//! The traits and dispatch functions are generated from a single list of the `PduBody` variants,
+//! and the generated `match` on `PduBody` is exhaustive. A PDU type that is added to `PduBody` thus must be added to the list,
+//! giving it a hook in both traits.
+//!
+//! ```
+//! # use dis_rs::handler::{dispatch, PduHandler};
+//! # use dis_rs::model::{Pdu, PduHeader};
+//! # use dis_rs::entity_state::model::EntityState;
+//! #[derive(Default)]
+//! struct EntityCounter {
+//!     entity_states: usize,
+//! }
+//!
+//! impl PduHandler for EntityCounter {
+//!     fn on_entity_state(&mut self, _header: &PduHeader, _body: &EntityState) {
+//!         self.entity_states += 1;
+//!     }
+//! }
+//!
+//! fn handle(pdus: &[Pdu]) -> usize {
+//!     let mut counter = EntityCounter::default();
+//!     pdus.iter().for_each(|pdu| dispatch(pdu, &mut counter));
+//!     counter.entity_states
+//! }
+//! ```
+use crate::common::acknowledge::model::Acknowledge;
+use crate::common::acknowledge_r::model::AcknowledgeR;
+use crate::common::action_request::model::ActionRequest;
+use crate::common::action_request_r::model::ActionRequestR;
+use crate::common::action_response::model::ActionResponse;
+use crate::common::action_response_r::model::ActionResponseR;
+use crate::common::aggregate_state::model::AggregateState;
+use crate::common::attribute::model::Attribute;
+use crate::common::collision::model::Collision;
+use crate::common::collision_elastic::model::CollisionElastic;
+use crate::common::comment::model::Comment;
+use crate::common::comment_r::model::CommentR;
+use crate::common::create_entity::model::CreateEntity;
+use crate::common::create_entity_r::model::CreateEntityR;
+use crate::common::custom::model::CustomPdu;
+use crate::common::data::model::Data;
+use crate::common::data_query::model::DataQuery;
+use crate::common::data_query_r::model::DataQueryR;
+use crate::common::data_r::model::DataR;
+use crate::common::designator::model::Designator;
+use crate::common::detonation::model::Detonation;
+use crate::common::electromagnetic_emission::model::ElectromagneticEmission;
+use crate::common::entity_state::model::EntityState;
+use crate::common::entity_state_update::model::EntityStateUpdate;
+use crate::common::event_report::model::EventReport;
+use crate::common::event_report_r::model::EventReportR;
+use crate::common::fire::model::Fire;
+use crate::common::iff::model::Iff;
+use crate::common::is_group_of::model::IsGroupOf;
+use crate::common::is_part_of::model::IsPartOf;
+use crate::common::model::{Pdu, PduBody, PduHeader};
+use crate::common::receiver::model::Receiver;
+use crate::common::record_query_r::model::RecordQueryR;
+use crate::common::record_r::model::RecordR;
+use crate::common::remove_entity::model::RemoveEntity;
+use crate::common::remove_entity_r::model::RemoveEntityR;
+use crate::common::repair_complete::model::RepairComplete;
+use crate::common::repair_response::model::RepairResponse;
+use crate::common::resupply_cancel::model::ResupplyCancel;
+use crate::common::resupply_offer::model::ResupplyOffer;
+use crate::common::resupply_received::model::ResupplyReceived;
+use crate::common::sees::model::SEES;
+use crate::common::service_request::model::ServiceRequest;
+use crate::common::set_data::model::SetData;
+use crate::common::set_data_r::model::SetDataR;
+use crate::common::set_record_r::model::SetRecordR;
+use crate::common::signal::model::Signal;
+use crate::common::start_resume::model::StartResume;
+use crate::common::start_resume_r::model::StartResumeR;
+use crate::common::stop_freeze::model::StopFreeze;
+use crate::common::stop_freeze_r::model::StopFreezeR;
+use crate::common::transfer_ownership::model::TransferOwnership;
+use crate::common::transmitter::model::Transmitter;
+use crate::common::underwater_acoustic::model::UnderwaterAcoustic;
+use core::future::{Future, ready};
+
+/// Generates the handler traits and dispatch functions.
+///
+/// `bodies` lists the `PduBody` variants that have a body model, with the name of their handler method and the type of the body.
+/// `unsupported` lists the `PduBody` variants without a body model, which are handled by `on_unknown` together with `Other`.
+macro_rules! pdu_handlers {
+    (
+        bodies: { $( $variant:ident($body:ty) => $method:ident ),* $(,)? },
+        unsupported: { $( $unsupported:ident ),* $(,)? } $(,)?
+    ) => {
+        /// Handler for PDUs, with a method per PDU type. Use [`dispatch`] to call the method matching a PDU.
+        ///
+        /// All methods do nothing by default.
+        pub trait PduHandler {
+            /// Handles the header of each PDU, before the method for the body is called.
+            fn on_header(&mut self, _header: &PduHeader) {}
+
+            /// Handles PDUs of types that are not supported by the crate (parsed as `PduBody::Other`),
+            /// or that have no body model yet.
+            fn on_unknown(&mut self, _pdu: &Pdu) {}
+
+            /// Handles PDUs of a user-defined type, parsed as `PduBody::Custom`.
+            fn on_custom(&mut self, _header: &PduHeader, _body: &dyn CustomPdu) {}
+
+            $(
+                #[doc = concat!("Handles a PDU with a `", stringify!($body), "` body.")]
+                fn $method(&mut self, _header: &PduHeader, _body: &$body) {}
+            )*
+        }
+
+        /// Asynchronous handler for PDUs, with a method per PDU type. Use [`dispatch_async`] to call the method matching a PDU.
+        ///
+        /// All methods do nothing by default.
+        pub trait AsyncPduHandler {
+            /// Handles the header of each PDU, before the method for the body is called.
+            fn on_header(&mut self, _header: &PduHeader) -> impl Future<Output = ()> + Send {
+                ready(())
+            }
+
+            /// Handles PDUs of types that are not supported by the crate (parsed as `PduBody::Other`),
+            /// or that have no body model yet.
+            fn on_unknown(&mut self, _pdu: &Pdu) -> impl Future<Output = ()> + Send {
+                ready(())
+            }
+
+            /// Handles PDUs of a user-defined type, parsed as `PduBody::Custom`.
+            fn on_custom(
+                &mut self,
+                _header: &PduHeader,
+                _body: &dyn CustomPdu,
+            ) -> impl Future<Output = ()> + Send {
+                ready(())
+            }
+
+            $(
+                #[doc = concat!("Handles a PDU with a `", stringify!($body), "` body.")]
+                fn $method(
+                    &mut self,
+                    _header: &PduHeader,
+                    _body: &$body,
+                ) -> impl Future<Output = ()> + Send {
+                    ready(())
+                }
+            )*
+        }
+
+        /// Calls [`PduHandler::on_header`] and then the method of `handler` that matches the body of `pdu`.
+        pub fn dispatch<H: PduHandler + ?Sized>(pdu: &Pdu, handler: &mut H) {
+            handler.on_header(&pdu.header);
+            match &pdu.body {
+                $( PduBody::$variant(body) => handler.$method(&pdu.header, body), )*
+                PduBody::Custom(body) => handler.on_custom(&pdu.header, body.as_ref()),
+                PduBody::Other(_) $( | PduBody::$unsupported )* => handler.on_unknown(pdu),
+            }
+        }
+
+        /// Awaits [`AsyncPduHandler::on_header`] and then the method of `handler` that matches the body of `pdu`.
+        pub async fn dispatch_async<H: AsyncPduHandler>(pdu: &Pdu, handler: &mut H) {
+            handler.on_header(&pdu.header).await;
+            match &pdu.body {
+                $( PduBody::$variant(body) => handler.$method(&pdu.header, body).await, )*
+                PduBody::Custom(body) => handler.on_custom(&pdu.header, body.as_ref()).await,
+                PduBody::Other(_) $( | PduBody::$unsupported )* => handler.on_unknown(pdu).await,
+            }
+        }
+    };
+}
+
+pdu_handlers! {
+    bodies: {
+        EntityState(EntityState) => on_entity_state,
+        Fire(Fire) => on_fire,
+        Detonation(Detonation) => on_detonation,
+        Collision(Collision) => on_collision,
+        ServiceRequest(ServiceRequest) => on_service_request,
+        ResupplyOffer(ResupplyOffer) => on_resupply_offer,
+        ResupplyReceived(ResupplyReceived) => on_resupply_received,
+        ResupplyCancel(ResupplyCancel) => on_resupply_cancel,
+        RepairComplete(RepairComplete) => on_repair_complete,
+        RepairResponse(RepairResponse) => on_repair_response,
+        CreateEntity(CreateEntity) => on_create_entity,
+        RemoveEntity(RemoveEntity) => on_remove_entity,
+        StartResume(StartResume) => on_start_resume,
+        StopFreeze(StopFreeze) => on_stop_freeze,
+        Acknowledge(Acknowledge) => on_acknowledge,
+        ActionRequest(ActionRequest) => on_action_request,
+        ActionResponse(ActionResponse) => on_action_response,
+        DataQuery(DataQuery) => on_data_query,
+        SetData(SetData) => on_set_data,
+        Data(Data) => on_data,
+        EventReport(EventReport) => on_event_report,
+        Comment(Comment) => on_comment,
+        ElectromagneticEmission(ElectromagneticEmission) => on_electromagnetic_emission,
+        Designator(Designator) => on_designator,
+        Transmitter(Transmitter) => on_transmitter,
+        Signal(Signal) => on_signal,
+        Receiver(Receiver) => on_receiver,
+        IFF(Iff) => on_iff,
+        UnderwaterAcoustic(UnderwaterAcoustic) => on_underwater_acoustic,
+        SupplementalEmissionEntityState(SEES) => on_supplemental_emission_entity_state,
+        AggregateState(AggregateState) => on_aggregate_state,
+        IsGroupOf(IsGroupOf) => on_is_group_of,
+        TransferOwnership(TransferOwnership) => on_transfer_ownership,
+        IsPartOf(IsPartOf) => on_is_part_of,
+        CreateEntityR(CreateEntityR) => on_create_entity_r,
+        RemoveEntityR(RemoveEntityR) => on_remove_entity_r,
+        StartResumeR(StartResumeR) => on_start_resume_r,
+        StopFreezeR(StopFreezeR) => on_stop_freeze_r,
+        AcknowledgeR(AcknowledgeR) => on_acknowledge_r,
+        ActionRequestR(ActionRequestR) => on_action_request_r,
+        ActionResponseR(ActionResponseR) => on_action_response_r,
+        DataQueryR(DataQueryR) => on_data_query_r,
+        SetDataR(SetDataR) => on_set_data_r,
+        DataR(DataR) => on_data_r,
+        EventReportR(EventReportR) => on_event_report_r,
+        CommentR(CommentR) => on_comment_r,
+        RecordR(RecordR) => on_record_r,
+        SetRecordR(SetRecordR) => on_set_record_r,
+        RecordQueryR(RecordQueryR) => on_record_query_r,
+        CollisionElastic(CollisionElastic) => on_collision_elastic,
+        EntityStateUpdate(EntityStateUpdate) => on_entity_state_update,
+        Attribute(Attribute) => on_attribute,
+    },
+    unsupported: {
+        IntercomSignal,
+        IntercomControl,
+        MinefieldState,
+        MinefieldQuery,
+        MinefieldData,
+        MinefieldResponseNACK,
+        EnvironmentalProcess,
+        GriddedData,
+        PointObjectState,
+        LinearObjectState,
+        ArealObjectState,
+        TSPI,
+        Appearance,
+        ArticulatedParts,
+        LEFire,
+        LEDetonation,
+        DirectedEnergyFire,
+        EntityDamageStatus,
+        InformationOperationsAction,
+        InformationOperationsReport,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyRaw;
+    use crate::common::model::{EntityId, TimeUnits, Timestamp};
+    use crate::common::other::model::Other;
+    use crate::enumerations::PduType;
+    use alloc::vec::Vec;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<&'static str>,
+    }
+
+    impl PduHandler for Recorder {
+        fn on_header(&mut self, _header: &PduHeader) {
+            self.calls.push("header");
+        }
+
+        fn on_unknown(&mut self, _pdu: &Pdu) {
+            self.calls.push("unknown");
+        }
+
+        fn on_entity_state(&mut self, _header: &PduHeader, body: &EntityState) {
+            assert_eq!(body.entity_id, EntityId::new(1, 2, 3));
+            self.calls.push("entity_state");
+        }
+    }
+
+    impl AsyncPduHandler for Recorder {
+        async fn on_fire(&mut self, _header: &PduHeader, _body: &Fire) {
+            self.calls.push("fire");
+        }
+    }
+
+    fn timestamp() -> Timestamp {
+        Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap())
+    }
+
+    fn pdu(pdu_type: PduType, body: PduBody) -> Pdu {
+        Pdu::finalize_from_parts(PduHeader::new_v7(1, pdu_type), body, timestamp())
+    }
+
+    #[test]
+    fn dispatch_to_handler() {
+        let mut recorder = Recorder::default();
+
+        dispatch(
+            &pdu(
+                PduType::EntityState,
+                EntityState::builder()
+                    .with_entity_id(EntityId::new(1, 2, 3))
+                    .build()
+                    .into_pdu_body(),
+            ),
+            &mut recorder,
+        );
+        dispatch(
+            &pdu(PduType::Fire, Fire::builder().build().into_pdu_body()),
+            &mut recorder,
+        );
+        dispatch(
+            &pdu(PduType::Other, Other::builder().build().into_pdu_body()),
+            &mut recorder,
+        );
+        dispatch(&pdu(PduType::TSPI, PduBody::TSPI), &mut recorder);
+
+        assert_eq!(
+            recorder.calls,
+            [
+                "header",
+                "entity_state",
+                "header",
+                "header",
+                "unknown",
+                "header",
+                "unknown"
+            ]
+        );
+    }
+
+    #[test]
+    fn dispatch_to_async_handler() {
+        let mut recorder = Recorder::default();
+        let fire = pdu(PduType::Fire, Fire::builder().build().into_pdu_body());
+        let poll = pin!(dispatch_async(&fire, &mut recorder))
+            .poll(&mut Context::from_waker(Waker::noop()));
+
+        assert_eq!(poll, Poll::Ready(()));
+        assert_eq!(recorder.calls, ["fire"]);
+    }
+}
@@ -118,6 +118,7 @@ fn receiver(&self) -> Option<&EntityId> {
 /// contains two 8-bit records.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IffLayer2 {
     pub layer_header: LayerHeader,
     pub beam_data: BeamData,
@@ -168,6 +169,7 @@ pub fn finalize_layer_header_length(mut self) -> Self {
 /// 7.6.5.4.3 Layer 3 Mode 5 Transponder Format
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IffLayer3 {
     pub layer_header: LayerHeader,
     pub reporting_simulation: SimulationAddress,
@@ -212,6 +214,7 @@ pub fn finalize_layer_header_length(mut self) -> Self {
 /// Interrogator or a Transponder in an IFF Layer 3 Mode 5 PDU
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Mode5BasicData {
     Interrogator(Mode5InterrogatorBasicData), // 7.6.5.4.2 Layer 3 Mode 5 Interrogator Format
@@ -239,6 +242,7 @@ pub fn new_transponder(basic_data: Mode5TransponderBasicData) -> Self {
 /// 7.6.5.5 Layer 4 Mode S formats
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IffLayer4 {
     pub layer_header: LayerHeader,
     pub reporting_simulation: SimulationAddress,
@@ -283,6 +287,7 @@ pub fn finalize_layer_header_length(mut self) -> Self {
 /// Interrogator or a Transponder in an IFF Layer 4 Mode S PDU
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ModeSBasicData {
     Interrogator(ModeSInterrogatorBasicData), // 7.6.5.5.2 Layer 4 Mode S Interrogator Format
@@ -310,6 +315,7 @@ pub fn new_transponder(basic_data: ModeSTransponderBasicData) -> Self {
 /// 7.6.5.6 Layer 5 data communications
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IffLayer5 {
     pub layer_header: LayerHeader,
     pub reporting_simulation: SimulationAddress,
@@ -463,6 +469,7 @@ fn from(value: &ChangeOptionsRecord) -> Self {
 /// 6.2.39 Fundamental Operational Data record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FundamentalOperationalData {
     pub system_status: SystemStatus,
     pub data_field_1: u8,
@@ -487,6 +494,7 @@ pub fn builder() -> FundamentalOperationalDataBuilder {
 /// `FundamentalOperationalData` record.
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ParameterCapable {
     #[default]
     Capable,
@@ -497,6 +505,7 @@ pub enum ParameterCapable {
 /// `FundamentalOperationalData` record.
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum OperationalStatus {
     #[default]
     Operational,
@@ -507,6 +516,7 @@ pub enum OperationalStatus {
 /// as used in IFF Layer 1.
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum LayersPresenceApplicability {
     #[default]
     NotPresentApplicable, // 0
@@ -542,6 +552,7 @@ pub fn data_length(&self) -> u16 {
 /// 6.2.43 IFF Data Specification record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IffDataSpecification {
     pub iff_data_records: Vec<IffDataRecord>,
 }
@@ -635,6 +646,7 @@ fn from(value: &InformationLayers) -> Self {
 /// 6.2.44 IFF Fundamental Parameter Data Record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IffFundamentalParameterData {
     pub erp: f32,
     pub frequency: f32,
@@ -655,6 +667,7 @@ pub fn builder() -> IffFundamentalParameterDataBuilder {
 /// 6.2.51 Layer Header
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LayerHeader {
     pub layer_number: u8,
     pub layer_specific_information: u8,
@@ -676,6 +689,7 @@ pub fn builder() -> LayerHeaderBuilder {
 // TODO placeholder for 24-bits - See Annex B.
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SystemSpecificData {
     pub part_1: u8,
     pub part_2: u8,
@@ -697,6 +711,7 @@ pub fn builder() -> SystemSpecificDataBuilder {
 /// 6.2.87 System Identifier record
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SystemId {
     pub system_type: IffSystemType,
     pub system_name: IffSystemName,
@@ -792,6 +807,7 @@ fn from(value: &DapSource) -> Self {
 /// Custom defined enum to model values in the DAP Source record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DapValue {
     #[default]
     ComputeLocally, // 0
@@ -876,6 +892,7 @@ fn from(value: &EnhancedMode1Code) -> Self {
 /// B.2.26 Mode 5 Interrogator Basic Data record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Mode5InterrogatorBasicData {
     pub status: Mode5InterrogatorStatus, // B.2.27 Mode 5 Interrogator Status record - page 592
     pub mode_5_message_formats_present: Mode5MessageFormats, // B.2.28 Mode 5 Message Formats record - page 592
@@ -1146,6 +1163,7 @@ fn from(value: &Mode5MessageFormats) -> Self {
 /// B.2.29 Mode 5 Transponder Basic Data record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Mode5TransponderBasicData {
     pub status: Mode5TransponderStatus,
     pub pin: u16,
@@ -1172,6 +1190,7 @@ pub fn builder() -> Mode5TransponderBasicDataBuilder {
 /// Custom defined enum to model a system being On or Off.
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum OnOffStatus {
     #[default]
     Off, // 0
@@ -1181,6 +1200,7 @@ pub enum OnOffStatus {
 /// Custom defined enum to model a system being Not Damaged or Damaged.
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DamageStatus {
     #[default]
     NoDamage, // 0
@@ -1190,6 +1210,7 @@ pub enum DamageStatus {
 /// Custom defined enum to model a system being Not Malfunctioning or Malfunctioning.
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum MalfunctionStatus {
     #[default]
     NoMalfunction, // 0
@@ -1199,6 +1220,7 @@ pub enum MalfunctionStatus {
 /// Custom defined enum to model a system being Not Enabled or Enabled.
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum EnabledStatus {
     #[default]
     NotEnabled, // 0
@@ -1209,6 +1231,7 @@ pub enum EnabledStatus {
 /// Mode 5 latitude, longitude, and altitude information.
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum LatLonAltSource {
     #[default]
     ComputeLocally, // 0
@@ -1406,6 +1429,7 @@ fn from(value: &ModeSAltitude) -> Self {
 /// B.2.37 Mode S Interrogator Basic Data record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ModeSInterrogatorBasicData {
     pub mode_s_interrogator_status: ModeSInterrogatorStatus,
     pub mode_s_levels_present: ModeSLevelsPresent,
@@ -1531,6 +1555,7 @@ fn from(value: &ModeSLevelsPresent) -> Self {
 /// Custom defined enum to model the presence of an element in an IFF system
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum IffPresence {
     #[default]
     NotPresent, // 0
@@ -1540,10 +1565,12 @@ pub enum IffPresence {
 /// B.2.41 Mode S Transponder Basic Data record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ModeSTransponderBasicData {
     pub status: ModeSTransponderStatus,
     pub levels_present: ModeSLevelsPresent,
     pub aircraft_present_domain: AircraftPresentDomain,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::aircraft_identification))]
     pub aircraft_identification: String, // B.2.35 - String of length 8, in ASCII.
     pub aircraft_address: u32,
     pub aircraft_identification_type: AircraftIdentificationType,
@@ -1669,6 +1696,7 @@ fn from(value: &ModeSTransponderStatus) -> Self {
 /// Custom defined enum to model the `SquitterStatus`
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SquitterStatus {
     #[default]
     Off, // 0
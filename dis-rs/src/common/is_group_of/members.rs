@@ -0,0 +1,219 @@
+//! Expansion of the grouped entities of an `IsGroupOf` PDU into absolute locations.
+//!
+//! The Group Entity Description records locate each member as an offset in meters from the group reference point.
+//! The offsets are 16-bit two's complement values along the East, North and Up axes of the
+//! local tangent plane at the reference point, which lies on the WGS-84 ellipsoid.
+use crate::common::entity_state::model::EntityAppearance;
+use crate::common::is_group_of::model::{GEDEntityLocation, GroupEntityDescription, IsGroupOf};
+use crate::common::model::{EntityId, Location};
+use crate::math::{cos, sin};
+use crate::utils::geodetic_lla_to_ecef;
+use alloc::vec::Vec;
+
+/// A member of a group, with its absolute location in world (ECEF) coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupMember {
+    pub entity_id: EntityId,
+    pub location: Location,
+    pub appearance: EntityAppearance,
+}
+
+impl IsGroupOf {
+    /// Returns the members of the group with their absolute locations.
+    ///
+    /// The entity ID of each member combines the simulation address of the group with the entity number of its description.
+    /// `Undefined` descriptions are skipped.
+    #[must_use]
+    pub fn members(&self) -> Vec<GroupMember> {
+        let latitude = self.group_reference_point.latitude;
+        let longitude = self.group_reference_point.longitude;
+        let reference = geodetic_lla_to_ecef(latitude, longitude, 0.0);
+
+        let (sin_lat, cos_lat) = (sin(latitude), cos(latitude));
+        let (sin_lon, cos_lon) = (sin(longitude), cos(longitude));
+        let east = [-sin_lon, cos_lon, 0.0];
+        let north = [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat];
+        let up = [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat];
+
+        self.descriptions
+            .iter()
+            .filter_map(|description| {
+                let entity_number = description.entity_id()?;
+                let offset = description.location()?;
+                let appearance = description.appearance()?;
+                let (east_offset, north_offset, up_offset) = (
+                    f64::from(offset.x_offset as i16),
+                    f64::from(offset.y_offset as i16),
+                    f64::from(offset.z_offset as i16),
+                );
+                let axis = |index: usize| {
+                    east_offset * east[index] + north_offset * north[index] + up_offset * up[index]
+                };
+
+                Some(GroupMember {
+                    entity_id: EntityId::new_sim_address(
+                        self.group_id.simulation_address,
+                        entity_number,
+                    ),
+                    location: Location::new(
+                        reference.0 + axis(0),
+                        reference.1 + axis(1),
+                        reference.2 + axis(2),
+                    ),
+                    appearance: *appearance,
+                })
+            })
+            .collect()
+    }
+}
+
+impl GroupEntityDescription {
+    /// Returns the entity number of the described member, or `None` for an `Undefined` description.
+    #[must_use]
+    pub fn entity_id(&self) -> Option<u16> {
+        match self {
+            GroupEntityDescription::Undefined => None,
+            GroupEntityDescription::BasicGroundCombatVehicle(ged) => Some(ged.entity_id),
+            GroupEntityDescription::EnhancedGroundCombatVehicle(ged) => {
+                Some(ged.basic_ground_combat_vehicle.entity_id)
+            }
+            GroupEntityDescription::BasicGroundCombatSoldier(ged) => Some(ged.entity_id),
+            GroupEntityDescription::EnhancedGroundCombatSoldier(ged) => {
+                Some(ged.basic_ground_combat_soldier.entity_id)
+            }
+            GroupEntityDescription::BasicRotorWingAircraft(ged) => Some(ged.entity_id),
+            GroupEntityDescription::EnhancedRotorWingAircraft(ged) => {
+                Some(ged.basic_rotor_wing_aircraft.entity_id)
+            }
+            GroupEntityDescription::BasicFixedWingAircraft(ged) => Some(ged.entity_id),
+            GroupEntityDescription::EnhancedFixedWingAircraft(ged) => {
+                Some(ged.basic_fixed_wing_aircraft.entity_id)
+            }
+            GroupEntityDescription::GroundLogisticsVehicle(ged) => Some(ged.entity_id),
+        }
+    }
+
+    /// Returns the location offset of the described member, or `None` for an `Undefined` description.
+    #[must_use]
+    pub fn location(&self) -> Option<&GEDEntityLocation> {
+        match self {
+            GroupEntityDescription::Undefined => None,
+            GroupEntityDescription::BasicGroundCombatVehicle(ged) => Some(&ged.location),
+            GroupEntityDescription::EnhancedGroundCombatVehicle(ged) => {
+                Some(&ged.basic_ground_combat_vehicle.location)
+            }
+            GroupEntityDescription::BasicGroundCombatSoldier(ged) => Some(&ged.location),
+            GroupEntityDescription::EnhancedGroundCombatSoldier(ged) => {
+                Some(&ged.basic_ground_combat_soldier.location)
+            }
+            GroupEntityDescription::BasicRotorWingAircraft(ged) => Some(&ged.location),
+            GroupEntityDescription::EnhancedRotorWingAircraft(ged) => {
+                Some(&ged.basic_rotor_wing_aircraft.location)
+            }
+            GroupEntityDescription::BasicFixedWingAircraft(ged) => Some(&ged.location),
+            GroupEntityDescription::EnhancedFixedWingAircraft(ged) => {
+                Some(&ged.basic_fixed_wing_aircraft.location)
+            }
+            GroupEntityDescription::GroundLogisticsVehicle(ged) => Some(&ged.location),
+        }
+    }
+
+    /// Returns the appearance of the described member, or `None` for an `Undefined` description.
+    #[must_use]
+    pub fn appearance(&self) -> Option<&EntityAppearance> {
+        match self {
+            GroupEntityDescription::Undefined => None,
+            GroupEntityDescription::BasicGroundCombatVehicle(ged) => Some(&ged.appearance),
+            GroupEntityDescription::EnhancedGroundCombatVehicle(ged) => {
+                Some(&ged.basic_ground_combat_vehicle.appearance)
+            }
+            GroupEntityDescription::BasicGroundCombatSoldier(ged) => Some(&ged.appearance),
+            GroupEntityDescription::EnhancedGroundCombatSoldier(ged) => {
+                Some(&ged.basic_ground_combat_soldier.appearance)
+            }
+            GroupEntityDescription::BasicRotorWingAircraft(ged) => Some(&ged.appearance),
+            GroupEntityDescription::EnhancedRotorWingAircraft(ged) => {
+                Some(&ged.basic_rotor_wing_aircraft.appearance)
+            }
+            GroupEntityDescription::BasicFixedWingAircraft(ged) => Some(&ged.appearance),
+            GroupEntityDescription::EnhancedFixedWingAircraft(ged) => {
+                Some(&ged.basic_fixed_wing_aircraft.appearance)
+            }
+            GroupEntityDescription::GroundLogisticsVehicle(ged) => Some(&ged.appearance),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::is_group_of::model::{
+        GEDRecord1, GEDRecord2, GEDRecord9, GroupReferencePoint,
+    };
+    use crate::enumerations::IsGroupOfGroupedEntityCategory;
+    use alloc::vec;
+
+    fn offset(x_offset: i16, y_offset: i16, z_offset: i16) -> GEDEntityLocation {
+        GEDEntityLocation {
+            x_offset: x_offset as u16,
+            y_offset: y_offset as u16,
+            z_offset: z_offset as u16,
+        }
+    }
+
+    #[test]
+    fn members_at_equator() {
+        let group = IsGroupOf {
+            group_id: EntityId::new(1, 2, 3),
+            grouped_entity_category: IsGroupOfGroupedEntityCategory::EnhancedGroundCombatVehicle,
+            group_reference_point: GroupReferencePoint::default(),
+            descriptions: vec![
+                GroupEntityDescription::EnhancedGroundCombatVehicle(GEDRecord2 {
+                    basic_ground_combat_vehicle: GEDRecord1 {
+                        entity_id: 10,
+                        location: offset(10, 20, 5),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+                GroupEntityDescription::Undefined,
+                GroupEntityDescription::GroundLogisticsVehicle(GEDRecord9 {
+                    entity_id: 11,
+                    location: offset(-10, 0, 0),
+                    ..Default::default()
+                }),
+            ],
+        };
+
+        let members = group.members();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].entity_id, EntityId::new(1, 2, 10));
+        assert_eq!(members[0].location, Location::new(6_378_142.0, 10.0, 20.0));
+        assert_eq!(members[1].entity_id, EntityId::new(1, 2, 11));
+        assert_eq!(members[1].location, Location::new(6_378_137.0, -10.0, 0.0));
+    }
+
+    #[test]
+    fn members_keep_their_distance() {
+        let group = IsGroupOf {
+            group_reference_point: GroupReferencePoint::default()
+                .with_latitude(0.9)
+                .with_longitude(-0.3),
+            descriptions: vec![
+                GroupEntityDescription::BasicGroundCombatVehicle(GEDRecord1::default()),
+                GroupEntityDescription::BasicGroundCombatVehicle(GEDRecord1 {
+                    location: offset(30, -40, 0),
+                    ..Default::default()
+                }),
+            ],
+            ..Default::default()
+        };
+
+        let members = group.members();
+        let distance = crate::common::entity_state::dead_reckoning::location_difference(
+            &members[0].location,
+            &members[1].location,
+        );
+        assert!((distance - 50.0).abs() < 1e-6);
+    }
+}
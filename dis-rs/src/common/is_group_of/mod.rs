@@ -1,4 +1,5 @@
 pub mod builder;
+pub mod members;
 pub mod model;
 pub mod parser;
 pub mod writer;
@@ -70,6 +70,7 @@ fn receiver(&self) -> Option<&EntityId> {
 /// Custom defined record.
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GroupReferencePoint {
     pub latitude: f64,
     pub longitude: f64,
@@ -98,6 +99,7 @@ pub const fn record_length(&self) -> u16 {
 /// Group Entity Description (GED) records
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum GroupEntityDescription {
     #[default]
     Undefined,
@@ -132,6 +134,7 @@ pub const fn record_length(&self) -> u16 {
 
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDEntityLocation {
     pub x_offset: u16,
     pub y_offset: u16,
@@ -147,6 +150,7 @@ pub const fn record_length(&self) -> u16 {
 
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDEntityOrientation {
     pub psi: u8,
     pub theta: u8,
@@ -163,9 +167,11 @@ pub const fn record_length(&self) -> u16 {
 /// UID 215
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord1 {
     pub entity_id: u16,
     pub location: GEDEntityLocation,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::land_platform_appearance))]
     pub appearance: EntityAppearance,
     pub orientation: GEDEntityOrientation,
     pub speed: u8,
@@ -188,6 +194,7 @@ pub const fn record_length(&self) -> u16 {
 /// UID 216
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord2 {
     pub basic_ground_combat_vehicle: GEDRecord1,
     pub fuel_status: u8,
@@ -206,9 +213,11 @@ pub const fn record_length(&self) -> u16 {
 /// UID 217
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord3 {
     pub entity_id: u16,
     pub location: GEDEntityLocation,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::land_platform_appearance))]
     pub appearance: EntityAppearance,
     pub orientation: GEDEntityOrientation,
     pub speed: u8,
@@ -231,6 +240,7 @@ pub const fn record_length(&self) -> u16 {
 /// UID 218
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord4 {
     pub basic_ground_combat_soldier: GEDRecord3,
     pub water_status: u8,
@@ -249,9 +259,11 @@ pub const fn record_length(&self) -> u16 {
 /// UID 219
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord5 {
     pub entity_id: u16,
     pub location: GEDEntityLocation,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::air_platform_appearance))]
     pub appearance: EntityAppearance,
     pub orientation: GEDEntityOrientation,
     pub fuel_status: u8,
@@ -277,6 +289,7 @@ pub const fn record_length(&self) -> u16 {
 /// UID 220
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord6 {
     pub basic_rotor_wing_aircraft: GEDRecord5,
     pub supplemental_fuel_status: u8,
@@ -295,9 +308,11 @@ pub const fn record_length(&self) -> u16 {
 /// UID 221
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord7 {
     pub entity_id: u16,
     pub location: GEDEntityLocation,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::air_platform_appearance))]
     pub appearance: EntityAppearance,
     pub orientation: GEDEntityOrientation,
     pub fuel_status: u8,
@@ -319,6 +334,7 @@ pub const fn record_length(&self) -> u16 {
 /// UID 222
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord8 {
     pub basic_fixed_wing_aircraft: GEDRecord7,
     pub supplemental_fuel_status: u8,
@@ -337,9 +353,11 @@ pub const fn record_length(&self) -> u16 {
 /// UID 223
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GEDRecord9 {
     pub entity_id: u16,
     pub location: GEDEntityLocation,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::land_platform_appearance))]
     pub appearance: EntityAppearance,
     pub orientation: GEDEntityOrientation,
     pub speed: u16,
@@ -13,6 +13,7 @@
 /// 7.8.5 `IsPartOf` PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct IsPartOf {
     pub originating_simulation_id: EntityId,
     pub receiving_entity_id: EntityId,
@@ -61,6 +62,7 @@ fn receiver(&self) -> Option<&EntityId> {
 /// 6.2.74 Relationship record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Relationship {
     pub nature: IsPartOfNature,
     pub position: IsPartOfPosition,
@@ -83,6 +85,7 @@ pub fn with_position(mut self, position: IsPartOfPosition) -> Self {
 /// 6.2.62 Named Location Identification record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct NamedLocationId {
     pub station_name: StationName,
     pub station_number: u16,
@@ -0,0 +1,216 @@
+//! Encoding and decoding of the marking strings of the Entity Marking (6.2.29) and Aggregate Marking (6.2.4) records,
+//! according to the `EntityMarkingCharacterSet` of the record.
+//!
+//! The supported character sets are:
+//! - Unused (0) and ASCII (1): printable ASCII characters (`0x20` to `0x7E`).
+//! - Army Marking (CCTT) (2): upper case letters `A` to `Z`, digits `0` to `9` and space.
+//! - Digit Chevron (3): digits `0` to `9`, the chevrons `<` and `>`, and space.
+//!
+//! All character sets encode one character per octet. Marking strings are terminated by the first
+//! null octet, and trailing spaces are not part of the marking.
+//! SISO-REF-010 does not define a Unicode (or other multi-octet) character set for markings,
+//! so other values are rejected as unsupported.
+//!
+//! Decoding is lenient, so that a marking does not make a whole received PDU invalid:
+//! each octet is decoded to the character with the same code point (ISO 8859-1), also when it is not part
+//! of the character set. Use `EntityMarking::validate()` / `AggregateMarking::validate()` to check a received marking.
+//!
+//! Encoding is strict: `Pdu::serialize` validates the markings of a PDU against their character set,
+//! and fails with a `DisError::MarkingInvalidCharacter` or `DisError::StringTooLongError` instead of writing an invalid marking.
+use crate::common::errors::DisError;
+use crate::enumerations::EntityMarkingCharacterSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Length of the marking string of an Entity Marking record, in octets.
+pub(crate) const ENTITY_MARKING_LENGTH: usize = 11;
+/// Length of the marking string of an Aggregate Marking record, in octets.
+pub(crate) const AGGREGATE_MARKING_LENGTH: usize = 31;
+
+const CHARACTER_SET_UNUSED: u8 = 0;
+const CHARACTER_SET_ASCII: u8 = 1;
+const CHARACTER_SET_ARMY_MARKING_CCTT: u8 = 2;
+const CHARACTER_SET_DIGIT_CHEVRON: u8 = 3;
+
+/// Checks whether character `c` is part of `character_set`.
+///
+/// # Errors
+/// Returns a `DisError::MarkingUnsupportedCharacterSet` when the character set is not supported.
+fn is_in_character_set(
+    character_set: EntityMarkingCharacterSet,
+    c: char,
+) -> Result<bool, DisError> {
+    match u8::from(character_set) {
+        CHARACTER_SET_UNUSED | CHARACTER_SET_ASCII => Ok(matches!(c, ' '..='~')),
+        CHARACTER_SET_ARMY_MARKING_CCTT => {
+            Ok(c.is_ascii_uppercase() || c.is_ascii_digit() || c == ' ')
+        }
+        CHARACTER_SET_DIGIT_CHEVRON => Ok(c.is_ascii_digit() || matches!(c, '<' | '>' | ' ')),
+        _ => Err(DisError::MarkingUnsupportedCharacterSet(character_set)),
+    }
+}
+
+/// Validates that `marking` can be encoded in `character_set` within `max_length` octets.
+///
+/// # Errors
+/// Returns a `DisError` when the character set is not supported, the marking is too long,
+/// or contains a character that is not part of the character set.
+pub(crate) fn validate_marking(
+    marking: &str,
+    character_set: EntityMarkingCharacterSet,
+    max_length: usize,
+) -> Result<(), DisError> {
+    for c in marking.chars() {
+        if !is_in_character_set(character_set, c)? {
+            return Err(DisError::MarkingInvalidCharacter(c, character_set));
+        }
+    }
+    if marking.len() > max_length {
+        return Err(DisError::StringTooLongError);
+    }
+    Ok(())
+}
+
+/// Encodes `marking`, clamped to and padded with spaces to `length` octets.
+///
+/// The marking is expected to be validated using [`validate_marking`], as done by `Pdu::serialize`,
+/// which ensures it consists of single octet characters only.
+pub(crate) fn encode_marking(marking: &str, length: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = marking.bytes().take(length).collect();
+    bytes.resize(length, b' ');
+    bytes
+}
+
+/// Decodes the octets of a marking string, one character per octet, up to the first null octet
+/// and without trailing spaces.
+///
+/// Octets are not checked against the character set of the marking, see [`validate_marking`].
+pub(crate) fn decode_marking(buf: &[u8]) -> String {
+    let end = buf.iter().position(|byte| *byte == 0).unwrap_or(buf.len());
+    let marking: String = buf[..end].iter().map(|byte| char::from(*byte)).collect();
+    String::from(marking.trim_end_matches(' '))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::entity_state::model::EntityMarking;
+    use core::str::FromStr;
+
+    fn army_marking() -> EntityMarkingCharacterSet {
+        EntityMarkingCharacterSet::from(CHARACTER_SET_ARMY_MARKING_CCTT)
+    }
+
+    fn digit_chevron() -> EntityMarkingCharacterSet {
+        EntityMarkingCharacterSet::from(CHARACTER_SET_DIGIT_CHEVRON)
+    }
+
+    #[test]
+    fn validate_ascii() {
+        assert_eq!(
+            validate_marking("EYE 10", EntityMarkingCharacterSet::ASCII, 11),
+            Ok(())
+        );
+        assert_eq!(
+            validate_marking("ÉYE", EntityMarkingCharacterSet::ASCII, 11),
+            Err(DisError::MarkingInvalidCharacter(
+                'É',
+                EntityMarkingCharacterSet::ASCII
+            ))
+        );
+        assert_eq!(
+            validate_marking("ABCDEFGHIJKL", EntityMarkingCharacterSet::ASCII, 11),
+            Err(DisError::StringTooLongError)
+        );
+    }
+
+    #[test]
+    fn validate_army_marking() {
+        assert_eq!(validate_marking("A12 C3", army_marking(), 11), Ok(()));
+        assert_eq!(
+            validate_marking("a12", army_marking(), 11),
+            Err(DisError::MarkingInvalidCharacter('a', army_marking()))
+        );
+    }
+
+    #[test]
+    fn validate_digit_chevron() {
+        assert_eq!(validate_marking("12<>3", digit_chevron(), 11), Ok(()));
+        assert_eq!(
+            validate_marking("12A", digit_chevron(), 11),
+            Err(DisError::MarkingInvalidCharacter('A', digit_chevron()))
+        );
+    }
+
+    #[test]
+    fn encode_pads_and_clamps() {
+        assert_eq!(encode_marking("EYE 10", 11), b"EYE 10     ".to_vec());
+        assert_eq!(encode_marking("ABCDEFGHIJKL", 11), b"ABCDEFGHIJK".to_vec());
+    }
+
+    #[test]
+    fn decode_stops_at_null() {
+        assert_eq!(
+            decode_marking(b"EYE 10\0\xFF\xFF\xFF\xFF"),
+            String::from("EYE 10")
+        );
+        assert_eq!(decode_marking(b"12<>3\0\0\0\0\0\0"), String::from("12<>3"));
+    }
+
+    #[test]
+    fn decode_characters_outside_character_set() {
+        let marking = decode_marking(b"EYE\xFF10     ");
+        assert_eq!(marking, String::from("EYE\u{FF}10"));
+        assert_eq!(
+            validate_marking(&marking, EntityMarkingCharacterSet::ASCII, 11),
+            Err(DisError::MarkingInvalidCharacter(
+                '\u{FF}',
+                EntityMarkingCharacterSet::ASCII
+            ))
+        );
+    }
+
+    #[test]
+    fn unsupported_character_set() {
+        let undefined = EntityMarkingCharacterSet::from(4);
+        assert_eq!(
+            validate_marking("EYE 10", undefined, 11),
+            Err(DisError::MarkingUnsupportedCharacterSet(undefined))
+        );
+    }
+
+    #[test]
+    fn entity_marking_from_str() {
+        assert_eq!(
+            EntityMarking::from_str("EYE 10"),
+            Ok(EntityMarking::new_ascii("EYE 10"))
+        );
+        assert_eq!(
+            EntityMarking::from_str("ÉYE"),
+            Err(DisError::MarkingInvalidCharacter(
+                'É',
+                EntityMarkingCharacterSet::ASCII
+            ))
+        );
+        assert_eq!(
+            EntityMarking::from_str("ABCDEFGHIJKL"),
+            Err(DisError::StringTooLongError)
+        );
+    }
+
+    #[test]
+    fn entity_marking_other_character_sets() {
+        assert_eq!(
+            EntityMarking::try_new("A12 C3", army_marking()),
+            Ok(EntityMarking::new("A12 C3", army_marking()))
+        );
+        assert_eq!(
+            EntityMarking::try_new("a12", army_marking()),
+            Err(DisError::MarkingInvalidCharacter('a', army_marking()))
+        );
+        assert_eq!(
+            EntityMarking::try_new("12<>3", digit_chevron()),
+            Ok(EntityMarking::new("12<>3", digit_chevron()))
+        );
+    }
+}
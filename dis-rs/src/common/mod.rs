@@ -10,24 +10,32 @@
 pub mod action_response_r;
 pub mod aggregate_state;
 pub mod attribute;
+pub mod clock_offset;
 pub mod collision;
 pub mod collision_elastic;
 pub mod comment;
 pub mod comment_r;
+pub mod conversion;
 pub mod create_entity;
 pub mod create_entity_r;
+pub mod custom;
 pub mod data;
 pub mod data_query;
 pub mod data_query_r;
 pub mod data_r;
+pub mod datum;
 pub mod designator;
 pub mod detonation;
+#[cfg(feature = "serde")]
+pub mod diff;
 pub mod electromagnetic_emission;
 pub mod entity_state;
 pub mod entity_state_update;
+pub mod entity_type_pattern;
 pub mod event_report;
 pub mod event_report_r;
 pub mod fire;
+pub mod handler;
 pub mod iff;
 pub mod is_group_of;
 pub mod is_part_of;
@@ -35,6 +43,7 @@
 pub mod receiver;
 pub mod record_query_r;
 pub mod record_r;
+pub mod remapping;
 pub mod remove_entity;
 pub mod remove_entity_r;
 pub mod repair_complete;
@@ -55,13 +64,18 @@
 pub mod transfer_ownership;
 pub mod transmitter;
 pub mod underwater_acoustic;
+pub mod unwrapped_timestamp;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 pub mod errors;
+mod marking;
 mod writer;
 
+use crate::common::custom::registry::CustomPduRegistry;
 use crate::common::errors::DisError;
 use crate::common::model::Pdu;
-use crate::common::parser::parse_multiple_pdu;
+use crate::common::parser::{parse_multiple_pdu, parse_multiple_pdu_with_registry};
 use crate::enumerations::{PduType, ProtocolVersion};
 use crate::model::PduBody;
 use alloc::vec::Vec;
@@ -154,6 +168,21 @@ pub fn parse(input: &[u8]) -> Result<Vec<Pdu>, DisError> {
     parse_multiple_pdu(input)
 }
 
+/// Parses the contents of the input like [`parse`], parsing PDUs with a type registered in `registry` as `PduBody::Custom`.
+///
+/// Parsing is all-or-nothing: when a single registered body or variable parameter record is not valid,
+/// none of the PDUs in the input are returned. To keep the other PDUs of the input, use [`parse`] and
+/// [`CustomPduRegistry::resolve`] for each PDU.
+///
+/// # Errors
+/// Returns a `DisError` when parsing fails, including the parsing of the body of a registered PDU type
+pub fn parse_with_registry(
+    input: &[u8],
+    registry: &CustomPduRegistry,
+) -> Result<Vec<Pdu>, DisError> {
+    parse_multiple_pdu_with_registry(input, Some(registry))
+}
+
 /// Parses the contents of the input as DIS version 6.
 /// This function tries to parse as many PDUs as there are in the buffer,
 /// assuming there are only complete PDUs present in the input.
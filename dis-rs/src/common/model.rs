@@ -12,6 +12,7 @@
 use crate::common::collision_elastic::model::CollisionElastic;
 use crate::common::comment::model::Comment;
 use crate::common::create_entity::model::CreateEntity;
+use crate::common::custom::model::{CustomPdu, CustomVariableParameter};
 use crate::common::data::model::Data;
 use crate::common::data_query::model::DataQuery;
 use crate::common::designator::model::Designator;
@@ -71,6 +72,7 @@
 use crate::transfer_ownership::model::TransferOwnership;
 use crate::underwater_acoustic::model::UnderwaterAcoustic;
 use alloc::{
+    boxed::Box,
     format,
     string::{String, ToString},
     vec::Vec,
@@ -260,6 +262,10 @@ pub enum PduBody {
     InformationOperationsAction,
     InformationOperationsReport,
     Attribute(Attribute),
+    /// The body of a user-defined PDU type, see [`CustomPduRegistry`](crate::custom::registry::CustomPduRegistry).
+    /// Custom bodies are not (de)serialized using `serde`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Box<dyn CustomPdu>),
 }
 
 impl BodyInfo for PduBody {
@@ -339,6 +345,7 @@ fn body_length(&self) -> u16 {
             PduBody::InformationOperationsAction => 0,
             PduBody::InformationOperationsReport => 0,
             PduBody::Attribute(body) => body.body_length(),
+            PduBody::Custom(body) => body.body_length(),
         }
     }
 
@@ -417,6 +424,7 @@ fn body_type(&self) -> PduType {
             PduBody::InformationOperationsAction => PduType::InformationOperationsAction,
             PduBody::InformationOperationsReport => PduType::InformationOperationsReport,
             PduBody::Attribute(body) => body.body_type(),
+            PduBody::Custom(body) => body.body_type(),
         }
     }
 }
@@ -498,6 +506,7 @@ fn originator(&self) -> Option<&EntityId> {
             PduBody::InformationOperationsAction => None,
             PduBody::InformationOperationsReport => None,
             PduBody::Attribute(body) => body.originator(),
+            PduBody::Custom(body) => body.originator(),
         }
     }
 
@@ -577,6 +586,7 @@ fn receiver(&self) -> Option<&EntityId> {
             PduBody::InformationOperationsAction => None,
             PduBody::InformationOperationsReport => None,
             PduBody::Attribute(body) => body.receiver(),
+            PduBody::Custom(body) => body.receiver(),
         }
     }
 }
@@ -670,6 +680,7 @@ fn from(pdu_type: PduType) -> Self {
 /// 6.2.80 Simulation Address record
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SimulationAddress {
     pub site_id: u16,
     pub application_id: u16,
@@ -753,8 +764,9 @@ fn try_from(value: String) -> Result<Self, Self::Error> {
 
 /// 6.2.28 Entity Identifier record
 /// 6.2.81 Simulation Identifier record
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EntityId {
     pub simulation_address: SimulationAddress,
     pub entity_id: u16,
@@ -850,8 +862,9 @@ fn try_from(value: String) -> Result<Self, Self::Error> {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Ord, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EventId {
     pub simulation_address: SimulationAddress,
     pub event_id: u16,
@@ -937,6 +950,7 @@ fn try_from(value: String) -> Result<Self, Self::Error> {
 /// 6.2.7 Angular Velocity Vector record
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VectorF32 {
     pub first_vector_component: f32,
     pub second_vector_component: f32,
@@ -976,6 +990,7 @@ pub fn with_third(mut self, third: f32) -> Self {
 /// 6.2.98 World Coordinates record
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Location {
     pub x_coordinate: f64,
     pub y_coordinate: f64,
@@ -1015,6 +1030,7 @@ pub fn with_z(mut self, z: f64) -> Self {
 /// 6.2.32 Euler Angles record
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Orientation {
     pub psi: f32,
     pub theta: f32,
@@ -1050,6 +1066,7 @@ pub fn with_phi(mut self, phi: f32) -> Self {
 /// 6.2.30 Entity Type record
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EntityType {
     pub kind: EntityKind,
     pub domain: PlatformDomain,
@@ -1200,6 +1217,7 @@ fn try_from(value: String) -> Result<Self, Self::Error> {
 /// 6.2.19.2 Munition Descriptor record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MunitionDescriptor {
     pub entity_type: EntityType,
     pub warhead: MunitionDescriptorWarhead,
@@ -1243,6 +1261,7 @@ pub fn with_rate(mut self, rate: u16) -> Self {
 /// 6.2.19.3 Explosion Descriptor record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ExplosionDescriptor {
     pub entity_type: EntityType,
     pub explosive_material: ExplosiveMaterialCategories,
@@ -1275,6 +1294,7 @@ pub fn with_explosive_force(mut self, explosive_force: f32) -> Self {
 /// 6.2.19.4 Expendable Descriptor record
 #[derive(Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ExpendableDescriptor {
     pub entity_type: EntityType,
 }
@@ -1290,6 +1310,7 @@ pub fn with_entity_type(mut self, entity_type: EntityType) -> Self {
 /// 6.2.14 Clock Time record
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClockTime {
     pub hour: i32,
     pub time_past_hour: u32,
@@ -1331,6 +1352,7 @@ pub fn new(
 /// 6.2.37 Fixed Datum record
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FixedDatum {
     pub datum_id: VariableRecordType,
     pub datum_value: u32,
@@ -1349,6 +1371,7 @@ pub fn new(datum_id: VariableRecordType, datum_value: u32) -> Self {
 /// 6.2.93 Variable Datum record
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VariableDatum {
     pub datum_id: VariableRecordType,
     pub datum_value: Vec<u8>,
@@ -1421,6 +1444,10 @@ pub enum VariableParameter {
     EntityType(EntityTypeParameter),
     EntityAssociation(EntityAssociationParameter),
     Unspecified(u8, [u8; FIFTEEN_OCTETS]),
+    /// A record of a user-defined record type, see [`CustomPduRegistry`](crate::custom::registry::CustomPduRegistry).
+    /// Custom records are not (de)serialized using `serde`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Box<dyn CustomVariableParameter>),
 }
 
 /// 6.2.94.2 Articulated Part VP record
@@ -1474,6 +1501,7 @@ pub fn to_variable_parameter(self) -> VariableParameter {
 /// 6.2.94.3 Attached Part VP record
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AttachedPart {
     pub detached_indicator: AttachedPartDetachedIndicator,
     pub attachment_id: u16,
@@ -1518,6 +1546,7 @@ pub fn to_variable_parameter(self) -> VariableParameter {
 /// 6.2.94.6 Separation VP record
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SeparationParameter {
     pub reason: SeparationReasonForSeparation,
     pub pre_entity_indicator: SeparationPreEntityIndicator,
@@ -1569,6 +1598,7 @@ pub fn to_variable_parameter(self) -> VariableParameter {
 /// 6.2.94.5 Entity Type VP record
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EntityTypeParameter {
     pub change_indicator: ChangeIndicator,
     pub entity_type: EntityType,
@@ -1596,6 +1626,7 @@ pub fn to_variable_parameter(self) -> VariableParameter {
 /// 6.2.94.4 Entity Association VP Record
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct EntityAssociationParameter {
     pub change_indicator: ChangeIndicator,
     pub association_status: EntityAssociationAssociationStatus,
@@ -1677,6 +1708,7 @@ pub fn to_variable_parameter(self) -> VariableParameter {
 /// 6.2.11 Beam Data record
 #[derive(Copy, Clone, Default, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BeamData {
     pub azimuth_center: f32,
     pub azimuth_sweep: f32,
@@ -1727,6 +1759,7 @@ pub fn with_sweep_sync(mut self, sweep_sync: f32) -> Self {
 /// 6.2.86 Supply Quantity record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SupplyQuantity {
     pub supply_type: EntityType,
     pub quantity: f32,
@@ -1751,6 +1784,7 @@ pub fn with_quantity(mut self, quantity: f32) -> Self {
 /// 6.2.73 Record Specification record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RecordSpecification {
     pub record_sets: Vec<RecordSet>,
 }
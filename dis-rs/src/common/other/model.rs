@@ -16,8 +16,11 @@
 /// This struct is used to provide access to the received data in not (yet) supported PDUs.
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Other {
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub originating_entity_id: Option<EntityId>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub receiving_entity_id: Option<EntityId>,
     pub body: Vec<u8>,
 }
@@ -11,6 +11,7 @@
 use crate::common::collision_elastic::parser::collision_elastic_body;
 use crate::common::comment::parser::comment_body;
 use crate::common::create_entity::parser::create_entity_body;
+use crate::common::custom::registry::CustomPduRegistry;
 use crate::common::data::parser::data_body;
 use crate::common::data_query::parser::data_query_body;
 use crate::common::designator::parser::designator_body;
@@ -76,10 +77,7 @@
 use crate::transfer_ownership::parser::transfer_ownership_body;
 use crate::underwater_acoustic::parser::underwater_acoustic_body;
 use crate::v7::parser::parse_pdu_status;
-use alloc::{
-    string::{String, ToString},
-    vec::Vec,
-};
+use alloc::{string::ToString, vec::Vec};
 use nom::IResult;
 use nom::bytes::complete::take;
 use nom::combinator::peek;
@@ -89,8 +87,22 @@
 use nom::{Err, Parser};
 
 pub(crate) fn parse_multiple_pdu(input: &[u8]) -> Result<Vec<Pdu>, DisError> {
+    parse_multiple_pdu_with_registry(input, None)
+}
+
+pub(crate) fn parse_multiple_pdu_with_registry(
+    input: &[u8],
+    registry: Option<&CustomPduRegistry>,
+) -> Result<Vec<Pdu>, DisError> {
     match many1(pdu).parse(input) {
-        Ok((_, pdus)) => Ok(pdus),
+        Ok((_, mut pdus)) => {
+            if let Some(registry) = registry {
+                for pdu in &mut pdus {
+                    registry.resolve(pdu)?;
+                }
+            }
+            Ok(pdus)
+        }
         Err(err) => Err(DisError::ParseError(err.to_string())), // TODO not very descriptive / error means we can not match any PDUs
     }
 }
@@ -804,21 +816,6 @@ pub(crate) fn record_set(input: &[u8]) -> IResult<&[u8], RecordSet> {
     ))
 }
 
-/// Takes a reference to a buffer and converts the contents into a String,
-/// sanitizing the input on leading/ending whitespace, and
-/// keeping only alphanumeric characters.
-pub(crate) fn sanitize_marking(buf: &[u8]) -> String {
-    let mut marking_string = String::from_utf8_lossy(buf).into_owned();
-    marking_string.truncate(
-        marking_string
-            .trim_end()
-            .trim_end_matches(|c: char| !c.is_alphanumeric())
-            .len(),
-    );
-
-    marking_string
-}
-
 /// Round upward a given number of bits to the next amount of full bytes
 ///
 /// E.g., 7 bits become 1 byte (8 bits), 12 bits become 2 bytes (16 bits)
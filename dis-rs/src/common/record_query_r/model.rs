@@ -17,6 +17,7 @@
 /// 7.11.14 Record Query-R PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RecordQueryR {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -67,6 +68,7 @@ fn receiver(&self) -> Option<&EntityId> {
 /// 6.2.72 Record Query Specification record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RecordQuerySpecification {
     pub record_ids: Vec<VariableRecordType>,
 }
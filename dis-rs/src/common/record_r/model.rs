@@ -15,6 +15,7 @@
 /// 7.11.16 Record-R PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RecordR {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -0,0 +1,786 @@
+//! Visiting and remapping of the identifiers that are embedded in PDUs.
+//!
+//! [`PduBody::visit_ids_mut`] calls an [`IdVisitor`] for every Entity Identifier, Event Identifier and Simulation Address
+//! record in a PDU body, including those nested in records such as Variable Parameters, Track/Jam data and Attribute record sets.
+//! [`PduBody::map_entity_ids`] and [`PduBody::map_simulation_addresses`] are shorthands to apply a function to these identifiers.
+//!
+//! [`IdRemapTable`] builds on this to translate identifiers between two exercises, for instance when bridging two networks,
+//! in both directions.
+use crate::common::iff::model::Mode5BasicData;
+use crate::common::is_group_of::model::GroupEntityDescription;
+use crate::common::model::{EntityId, EventId, Pdu, PduBody, SimulationAddress, VariableParameter};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use thiserror::Error;
+
+/// Visitor for the identifiers embedded in a PDU body, see [`PduBody::visit_ids_mut`].
+pub trait IdVisitor {
+    /// Visits an Entity Identifier (or Simulation Identifier) record.
+    fn visit_entity_id(&mut self, entity_id: &mut EntityId);
+
+    /// Visits an Event Identifier record.
+    /// The default implementation visits the Simulation Address of the event.
+    fn visit_event_id(&mut self, event_id: &mut EventId) {
+        self.visit_simulation_address(&mut event_id.simulation_address);
+    }
+
+    /// Visits a Simulation Address record that is not part of an Entity or Event Identifier record.
+    fn visit_simulation_address(&mut self, simulation_address: &mut SimulationAddress);
+}
+
+/// Applies a function to each Entity Identifier, leaving other identifiers untouched.
+struct EntityIdMapper<F>(F);
+
+impl<F: FnMut(EntityId) -> EntityId> IdVisitor for EntityIdMapper<F> {
+    fn visit_entity_id(&mut self, entity_id: &mut EntityId) {
+        *entity_id = (self.0)(*entity_id);
+    }
+
+    fn visit_event_id(&mut self, _event_id: &mut EventId) {}
+
+    fn visit_simulation_address(&mut self, _simulation_address: &mut SimulationAddress) {}
+}
+
+/// Applies a function to each Simulation Address, including those of Entity and Event Identifiers.
+struct SimulationAddressMapper<F>(F);
+
+impl<F: FnMut(SimulationAddress) -> SimulationAddress> IdVisitor for SimulationAddressMapper<F> {
+    fn visit_entity_id(&mut self, entity_id: &mut EntityId) {
+        self.visit_simulation_address(&mut entity_id.simulation_address);
+    }
+
+    fn visit_simulation_address(&mut self, simulation_address: &mut SimulationAddress) {
+        *simulation_address = (self.0)(*simulation_address);
+    }
+}
+
+impl PduBody {
+    /// Calls `visitor` for each identifier embedded in the body, in the order in which they appear in the PDU.
+    ///
+    /// The members of an `IsGroupOf` PDU only carry the entity number of their Entity Identifier.
+    /// They are visited as an `EntityId` with the Simulation Address of the (original) group,
+    /// and only the entity number of the visited identifier is stored.
+    #[allow(clippy::too_many_lines)]
+    pub fn visit_ids_mut<V: IdVisitor>(&mut self, visitor: &mut V) {
+        match self {
+            PduBody::Other(body) => {
+                if let Some(id) = body.originating_entity_id.as_mut() {
+                    visitor.visit_entity_id(id);
+                }
+                if let Some(id) = body.receiving_entity_id.as_mut() {
+                    visitor.visit_entity_id(id);
+                }
+            }
+            PduBody::EntityState(body) => {
+                visitor.visit_entity_id(&mut body.entity_id);
+                visit_variable_parameters(&mut body.variable_parameters, visitor);
+            }
+            PduBody::Fire(body) => {
+                visitor.visit_entity_id(&mut body.firing_entity_id);
+                visitor.visit_entity_id(&mut body.target_entity_id);
+                visitor.visit_entity_id(&mut body.entity_id);
+                visitor.visit_event_id(&mut body.event_id);
+            }
+            PduBody::Detonation(body) => {
+                visitor.visit_entity_id(&mut body.source_entity_id);
+                visitor.visit_entity_id(&mut body.target_entity_id);
+                visitor.visit_entity_id(&mut body.exploding_entity_id);
+                visitor.visit_event_id(&mut body.event_id);
+                visit_variable_parameters(&mut body.variable_parameters, visitor);
+            }
+            PduBody::Collision(body) => {
+                visitor.visit_entity_id(&mut body.issuing_entity_id);
+                visitor.visit_entity_id(&mut body.colliding_entity_id);
+                visitor.visit_event_id(&mut body.event_id);
+            }
+            PduBody::CollisionElastic(body) => {
+                visitor.visit_entity_id(&mut body.issuing_entity_id);
+                visitor.visit_entity_id(&mut body.colliding_entity_id);
+                visitor.visit_event_id(&mut body.event_id);
+            }
+            PduBody::ServiceRequest(body) => {
+                visitor.visit_entity_id(&mut body.requesting_id);
+                visitor.visit_entity_id(&mut body.servicing_id);
+            }
+            PduBody::ResupplyOffer(body) => {
+                visitor.visit_entity_id(&mut body.requesting_id);
+                visitor.visit_entity_id(&mut body.servicing_id);
+            }
+            PduBody::ResupplyReceived(body) => {
+                visitor.visit_entity_id(&mut body.requesting_id);
+                visitor.visit_entity_id(&mut body.servicing_id);
+            }
+            PduBody::ResupplyCancel(body) => {
+                visitor.visit_entity_id(&mut body.requesting_id);
+                visitor.visit_entity_id(&mut body.servicing_id);
+            }
+            PduBody::RepairComplete(body) => {
+                visitor.visit_entity_id(&mut body.receiving_id);
+                visitor.visit_entity_id(&mut body.repairing_id);
+            }
+            PduBody::RepairResponse(body) => {
+                visitor.visit_entity_id(&mut body.receiving_id);
+                visitor.visit_entity_id(&mut body.repairing_id);
+            }
+            PduBody::CreateEntity(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::RemoveEntity(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::StartResume(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::StopFreeze(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::Acknowledge(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::ActionRequest(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::ActionResponse(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::DataQuery(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::SetData(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::Data(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::EventReport(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::Comment(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::ElectromagneticEmission(body) => {
+                visitor.visit_entity_id(&mut body.emitting_entity_id);
+                visitor.visit_event_id(&mut body.event_id);
+                body.emitter_systems
+                    .iter_mut()
+                    .flat_map(|system| system.beams.iter_mut())
+                    .flat_map(|beam| beam.track_jam_data.iter_mut())
+                    .for_each(|track_jam| visitor.visit_entity_id(&mut track_jam.entity_id));
+            }
+            PduBody::Designator(body) => {
+                visitor.visit_entity_id(&mut body.designating_entity_id);
+                visitor.visit_entity_id(&mut body.designated_entity_id);
+            }
+            PduBody::Transmitter(body) => {
+                visitor.visit_entity_id(&mut body.radio_reference_id);
+            }
+            PduBody::Signal(body) => {
+                visitor.visit_entity_id(&mut body.radio_reference_id);
+            }
+            PduBody::Receiver(body) => {
+                visitor.visit_entity_id(&mut body.radio_reference_id);
+                visitor.visit_entity_id(&mut body.transmitter_radio_reference_id);
+            }
+            PduBody::IFF(body) => {
+                visitor.visit_entity_id(&mut body.emitting_entity_id);
+                visitor.visit_event_id(&mut body.event_id);
+                if let Some(layer_3) = body.layer_3.as_mut() {
+                    visitor.visit_simulation_address(&mut layer_3.reporting_simulation);
+                    if let Mode5BasicData::Interrogator(basic_data) = &mut layer_3.mode_5_basic_data
+                    {
+                        visitor.visit_entity_id(&mut basic_data.interrogated_entity_id);
+                    }
+                }
+                if let Some(layer_4) = body.layer_4.as_mut() {
+                    visitor.visit_simulation_address(&mut layer_4.reporting_simulation);
+                }
+                if let Some(layer_5) = body.layer_5.as_mut() {
+                    visitor.visit_simulation_address(&mut layer_5.reporting_simulation);
+                }
+            }
+            PduBody::UnderwaterAcoustic(body) => {
+                visitor.visit_entity_id(&mut body.emitting_entity_id);
+                visitor.visit_event_id(&mut body.event_id);
+            }
+            PduBody::SupplementalEmissionEntityState(body) => {
+                visitor.visit_entity_id(&mut body.originating_entity_id);
+            }
+            PduBody::AggregateState(body) => {
+                visitor.visit_entity_id(&mut body.aggregate_id);
+                body.aggregates
+                    .iter_mut()
+                    .chain(body.entities.iter_mut())
+                    .for_each(|id| visitor.visit_entity_id(id));
+            }
+            PduBody::IsGroupOf(body) => {
+                let group_address = body.group_id.simulation_address;
+                visitor.visit_entity_id(&mut body.group_id);
+                body.descriptions
+                    .iter_mut()
+                    .filter_map(group_entity_number_mut)
+                    .for_each(|entity_number| {
+                        let mut id = EntityId::new_sim_address(group_address, *entity_number);
+                        visitor.visit_entity_id(&mut id);
+                        *entity_number = id.entity_id;
+                    });
+            }
+            PduBody::TransferOwnership(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+                visitor.visit_entity_id(&mut body.transfer_entity_id);
+            }
+            PduBody::IsPartOf(body) => {
+                visitor.visit_entity_id(&mut body.originating_simulation_id);
+                visitor.visit_entity_id(&mut body.receiving_entity_id);
+            }
+            PduBody::CreateEntityR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::RemoveEntityR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::StartResumeR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::StopFreezeR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::AcknowledgeR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::ActionRequestR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::ActionResponseR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::DataQueryR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::SetDataR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::DataR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::EventReportR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::CommentR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::RecordR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::SetRecordR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::RecordQueryR(body) => {
+                visitor.visit_entity_id(&mut body.originating_id);
+                visitor.visit_entity_id(&mut body.receiving_id);
+            }
+            PduBody::EntityStateUpdate(body) => {
+                visitor.visit_entity_id(&mut body.entity_id);
+                visit_variable_parameters(&mut body.variable_parameters, visitor);
+            }
+            PduBody::Attribute(body) => {
+                visitor.visit_simulation_address(&mut body.originating_simulation_address);
+                body.attribute_record_sets
+                    .iter_mut()
+                    .for_each(|record_set| visitor.visit_entity_id(&mut record_set.entity_id));
+            }
+            PduBody::Custom(body) => body.visit_ids_mut(visitor),
+            PduBody::IntercomSignal
+            | PduBody::IntercomControl
+            | PduBody::MinefieldState
+            | PduBody::MinefieldQuery
+            | PduBody::MinefieldData
+            | PduBody::MinefieldResponseNACK
+            | PduBody::EnvironmentalProcess
+            | PduBody::GriddedData
+            | PduBody::PointObjectState
+            | PduBody::LinearObjectState
+            | PduBody::ArealObjectState
+            | PduBody::TSPI
+            | PduBody::Appearance
+            | PduBody::ArticulatedParts
+            | PduBody::LEFire
+            | PduBody::LEDetonation
+            | PduBody::DirectedEnergyFire
+            | PduBody::EntityDamageStatus
+            | PduBody::InformationOperationsAction
+            | PduBody::InformationOperationsReport => {}
+        }
+    }
+
+    /// Replaces each Entity Identifier embedded in the body with the result of `f`.
+    ///
+    /// Event Identifiers and stand-alone Simulation Addresses are left untouched,
+    /// see [`PduBody::map_simulation_addresses`] or [`PduBody::visit_ids_mut`] to map these as well.
+    pub fn map_entity_ids<F: FnMut(EntityId) -> EntityId>(&mut self, f: F) {
+        self.visit_ids_mut(&mut EntityIdMapper(f));
+    }
+
+    /// Replaces each Simulation Address embedded in the body with the result of `f`,
+    /// including the Simulation Addresses of Entity and Event Identifiers.
+    pub fn map_simulation_addresses<F: FnMut(SimulationAddress) -> SimulationAddress>(
+        &mut self,
+        f: F,
+    ) {
+        self.visit_ids_mut(&mut SimulationAddressMapper(f));
+    }
+}
+
+fn visit_variable_parameters<V: IdVisitor>(parameters: &mut [VariableParameter], visitor: &mut V) {
+    for parameter in parameters {
+        match parameter {
+            VariableParameter::Separation(separation) => {
+                visitor.visit_entity_id(&mut separation.parent_entity_id);
+            }
+            VariableParameter::EntityAssociation(association) => {
+                visitor.visit_entity_id(&mut association.entity_id);
+            }
+            VariableParameter::Custom(record) => record.visit_ids_mut(visitor),
+            VariableParameter::Articulated(_)
+            | VariableParameter::Attached(_)
+            | VariableParameter::EntityType(_)
+            | VariableParameter::Unspecified(_, _) => {}
+        }
+    }
+}
+
+/// The entity number of the member described by a Group Entity Description record, if any.
+fn group_entity_number_mut(description: &mut GroupEntityDescription) -> Option<&mut u16> {
+    match description {
+        GroupEntityDescription::Undefined => None,
+        GroupEntityDescription::BasicGroundCombatVehicle(record) => Some(&mut record.entity_id),
+        GroupEntityDescription::EnhancedGroundCombatVehicle(record) => {
+            Some(&mut record.basic_ground_combat_vehicle.entity_id)
+        }
+        GroupEntityDescription::BasicGroundCombatSoldier(record) => Some(&mut record.entity_id),
+        GroupEntityDescription::EnhancedGroundCombatSoldier(record) => {
+            Some(&mut record.basic_ground_combat_soldier.entity_id)
+        }
+        GroupEntityDescription::BasicRotorWingAircraft(record) => Some(&mut record.entity_id),
+        GroupEntityDescription::EnhancedRotorWingAircraft(record) => {
+            Some(&mut record.basic_rotor_wing_aircraft.entity_id)
+        }
+        GroupEntityDescription::BasicFixedWingAircraft(record) => Some(&mut record.entity_id),
+        GroupEntityDescription::EnhancedFixedWingAircraft(record) => {
+            Some(&mut record.basic_fixed_wing_aircraft.entity_id)
+        }
+        GroupEntityDescription::GroundLogisticsVehicle(record) => Some(&mut record.entity_id),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum RemapError {
+    #[error("The source identifier {0} is already mapped to another identifier.")]
+    SourceAlreadyMapped(String),
+    #[error("The target identifier {0} is already mapped from another identifier.")]
+    TargetAlreadyMapped(String),
+}
+
+/// The direction in which an [`IdRemapTable`] translates identifiers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RemapDirection {
+    /// From the source identifiers to the target identifiers.
+    #[default]
+    Forward,
+    /// From the target identifiers back to the source identifiers.
+    Reverse,
+}
+
+/// A one-to-one mapping, which can be looked up in both directions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct BiMap<T: Ord> {
+    forward: BTreeMap<T, T>,
+    reverse: BTreeMap<T, T>,
+}
+
+impl<T: Ord> Default for BiMap<T> {
+    fn default() -> Self {
+        Self {
+            forward: BTreeMap::new(),
+            reverse: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Ord + Copy + core::fmt::Display> BiMap<T> {
+    fn insert(&mut self, source: T, target: T) -> Result<(), RemapError> {
+        match (self.forward.get(&source), self.reverse.get(&target)) {
+            (Some(existing), _) if *existing != target => {
+                Err(RemapError::SourceAlreadyMapped(format!("{source}")))
+            }
+            (_, Some(existing)) if *existing != source => {
+                Err(RemapError::TargetAlreadyMapped(format!("{target}")))
+            }
+            _ => {
+                self.forward.insert(source, target);
+                self.reverse.insert(target, source);
+                Ok(())
+            }
+        }
+    }
+
+    fn remove(&mut self, source: &T) -> Option<T> {
+        let target = self.forward.remove(source)?;
+        self.reverse.remove(&target);
+        Some(target)
+    }
+
+    fn get(&self, value: &T, direction: RemapDirection) -> Option<T> {
+        let map = match direction {
+            RemapDirection::Forward => &self.forward,
+            RemapDirection::Reverse => &self.reverse,
+        };
+        map.get(value).copied()
+    }
+}
+
+/// A table to translate the identifiers in PDUs from one exercise to another, and back.
+///
+/// The table holds one-to-one mappings of Exercise IDs, Simulation Addresses and individual Entity IDs.
+/// An Entity ID is translated using its exact mapping when present, and otherwise by translating its Simulation Address.
+/// Identifiers without a mapping are left unchanged.
+///
+/// ```
+/// # use dis_rs::model::{EntityId, SimulationAddress};
+/// # use dis_rs::remapping::{IdRemapTable, RemapDirection};
+/// let mut table = IdRemapTable::new();
+/// table.insert_simulation_address(SimulationAddress::new(1, 1), SimulationAddress::new(10, 1)).unwrap();
+///
+/// let remapped = table.remap_entity_id(EntityId::new(1, 1, 5), RemapDirection::Forward);
+/// assert_eq!(remapped, EntityId::new(10, 1, 5));
+/// assert_eq!(table.remap_entity_id(remapped, RemapDirection::Reverse), EntityId::new(1, 1, 5));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IdRemapTable {
+    exercise_ids: BiMap<u8>,
+    simulation_addresses: BiMap<SimulationAddress>,
+    entity_ids: BiMap<EntityId>,
+}
+
+impl IdRemapTable {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps Exercise ID `source` to `target`.
+    ///
+    /// # Errors
+    /// Returns a `RemapError` when either `source` or `target` is already part of another mapping.
+    pub fn insert_exercise_id(&mut self, source: u8, target: u8) -> Result<(), RemapError> {
+        self.exercise_ids.insert(source, target)
+    }
+
+    /// Maps Simulation Address `source` to `target`, which also applies to the Entity and Event IDs of the simulation.
+    ///
+    /// # Errors
+    /// Returns a `RemapError` when either `source` or `target` is already part of another mapping.
+    pub fn insert_simulation_address(
+        &mut self,
+        source: SimulationAddress,
+        target: SimulationAddress,
+    ) -> Result<(), RemapError> {
+        self.simulation_addresses.insert(source, target)
+    }
+
+    /// Maps Entity ID `source` to `target`, taking precedence over the mapping of its Simulation Address.
+    ///
+    /// # Errors
+    /// Returns a `RemapError` when either `source` or `target` is already part of another mapping.
+    pub fn insert_entity_id(
+        &mut self,
+        source: EntityId,
+        target: EntityId,
+    ) -> Result<(), RemapError> {
+        self.entity_ids.insert(source, target)
+    }
+
+    /// Removes the mapping of Exercise ID `source`, returning its target when it was mapped.
+    pub fn remove_exercise_id(&mut self, source: u8) -> Option<u8> {
+        self.exercise_ids.remove(&source)
+    }
+
+    /// Removes the mapping of Simulation Address `source`, returning its target when it was mapped.
+    pub fn remove_simulation_address(
+        &mut self,
+        source: SimulationAddress,
+    ) -> Option<SimulationAddress> {
+        self.simulation_addresses.remove(&source)
+    }
+
+    /// Removes the mapping of Entity ID `source`, returning its target when it was mapped.
+    pub fn remove_entity_id(&mut self, source: EntityId) -> Option<EntityId> {
+        self.entity_ids.remove(&source)
+    }
+
+    /// Translates `exercise_id` in `direction`.
+    #[must_use]
+    pub fn remap_exercise_id(&self, exercise_id: u8, direction: RemapDirection) -> u8 {
+        self.exercise_ids
+            .get(&exercise_id, direction)
+            .unwrap_or(exercise_id)
+    }
+
+    /// Translates `simulation_address` in `direction`.
+    #[must_use]
+    pub fn remap_simulation_address(
+        &self,
+        simulation_address: SimulationAddress,
+        direction: RemapDirection,
+    ) -> SimulationAddress {
+        self.simulation_addresses
+            .get(&simulation_address, direction)
+            .unwrap_or(simulation_address)
+    }
+
+    /// Translates `entity_id` in `direction`.
+    #[must_use]
+    pub fn remap_entity_id(&self, entity_id: EntityId, direction: RemapDirection) -> EntityId {
+        self.entity_ids
+            .get(&entity_id, direction)
+            .unwrap_or_else(|| {
+                EntityId::new_sim_address(
+                    self.remap_simulation_address(entity_id.simulation_address, direction),
+                    entity_id.entity_id,
+                )
+            })
+    }
+
+    /// Translates the Exercise ID and all identifiers embedded in `pdu` in `direction`.
+    pub fn remap_pdu(&self, pdu: &mut Pdu, direction: RemapDirection) {
+        pdu.header.exercise_id = self.remap_exercise_id(pdu.header.exercise_id, direction);
+        self.remap_body(&mut pdu.body, direction);
+    }
+
+    /// Translates all identifiers embedded in `body` in `direction`.
+    pub fn remap_body(&self, body: &mut PduBody, direction: RemapDirection) {
+        body.visit_ids_mut(&mut TableVisitor {
+            table: self,
+            direction,
+        });
+    }
+}
+
+struct TableVisitor<'a> {
+    table: &'a IdRemapTable,
+    direction: RemapDirection,
+}
+
+impl IdVisitor for TableVisitor<'_> {
+    fn visit_entity_id(&mut self, entity_id: &mut EntityId) {
+        *entity_id = self.table.remap_entity_id(*entity_id, self.direction);
+    }
+
+    fn visit_simulation_address(&mut self, simulation_address: &mut SimulationAddress) {
+        *simulation_address = self
+            .table
+            .remap_simulation_address(*simulation_address, self.direction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyRaw;
+    use crate::common::attribute::model::{Attribute, AttributeRecordSet};
+    use crate::common::entity_state::model::EntityState;
+    use crate::common::fire::model::Fire;
+    use crate::common::is_group_of::model::{GEDRecord1, GEDRecord2, IsGroupOf};
+    use crate::common::model::{PduHeader, SeparationParameter, TimeUnits, Timestamp};
+    use crate::enumerations::PduType;
+
+    fn timestamp() -> Timestamp {
+        Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap())
+    }
+
+    fn collect_entity_ids(body: &mut PduBody) -> alloc::vec::Vec<EntityId> {
+        let mut ids = alloc::vec::Vec::new();
+        body.map_entity_ids(|id| {
+            ids.push(id);
+            id
+        });
+        ids
+    }
+
+    #[test]
+    fn map_entity_ids_reaches_nested_ids() {
+        let mut body = EntityState::builder()
+            .with_entity_id(EntityId::new(1, 1, 1))
+            .with_variable_parameter(VariableParameter::Separation(
+                SeparationParameter::default().with_parent_entity_id(EntityId::new(1, 1, 2)),
+            ))
+            .build()
+            .into_pdu_body();
+
+        assert_eq!(
+            collect_entity_ids(&mut body),
+            vec![EntityId::new(1, 1, 1), EntityId::new(1, 1, 2)]
+        );
+
+        body.map_entity_ids(|id| EntityId::new(2, 2, id.entity_id + 10));
+        assert_eq!(
+            collect_entity_ids(&mut body),
+            vec![EntityId::new(2, 2, 11), EntityId::new(2, 2, 12)]
+        );
+    }
+
+    #[test]
+    fn map_entity_ids_leaves_event_ids() {
+        let mut body = Fire::builder()
+            .with_firing_entity_id(EntityId::new(1, 1, 1))
+            .with_event_id(EventId::new(1, 1, 7))
+            .build()
+            .into_pdu_body();
+
+        body.map_entity_ids(|id| EntityId::new(2, 2, id.entity_id));
+        let PduBody::Fire(fire) = &body else {
+            panic!("Expected a Fire PDU body");
+        };
+        assert_eq!(fire.firing_entity_id, EntityId::new(2, 2, 1));
+        assert_eq!(fire.event_id, EventId::new(1, 1, 7));
+
+        body.map_simulation_addresses(|_| SimulationAddress::new(3, 3));
+        let PduBody::Fire(fire) = &body else {
+            panic!("Expected a Fire PDU body");
+        };
+        assert_eq!(fire.firing_entity_id, EntityId::new(3, 3, 1));
+        assert_eq!(fire.target_entity_id, EntityId::new(3, 3, 0));
+        assert_eq!(fire.event_id, EventId::new(3, 3, 7));
+    }
+
+    #[test]
+    fn map_group_members() {
+        let mut body = IsGroupOf::builder()
+            .with_group_id(EntityId::new(1, 1, 100))
+            .with_description(GroupEntityDescription::BasicGroundCombatVehicle(
+                GEDRecord1 {
+                    entity_id: 1,
+                    ..Default::default()
+                },
+            ))
+            .with_description(GroupEntityDescription::EnhancedGroundCombatVehicle(
+                GEDRecord2 {
+                    basic_ground_combat_vehicle: GEDRecord1 {
+                        entity_id: 2,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ))
+            .build()
+            .into_pdu_body();
+
+        assert_eq!(
+            collect_entity_ids(&mut body),
+            vec![
+                EntityId::new(1, 1, 100),
+                EntityId::new(1, 1, 1),
+                EntityId::new(1, 1, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn remap_table_rejects_conflicts() {
+        let mut table = IdRemapTable::new();
+        assert!(table.insert_exercise_id(1, 2).is_ok());
+        assert!(table.insert_exercise_id(1, 2).is_ok());
+        assert!(matches!(
+            table.insert_exercise_id(1, 3),
+            Err(RemapError::SourceAlreadyMapped(_))
+        ));
+        assert!(matches!(
+            table.insert_exercise_id(4, 2),
+            Err(RemapError::TargetAlreadyMapped(_))
+        ));
+        assert_eq!(table.remove_exercise_id(1), Some(2));
+        assert!(table.insert_exercise_id(4, 2).is_ok());
+    }
+
+    #[test]
+    fn remap_table_round_trip() {
+        let mut table = IdRemapTable::new();
+        table.insert_exercise_id(1, 2).unwrap();
+        table
+            .insert_simulation_address(SimulationAddress::new(1, 1), SimulationAddress::new(10, 1))
+            .unwrap();
+        table
+            .insert_entity_id(EntityId::new(1, 1, 5), EntityId::new(20, 2, 50))
+            .unwrap();
+
+        let body = Attribute::builder()
+            .with_originating_simulation_address(SimulationAddress::new(1, 1))
+            .with_attribute_record_set(
+                AttributeRecordSet::new().with_entity_id(EntityId::new(1, 1, 5)),
+            )
+            .with_attribute_record_set(
+                AttributeRecordSet::new().with_entity_id(EntityId::new(1, 1, 6)),
+            )
+            .with_attribute_record_set(
+                AttributeRecordSet::new().with_entity_id(EntityId::new(3, 3, 7)),
+            )
+            .build()
+            .into_pdu_body();
+        let original =
+            Pdu::finalize_from_parts(PduHeader::new_v7(1, PduType::Attribute), body, timestamp());
+
+        let mut pdu = original.clone();
+        table.remap_pdu(&mut pdu, RemapDirection::Forward);
+        assert_eq!(pdu.header.exercise_id, 2);
+        let PduBody::Attribute(attribute) = &pdu.body else {
+            panic!("Expected an Attribute PDU body");
+        };
+        assert_eq!(
+            attribute.originating_simulation_address,
+            SimulationAddress::new(10, 1)
+        );
+        let ids: alloc::vec::Vec<EntityId> = attribute
+            .attribute_record_sets
+            .iter()
+            .map(|record_set| record_set.entity_id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                EntityId::new(20, 2, 50),
+                EntityId::new(10, 1, 6),
+                EntityId::new(3, 3, 7)
+            ]
+        );
+
+        table.remap_pdu(&mut pdu, RemapDirection::Reverse);
+        assert_eq!(pdu, original);
+    }
+}
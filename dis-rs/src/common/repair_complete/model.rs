@@ -13,6 +13,7 @@
 /// 7.4.6 Repair Complete PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RepairComplete {
     pub receiving_id: EntityId,
     pub repairing_id: EntityId,
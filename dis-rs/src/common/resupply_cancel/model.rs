@@ -13,6 +13,7 @@
 /// 7.4.5 Resupply Cancel PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ResupplyCancel {
     pub requesting_id: EntityId,
     pub servicing_id: EntityId,
@@ -15,9 +15,11 @@
 /// 7.4.3 Resupply Offer PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ResupplyOffer {
     pub requesting_id: EntityId,
     pub servicing_id: EntityId,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub supplies: Vec<SupplyQuantity>,
 }
 
@@ -15,9 +15,11 @@
 /// 7.4.4 Resupply Received PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ResupplyReceived {
     pub requesting_id: EntityId,
     pub servicing_id: EntityId,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub supplies: Vec<SupplyQuantity>,
 }
 
@@ -15,6 +15,7 @@
 /// 7.6.6 Supplemental Emission/Entity State (SEES) PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SEES {
     pub originating_entity_id: EntityId,
     pub infrared_signature_representation_index: u16,
@@ -65,6 +66,7 @@ fn receiver(&self) -> Option<&EntityId> {
 /// 6.2.68 Propulsion System Data record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PropulsionSystemData {
     pub power_setting: f32,
     pub engine_rpm: f32,
@@ -87,6 +89,7 @@ pub fn with_engine_rpm(mut self, engine_rpm: f32) -> Self {
 /// 6.2.97 Vectoring Nozzle System Data record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VectoringNozzleSystemData {
     pub horizontal_deflection_angle: f32,
     pub vertical_deflection_angle: f32,
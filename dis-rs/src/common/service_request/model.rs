@@ -14,10 +14,12 @@
 /// 7.4.2 Service Request PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ServiceRequest {
     pub requesting_id: EntityId,
     pub servicing_id: EntityId,
     pub service_type_requested: ServiceRequestServiceTypeRequested,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub supplies: Vec<SupplyQuantity>,
 }
 
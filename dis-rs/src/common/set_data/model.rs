@@ -18,6 +18,7 @@
 /// 7.5.10 Set Data PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SetData {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
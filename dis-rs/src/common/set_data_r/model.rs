@@ -18,6 +18,7 @@
 /// 7.11.10 Set Data-R PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SetDataR {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -13,6 +13,7 @@
 /// 7.5.4 Start/Resume PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct StartResume {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -1,6 +1,6 @@
 use crate::Serialize;
 use bytes::BufMut;
-use core::{fmt::Display, time::Duration};
+use core::{cell::Cell, fmt::Display, time::Duration};
 
 /// Number of [`TimeUnits`] in one hour.
 pub const TIME_UNITS_PER_HOUR: u32 = 1 << 31;
@@ -158,6 +158,113 @@ pub fn to_duration(self) -> Duration {
 
         self.time_units().to_duration()
     }
+
+    /// Constructs a *relative* `Timestamp` for the current time of `clock`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::time::Duration;
+    /// # use dis_rs::model::{Timestamp, TimeUnits};
+    /// # use dis_rs::timestamp::ManualClock;
+    /// let clock = ManualClock::new(Duration::from_secs(7_200 + 1_800));
+    /// let timestamp = Timestamp::relative_from(&clock);
+    /// assert_eq!(timestamp, Timestamp::Relative(TimeUnits::new(1_073_741_824).unwrap()));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn relative_from<C: Clock + ?Sized>(clock: &C) -> Self {
+        Self::Relative(TimeUnits::from_duration_past_hour(clock.now()))
+    }
+
+    /// Constructs an *absolute* `Timestamp` for the current time of `clock`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::time::Duration;
+    /// # use dis_rs::model::{Timestamp, TimeUnits};
+    /// # use dis_rs::timestamp::ManualClock;
+    /// let clock = ManualClock::new(Duration::from_secs(7_200 + 1_800));
+    /// let timestamp = Timestamp::absolute_from(&clock);
+    /// assert_eq!(timestamp, Timestamp::Absolute(TimeUnits::new(1_073_741_824).unwrap()));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn absolute_from<C: Clock + ?Sized>(clock: &C) -> Self {
+        Self::Absolute(TimeUnits::from_duration_past_hour(clock.now()))
+    }
+
+    /// Constructs a *relative* `Timestamp` for the current time of the host clock ([`SystemClock`]).
+    #[cfg(feature = "std")]
+    #[inline]
+    #[must_use]
+    pub fn now_relative() -> Self {
+        Self::relative_from(&SystemClock)
+    }
+
+    /// Constructs an *absolute* `Timestamp` for the current time of the host clock ([`SystemClock`]),
+    /// which should be synchronized to UTC.
+    #[cfg(feature = "std")]
+    #[inline]
+    #[must_use]
+    pub fn now_absolute() -> Self {
+        Self::absolute_from(&SystemClock)
+    }
+}
+
+/// Source of the current time, used to generate [`Timestamp`]s.
+///
+/// The time is the [`Duration`] elapsed since an epoch that starts at the top of an hour, such as the Unix epoch.
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+/// The system clock of the host, as the time elapsed since the Unix epoch.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Duration {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+}
+
+/// A clock that is set and advanced manually, for instance in tests.
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock {
+    now: Cell<Duration>,
+}
+
+impl ManualClock {
+    #[must_use]
+    pub fn new(now: Duration) -> Self {
+        Self {
+            now: Cell::new(now),
+        }
+    }
+
+    /// Sets the current time of the clock.
+    pub fn set(&self, now: Duration) {
+        self.now.set(now);
+    }
+
+    /// Advances the current time of the clock by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Clock for ManualClock {
+    #[inline]
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
 }
 
 impl Serialize for Timestamp {
@@ -344,6 +451,27 @@ pub fn from_duration(duration: Duration) -> Option<Self> {
         }
     }
 
+    /// Constructs a new `TimeUnits` from the time past the hour in `duration`.
+    /// Durations that round up to the start of the next hour result in [`Self::ZERO`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::time::Duration;
+    /// # use dis_rs::model::TimeUnits;
+    /// let time_units = TimeUnits::from_duration_past_hour(Duration::from_mins(90));
+    /// assert_eq!(time_units.to_duration(), Duration::from_mins(30));
+    ///
+    /// let time_units = TimeUnits::from_duration_past_hour(Duration::from_nanos(3_599_999_999_999));
+    /// assert!(time_units.is_zero());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn from_duration_past_hour(duration: Duration) -> Self {
+        let nanos = duration.as_nanos() % u128::from(NANOS_PER_HOUR);
+        Self::from_duration(Duration::from_nanos(nanos as u64)).unwrap_or(Self::ZERO)
+    }
+
     /// Returns the inner [`u32`] value.
     ///
     /// # Examples
@@ -16,6 +16,7 @@
 /// 7.8.4 Transfer Ownership (TO) PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TransferOwnership {
     pub originating_id: EntityId,
     pub receiving_id: EntityId,
@@ -0,0 +1,24 @@
+use crate::common::SupportedVersion;
+use crate::common::conversion::ConversionLoss;
+use crate::common::transmitter::model::Transmitter;
+use alloc::vec::Vec;
+
+impl Transmitter {
+    /// Adapts the fields of the body that differ between protocol versions to `version`.
+    ///
+    /// V6 does not define Variable Transmitter Parameters records; the field holding their number is padding.
+    pub(crate) fn convert_to(
+        &mut self,
+        version: &SupportedVersion,
+        losses: &mut Vec<ConversionLoss>,
+    ) {
+        if matches!(version, SupportedVersion::V6)
+            && !self.variable_transmitter_parameters.is_empty()
+        {
+            self.variable_transmitter_parameters.clear();
+            losses.push(ConversionLoss::Dropped(
+                "transmitter.variable_transmitter_parameters",
+            ));
+        }
+    }
+}
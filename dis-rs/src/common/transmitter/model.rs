@@ -147,6 +147,7 @@ pub fn with_radio_system(mut self, radio_system: TransmitterModulationTypeSystem
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SpreadSpectrum {
     pub frequency_hopping: bool,
     pub pseudo_noise: bool,
@@ -279,6 +280,7 @@ fn from(value: CryptoKeyId) -> Self {
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(Default)]
 pub enum CryptoMode {
     #[default]
@@ -298,6 +300,7 @@ fn from(value: bool) -> Self {
 
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BeamAntennaPattern {
     pub beam_direction: Orientation,
     pub azimuth_beamwidth: f32,
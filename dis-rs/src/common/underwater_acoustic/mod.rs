@@ -6,11 +6,12 @@
 #[cfg(test)]
 mod tests {
     use crate::BodyRaw;
+    use crate::Serialize;
     use crate::common::model::{Pdu, PduHeader, TimeUnits, Timestamp};
     use crate::common::parser::parse_pdu;
     use crate::enumerations::{
-        PduType, UAPassiveParameterIndex, UAPropulsionPlantConfiguration,
-        UAStateChangeUpdateIndicator,
+        APAStatus, PduType, UAAdditionalPassiveActivityParameterIndex, UAPassiveParameterIndex,
+        UAPropulsionPlantConfiguration, UAStateChangeUpdateIndicator,
     };
     use crate::model::{EntityId, EventId};
     use crate::underwater_acoustic::model::{
@@ -79,4 +80,18 @@ fn underwater_acoustic_internal_consistency() {
             }
         }
     }
+
+    #[test]
+    fn serialize_apa_status() {
+        let apa = APA::default()
+            .with_parameter(UAAdditionalPassiveActivityParameterIndex::from(5))
+            .with_status(APAStatus::from(2))
+            .with_value(-3);
+
+        let mut buf = BytesMut::with_capacity(4);
+        apa.serialize(&mut buf);
+
+        // parameter index in the upper 14 bits, status in the lower 2 bits
+        assert_eq!(buf.as_ref(), &[0x00, 0x16, 0xFF, 0xFD]);
+    }
 }
@@ -20,14 +20,18 @@
 /// 7.6.4 Underwater Acoustic (UA) PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UnderwaterAcoustic {
     pub emitting_entity_id: EntityId,
     pub event_id: EventId,
     pub state_change_update_indicator: UAStateChangeUpdateIndicator,
     pub passive_parameter_index: UAPassiveParameterIndex,
     pub propulsion_plant_configuration: PropulsionPlantConfiguration,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub shafts: Vec<Shaft>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub apas: Vec<APA>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub emitter_systems: Vec<UAEmitterSystem>,
 }
 
@@ -106,6 +110,7 @@ pub fn record_length(&self) -> u16 {
 /// Table 164—UA PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Shaft {
     pub current_rpm: i16,
     pub ordered_rpm: i16,
@@ -175,9 +180,11 @@ pub fn record_length(&self) -> u16 {
 /// Figure 50 — General form of emitter systems in the UA PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UAEmitterSystem {
     pub acoustic_emitter_system: AcousticEmitterSystem,
     pub location: VectorF32,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::common::arbitrary::u8_counted))]
     pub beams: Vec<UABeam>,
 }
 
@@ -218,6 +225,7 @@ pub fn record_length(&self) -> u16 {
 /// 6.2.2 Acoustic Emitter System record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AcousticEmitterSystem {
     pub acoustic_system_name: UAAcousticSystemName,
     pub function: UAAcousticEmitterSystemFunction,
@@ -254,6 +262,7 @@ pub fn record_length(&self) -> u16 {
 /// 7.6.4 Underwater Acoustic (UA) PDU
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UABeam {
     pub beam_data_length: u8,
     pub beam_id_number: u8,
@@ -291,6 +300,7 @@ pub fn record_length(&self) -> u16 {
 /// 6.2.91 UA Fundamental Parameter Data record
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct UAFundamentalParameterData {
     pub active_emission_parameter_index: UAActiveEmissionParameterIndex,
     pub scan_pattern: UAScanPattern,
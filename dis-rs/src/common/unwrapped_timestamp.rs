@@ -0,0 +1,256 @@
+//! Unwrapping of PDU timestamps across hour rollovers.
+//!
+//! A [`Timestamp`] only expresses the time past the hour, so it wraps back to zero at the top of every hour.
+//! The [`TimestampUnwrapper`] tracks the hour rollovers of each sender,
+//! turning its timestamps into monotonic [`UnwrappedTimestamp`]s that can be ordered and subtracted.
+use crate::common::Interaction;
+use crate::common::model::{Pdu, SimulationAddress};
+use crate::common::timestamp::{TIME_UNITS_PER_HOUR, TimeUnits, Timestamp};
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+
+const UNITS_PER_HOUR: i64 = TIME_UNITS_PER_HOUR as i64;
+const HALF_HOUR: i64 = UNITS_PER_HOUR / 2;
+const SECONDS_PER_HOUR: u64 = 3_600;
+
+/// A timestamp that keeps counting past the hour, expressed in time units since the start of the first hour
+/// observed for a sender.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnwrappedTimestamp(i64);
+
+impl UnwrappedTimestamp {
+    /// Constructs a new `UnwrappedTimestamp` at `time_units` past the start of `hour`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dis_rs::model::TimeUnits;
+    /// # use dis_rs::unwrapped_timestamp::UnwrappedTimestamp;
+    /// let timestamp = UnwrappedTimestamp::new(2, TimeUnits::new(100).unwrap());
+    /// assert_eq!(timestamp.hour(), 2);
+    /// assert_eq!(timestamp.time_units(), TimeUnits::new(100).unwrap());
+    /// ```
+    #[must_use]
+    pub const fn new(hour: i64, time_units: TimeUnits) -> Self {
+        Self(hour * UNITS_PER_HOUR + time_units.inner() as i64)
+    }
+
+    /// Returns the hour of the timestamp, counted from the first hour observed for the sender.
+    /// Late PDUs from before the first observed hour are in hour `-1`.
+    #[must_use]
+    pub const fn hour(self) -> i64 {
+        self.0.div_euclid(UNITS_PER_HOUR)
+    }
+
+    /// Returns the time units past the start of the hour of the timestamp.
+    #[must_use]
+    pub const fn time_units(self) -> TimeUnits {
+        // SAFETY: the euclidean remainder is always in the range 0..TIME_UNITS_PER_HOUR
+        #[allow(unsafe_code)]
+        unsafe {
+            TimeUnits::new_unchecked(self.0.rem_euclid(UNITS_PER_HOUR) as u32)
+        }
+    }
+
+    /// Returns the total number of time units since the start of the first observed hour.
+    #[must_use]
+    pub const fn total_time_units(self) -> i64 {
+        self.0
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or `None` when `earlier` is later than `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::time::Duration;
+    /// # use dis_rs::model::TimeUnits;
+    /// # use dis_rs::unwrapped_timestamp::UnwrappedTimestamp;
+    /// let earlier = UnwrappedTimestamp::new(0, TimeUnits::MAX);
+    /// let later = UnwrappedTimestamp::new(1, TimeUnits::ZERO);
+    /// assert!(later.checked_duration_since(earlier).unwrap() < Duration::from_micros(2));
+    /// assert_eq!(earlier.checked_duration_since(later), None);
+    /// ```
+    #[must_use]
+    pub fn checked_duration_since(self, earlier: Self) -> Option<Duration> {
+        let difference = self.0.checked_sub(earlier.0)?;
+        if difference < 0 {
+            return None;
+        }
+        let hours = (difference / UNITS_PER_HOUR) as u64;
+        let rest = Self(difference % UNITS_PER_HOUR).time_units();
+        Some(Duration::from_secs(hours * SECONDS_PER_HOUR) + rest.to_duration())
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or zero when `earlier` is later than `self`.
+    #[must_use]
+    pub fn duration_since(self, earlier: Self) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct SenderClock {
+    hour: i64,
+    latest: TimeUnits,
+}
+
+/// Tracks the hour rollovers of the timestamps of each sender, identified by a key `K`.
+///
+/// Timestamps are expected to arrive roughly in order. A timestamp more than half an hour before the latest one of a sender
+/// means that the clock of the sender has rolled over into the next hour.
+/// A timestamp more than half an hour after the latest one is a late PDU from the previous hour, and does not
+/// change the state of the sender.
+///
+/// The first timestamp of each sender is placed in hour `0`, so unwrapped timestamps of different senders are not comparable.
+///
+/// # Examples
+///
+/// ```
+/// # use dis_rs::model::{SimulationAddress, TimeUnits, Timestamp};
+/// # use dis_rs::unwrapped_timestamp::TimestampUnwrapper;
+/// let sender = SimulationAddress::new(1, 1);
+/// let mut unwrapper = TimestampUnwrapper::new();
+///
+/// let before = unwrapper.unwrap_timestamp(sender, Timestamp::Absolute(TimeUnits::MAX));
+/// let after = unwrapper.unwrap_timestamp(sender, Timestamp::Absolute(TimeUnits::ZERO));
+/// assert!(after > before);
+/// assert_eq!(after.hour(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimestampUnwrapper<K: Ord = SimulationAddress> {
+    senders: BTreeMap<K, SenderClock>,
+}
+
+impl<K: Ord> Default for TimestampUnwrapper<K> {
+    fn default() -> Self {
+        Self {
+            senders: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord> TimestampUnwrapper<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Unwraps `timestamp` received from `sender`, updating the hour rollovers tracked for the sender.
+    pub fn unwrap_timestamp(&mut self, sender: K, timestamp: Timestamp) -> UnwrappedTimestamp {
+        let time_units = timestamp.time_units();
+        let clock = self.senders.entry(sender).or_insert(SenderClock {
+            hour: 0,
+            latest: time_units,
+        });
+
+        let difference = i64::from(time_units.inner()) - i64::from(clock.latest.inner());
+        if difference < -HALF_HOUR {
+            clock.hour += 1;
+            clock.latest = time_units;
+            UnwrappedTimestamp::new(clock.hour, time_units)
+        } else if difference > HALF_HOUR {
+            UnwrappedTimestamp::new(clock.hour - 1, time_units)
+        } else {
+            if difference > 0 {
+                clock.latest = time_units;
+            }
+            UnwrappedTimestamp::new(clock.hour, time_units)
+        }
+    }
+
+    /// Stops tracking `sender`, for instance when it leaves the exercise.
+    pub fn remove(&mut self, sender: &K) {
+        self.senders.remove(sender);
+    }
+
+    /// Stops tracking all senders.
+    pub fn clear(&mut self) {
+        self.senders.clear();
+    }
+}
+
+impl TimestampUnwrapper<SimulationAddress> {
+    /// Unwraps the timestamp in the header of `pdu`, using the simulation address of the originator as sender.
+    ///
+    /// Returns `None` when the body of the PDU has no originating entity.
+    pub fn unwrap_pdu(&mut self, pdu: &Pdu) -> Option<UnwrappedTimestamp> {
+        let sender = pdu.originator()?.simulation_address;
+        Some(self.unwrap_timestamp(sender, pdu.header.timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: SimulationAddress = SimulationAddress {
+        site_id: 1,
+        application_id: 1,
+    };
+
+    fn at_minute(minute: u64) -> Timestamp {
+        Timestamp::Absolute(TimeUnits::from_duration_past_hour(Duration::from_mins(minute)))
+    }
+
+    fn assert_seconds(duration: Duration, seconds: u64) {
+        assert_eq!(duration.as_secs_f64().round(), seconds as f64);
+    }
+
+    #[test]
+    fn rollover_increments_the_hour() {
+        let mut unwrapper = TimestampUnwrapper::new();
+
+        let first = unwrapper.unwrap_timestamp(SENDER, at_minute(50));
+        let second = unwrapper.unwrap_timestamp(SENDER, at_minute(59));
+        let third = unwrapper.unwrap_timestamp(SENDER, at_minute(61));
+        let fourth = unwrapper.unwrap_timestamp(SENDER, at_minute(80));
+
+        assert_eq!(first.hour(), 0);
+        assert_eq!(second.hour(), 0);
+        assert_eq!(third.hour(), 1);
+        assert_eq!(fourth.hour(), 1);
+        assert!(first < second && second < third && third < fourth);
+        assert_seconds(third.duration_since(second), 120);
+    }
+
+    #[test]
+    fn late_pdus_from_previous_hour() {
+        let mut unwrapper = TimestampUnwrapper::new();
+
+        let before = unwrapper.unwrap_timestamp(SENDER, at_minute(59));
+        let after = unwrapper.unwrap_timestamp(SENDER, at_minute(61));
+        let late = unwrapper.unwrap_timestamp(SENDER, at_minute(58));
+        let next = unwrapper.unwrap_timestamp(SENDER, at_minute(62));
+
+        assert_eq!(late.hour(), 0);
+        assert!(late < before && before < after && after < next);
+        assert_eq!(next.hour(), 1);
+        assert_eq!(late.checked_duration_since(after), None);
+        assert_seconds(after.duration_since(late), 180);
+    }
+
+    #[test]
+    fn late_pdu_before_first_hour() {
+        let mut unwrapper = TimestampUnwrapper::new();
+
+        let first = unwrapper.unwrap_timestamp(SENDER, at_minute(1));
+        let late = unwrapper.unwrap_timestamp(SENDER, at_minute(59));
+
+        assert_eq!(late.hour(), -1);
+        assert_seconds(first.duration_since(late), 120);
+    }
+
+    #[test]
+    fn senders_are_tracked_separately() {
+        let other = SimulationAddress::new(1, 2);
+        let mut unwrapper = TimestampUnwrapper::new();
+
+        unwrapper.unwrap_timestamp(SENDER, at_minute(59));
+        assert_eq!(unwrapper.unwrap_timestamp(other, at_minute(1)).hour(), 0);
+        assert_eq!(unwrapper.unwrap_timestamp(SENDER, at_minute(1)).hour(), 1);
+
+        unwrapper.remove(&SENDER);
+        assert_eq!(unwrapper.unwrap_timestamp(SENDER, at_minute(2)).hour(), 0);
+    }
+}
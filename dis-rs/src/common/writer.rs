@@ -44,6 +44,8 @@ impl Pdu {
     ///
     /// Fails when the capacity of the buffer is smaller than the serialized length of the PDU (header + body).
     ///
+    /// Fails when a marking of the PDU cannot be encoded in its character set.
+    ///
     /// # Errors
     /// Returns a `DisError` when parsing fails
     pub fn serialize(&self, buf: &mut BytesMut) -> Result<u16, DisError> {
@@ -53,6 +55,11 @@ pub fn serialize(&self, buf: &mut BytesMut) -> Result<u16, DisError> {
                 buf.capacity(),
             ));
         }
+        match &self.body {
+            PduBody::EntityState(body) => body.entity_marking.validate()?,
+            PduBody::AggregateState(body) => body.aggregate_marking.validate()?,
+            _ => {}
+        }
         let header_size = self.header.serialize(buf);
         let version: SupportedVersion = self.header.protocol_version.into();
         let body_size = match &self.body {
@@ -129,6 +136,7 @@ pub fn serialize(&self, buf: &mut BytesMut) -> Result<u16, DisError> {
             // PduBody::InformationOperationsAction(body) => { body.serialize_pdu(version, buf) }
             // PduBody::InformationOperationsReport(body) => { body.serialize_pdu(version, buf) }
             PduBody::Attribute(body) => body.serialize_pdu(version, buf),
+            PduBody::Custom(body) => body.serialize_pdu(version, buf),
             _ => 0,
         };
 
@@ -279,6 +287,11 @@ fn serialize(&self, buf: &mut BytesMut) -> u16 {
                 buf.put(&value[..]);
                 16
             }
+            VariableParameter::Custom(record) => {
+                buf.put_u8(record.record_type().into());
+                buf.put(&record.serialize_record()[..]);
+                16
+            }
         }
     }
 }
@@ -0,0 +1,363 @@
+//! Correlation of Fire and Detonation PDUs into engagements, for after action review.
+//!
+//! The [`EngagementTracker`] matches a Fire PDU to its Detonation PDU by the Event ID and the munition entity,
+//! links both to the shooter and target, and computes the miss distance between the location of the detonation
+//! and the dead reckoned location of the target at the time of detonation.
+//! Fire events that are not followed by a detonation within the timeout result in a timed out engagement.
+//!
+//! Time is passed in explicitly by the caller as a `Duration` since an arbitrary (but fixed) epoch,
+//! which allows driving the tracker from any clock, including the replay of a recording.
+use crate::common::Interaction;
+use crate::common::detonation::model::Detonation;
+use crate::common::entity_state::dead_reckoning::location_difference;
+use crate::common::entity_state::model::EntityState;
+use crate::common::fire::model::Fire;
+use crate::common::model::{EntityId, EventId, Location, PduBody};
+use crate::fixed_parameters::NO_ENTITY;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// Timing settings of the tracker.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EngagementSettings {
+    /// Time after a Fire PDU after which the engagement is closed when no Detonation PDU was received.
+    pub detonation_timeout: Duration,
+}
+
+impl EngagementSettings {
+    #[must_use]
+    pub fn with_detonation_timeout(mut self, detonation_timeout: Duration) -> Self {
+        self.detonation_timeout = detonation_timeout;
+        self
+    }
+}
+
+impl Default for EngagementSettings {
+    fn default() -> Self {
+        Self {
+            detonation_timeout: Duration::from_mins(1),
+        }
+    }
+}
+
+/// How an engagement ended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EngagementOutcome {
+    /// The fired munition detonated.
+    Detonated,
+    /// No detonation was received for the fire event within the timeout.
+    TimedOut,
+    /// A detonation was received without a preceding fire event.
+    DetonationWithoutFire,
+}
+
+/// A complete engagement, from fire to detonation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Engagement {
+    pub event_id: EventId,
+    pub munition_id: EntityId,
+    pub shooter_id: EntityId,
+    pub target_id: EntityId,
+    pub fire: Option<Fire>,
+    pub fired_at: Option<Duration>,
+    pub detonation: Option<Detonation>,
+    pub detonated_at: Option<Duration>,
+    /// The dead reckoned location of the target at the time of detonation, when the state of the target is known.
+    pub target_location: Option<Location>,
+    /// Distance in meters between the detonation and the target location.
+    pub miss_distance: Option<f64>,
+    pub outcome: EngagementOutcome,
+}
+
+#[derive(Clone, Debug)]
+struct PendingFire {
+    fire: Fire,
+    fired_at: Duration,
+}
+
+#[derive(Clone, Debug)]
+struct KnownEntity {
+    state: EntityState,
+    received_at: Duration,
+}
+
+/// Tracks fire events until their detonation.
+///
+/// Typical usage:
+/// 1. Pass each incoming PDU body to [`EngagementTracker::handle`], which keeps track of Fire PDUs and the Entity State of targets,
+///    and completes engagements on Detonation PDUs.
+/// 2. Regularly call [`EngagementTracker::poll`] to time out fire events without a detonation.
+/// 3. Collect completed engagements using [`EngagementTracker::pop_engagement`] or [`EngagementTracker::drain_engagements`].
+#[derive(Clone, Debug, Default)]
+pub struct EngagementTracker {
+    settings: EngagementSettings,
+    pending: BTreeMap<(EventId, EntityId), PendingFire>,
+    entities: BTreeMap<EntityId, KnownEntity>,
+    engagements: VecDeque<Engagement>,
+}
+
+impl EngagementTracker {
+    #[must_use]
+    pub fn new(settings: EngagementSettings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn settings(&self) -> &EngagementSettings {
+        &self.settings
+    }
+
+    /// Processes an incoming PDU body received at time `now`.
+    /// Fire, Detonation and Entity State bodies are used, other bodies are ignored.
+    pub fn handle(&mut self, body: &PduBody, now: Duration) {
+        match body {
+            PduBody::Fire(fire) => self.fire(fire, now),
+            PduBody::Detonation(detonation) => self.detonation(detonation, now),
+            PduBody::EntityState(state) => self.entity_state(state, now),
+            _ => {}
+        }
+    }
+
+    /// Starts tracking the fire event of `fire`, received at time `now`.
+    pub fn fire(&mut self, fire: &Fire, now: Duration) {
+        self.pending.insert(
+            (fire.event_id, fire.entity_id),
+            PendingFire {
+                fire: fire.clone(),
+                fired_at: now,
+            },
+        );
+    }
+
+    /// Completes the engagement of `detonation`, received at time `now`.
+    pub fn detonation(&mut self, detonation: &Detonation, now: Duration) {
+        let pending = self
+            .pending
+            .remove(&(detonation.event_id, detonation.exploding_entity_id));
+
+        let shooter_id = pending
+            .as_ref()
+            .and_then(|pending| pending.fire.originator())
+            .or_else(|| detonation.originator())
+            .copied()
+            .unwrap_or_default();
+        let target_id = detonation
+            .receiver()
+            .filter(|id| is_known(**id))
+            .or_else(|| pending.as_ref().and_then(|pending| pending.fire.receiver()))
+            .copied()
+            .unwrap_or_default();
+
+        let target_location = self.entities.get(&target_id).map(|target| {
+            let (location, _) = target
+                .state
+                .dead_reckon(now.saturating_sub(target.received_at));
+            location
+        });
+        let miss_distance = target_location
+            .map(|target| location_difference(&detonation.location_in_world_coordinates, &target));
+
+        let outcome = if pending.is_some() {
+            EngagementOutcome::Detonated
+        } else {
+            EngagementOutcome::DetonationWithoutFire
+        };
+        let (fire, fired_at) = pending
+            .map(|pending| (pending.fire, pending.fired_at))
+            .unzip();
+
+        self.engagements.push_back(Engagement {
+            event_id: detonation.event_id,
+            munition_id: detonation.exploding_entity_id,
+            shooter_id,
+            target_id,
+            fire,
+            fired_at,
+            detonation: Some(detonation.clone()),
+            detonated_at: Some(now),
+            target_location,
+            miss_distance,
+            outcome,
+        });
+    }
+
+    /// Keeps the latest `state` of an entity, received at time `now`, to locate targets.
+    pub fn entity_state(&mut self, state: &EntityState, now: Duration) {
+        self.entities.insert(
+            state.entity_id,
+            KnownEntity {
+                state: state.clone(),
+                received_at: now,
+            },
+        );
+    }
+
+    /// Forgets the state of the entity with `entity_id`.
+    pub fn remove_entity(&mut self, entity_id: &EntityId) {
+        self.entities.remove(entity_id);
+    }
+
+    /// Advances the tracker to time `now`, closing fire events for which the detonation timeout has passed.
+    pub fn poll(&mut self, now: Duration) {
+        let timed_out: Vec<(EventId, EntityId)> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| {
+                now.saturating_sub(pending.fired_at) >= self.settings.detonation_timeout
+            })
+            .map(|(key, _)| *key)
+            .collect();
+
+        for key in timed_out {
+            if let Some(pending) = self.pending.remove(&key) {
+                self.engagements.push_back(Engagement {
+                    event_id: pending.fire.event_id,
+                    munition_id: pending.fire.entity_id,
+                    shooter_id: pending.fire.firing_entity_id,
+                    target_id: pending.fire.target_entity_id,
+                    fire: Some(pending.fire),
+                    fired_at: Some(pending.fired_at),
+                    detonation: None,
+                    detonated_at: None,
+                    target_location: None,
+                    miss_distance: None,
+                    outcome: EngagementOutcome::TimedOut,
+                });
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Takes the oldest completed engagement.
+    pub fn pop_engagement(&mut self) -> Option<Engagement> {
+        self.engagements.pop_front()
+    }
+
+    /// Takes all completed engagements, oldest first.
+    pub fn drain_engagements(&mut self) -> impl Iterator<Item = Engagement> + '_ {
+        self.engagements.drain(..)
+    }
+}
+
+/// A target of `NO_ENTITY` is unknown (`TARGET_ID_UNKNOWN`).
+fn is_known(id: EntityId) -> bool {
+    id.entity_id != NO_ENTITY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyRaw;
+    use crate::common::entity_state::model::DrParameters;
+    use crate::common::model::VectorF32;
+    use crate::enumerations::DeadReckoningAlgorithm;
+
+    fn shooter() -> EntityId {
+        EntityId::new(1, 1, 1)
+    }
+
+    fn target() -> EntityId {
+        EntityId::new(2, 1, 1)
+    }
+
+    fn munition() -> EntityId {
+        EntityId::new(1, 1, 100)
+    }
+
+    fn fire(event: u16) -> Fire {
+        Fire {
+            firing_entity_id: shooter(),
+            target_entity_id: target(),
+            entity_id: munition(),
+            event_id: EventId::new(1, 1, event),
+            ..Default::default()
+        }
+    }
+
+    fn detonation(event: u16, location: Location) -> Detonation {
+        Detonation {
+            source_entity_id: shooter(),
+            target_entity_id: EntityId::new(0, 0, NO_ENTITY),
+            exploding_entity_id: munition(),
+            event_id: EventId::new(1, 1, event),
+            location_in_world_coordinates: location,
+            ..Default::default()
+        }
+    }
+
+    fn moving_target() -> EntityState {
+        EntityState::builder()
+            .with_entity_id(target())
+            .with_location(Location::new(1_000.0, 0.0, 0.0))
+            .with_velocity(VectorF32::new(10.0, 0.0, 0.0))
+            .with_dead_reckoning_parameters(DrParameters::default().with_algorithm(
+                DeadReckoningAlgorithm::DRM_FPW_ConstantVelocityLowAccelerationLinearMotionEntity,
+            ))
+            .build()
+    }
+
+    #[test]
+    fn fire_and_detonation_form_an_engagement() {
+        let mut tracker = EngagementTracker::default();
+        tracker.handle(&moving_target().into_pdu_body(), Duration::ZERO);
+        tracker.handle(&fire(1).into_pdu_body(), Duration::from_secs(1));
+        assert_eq!(tracker.pending_len(), 1);
+
+        let location = Location::new(1_023.0, 4.0, 0.0);
+        tracker.handle(
+            &detonation(1, location).into_pdu_body(),
+            Duration::from_secs(2),
+        );
+        assert_eq!(tracker.pending_len(), 0);
+
+        let engagement = tracker.pop_engagement().unwrap();
+        assert_eq!(engagement.outcome, EngagementOutcome::Detonated);
+        assert_eq!(engagement.shooter_id, shooter());
+        assert_eq!(engagement.target_id, target());
+        assert_eq!(engagement.fired_at, Some(Duration::from_secs(1)));
+        assert_eq!(
+            engagement.target_location,
+            Some(Location::new(1_020.0, 0.0, 0.0))
+        );
+        assert!((engagement.miss_distance.unwrap() - 5.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fire_without_detonation_times_out() {
+        let mut tracker = EngagementTracker::new(
+            EngagementSettings::default().with_detonation_timeout(Duration::from_secs(10)),
+        );
+        tracker.fire(&fire(1), Duration::from_secs(1));
+        tracker.fire(&fire(2), Duration::from_secs(5));
+
+        tracker.poll(Duration::from_secs(10));
+        assert!(tracker.pop_engagement().is_none());
+
+        tracker.poll(Duration::from_secs(11));
+        let engagement = tracker.pop_engagement().unwrap();
+        assert_eq!(engagement.outcome, EngagementOutcome::TimedOut);
+        assert_eq!(engagement.event_id, EventId::new(1, 1, 1));
+        assert_eq!(engagement.detonation, None);
+        assert_eq!(tracker.pending_len(), 1);
+    }
+
+    #[test]
+    fn detonation_without_fire() {
+        let mut tracker = EngagementTracker::default();
+        tracker.detonation(&detonation(3, Location::default()), Duration::ZERO);
+
+        let engagement = tracker.pop_engagement().unwrap();
+        assert_eq!(engagement.outcome, EngagementOutcome::DetonationWithoutFire);
+        assert_eq!(engagement.shooter_id, shooter());
+        assert_eq!(engagement.fire, None);
+        assert_eq!(engagement.miss_distance, None);
+    }
+}
@@ -0,0 +1,520 @@
+//! Filter expressions over the fields of PDUs, such as `type == EntityState && force == Friendly && entity.site == 12`
+//! or `type in [Fire, Detonation]`.
+//!
+//! An expression consists of conditions `<field> <operator> <value>` or `<field> in [<value>, ...]`,
+//! combined using `&&`, `||`, `!` and parentheses. `&&` takes precedence over `||`.
+//!
+//! The supported fields are:
+//!
+//! | **Field**                                             | **Values**                                               | **Operators**  |
+//! |-------------------------------------------------------|----------------------------------------------------------|----------------|
+//! | `type`, `version`, `family`                           | `PduType`, `ProtocolVersion`, `ProtocolFamily` name, description or number | `==`, `!=`, `in` |
+//! | `exercise`, `length`                                  | number                                                   | all            |
+//! | `originator`, `receiver` (`entity` equals `originator`) | Entity ID `site:application:entity`                     | `==`, `!=`, `in` |
+//! | `originator.site`, `.application`, `.entity` (same for `receiver`, `entity`) | number                        | all            |
+//! | `force`                                               | `ForceId` name, description or number                    | `==`, `!=`, `in` |
+//! | `entity_type`                                         | `EntityTypePattern`, e.g. `1.1.225.*.*.*.*`              | `==`, `!=`, `in` |
+//! | `marking`                                             | text                                                     | `==`, `!=`, `in` |
+//!
+//! Values containing spaces or operator characters are quoted, e.g. `type == "Entity State"`.
+//! The originator and receiver are determined by the `Interaction` impl of the PDU.
+//! `force`, `entity_type` and `marking` are taken from Entity State and Aggregate State PDUs,
+//! and `entity_type` also from the descriptor of Fire and Detonation PDUs.
+//!
+//! A condition on a field that is not present in a PDU does not match, regardless of the operator.
+//! Unknown fields and invalid values are reported when parsing the expression.
+mod parser;
+
+use crate::common::Interaction;
+use crate::common::detonation::model::DetonationDescriptor;
+use crate::common::entity_type_pattern::EntityTypePattern;
+use crate::common::fire::model::FireDescriptor;
+use crate::common::model::{EntityId, EntityType, Pdu, PduBody};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum FilterError {
+    #[error("Unexpected '{1}' at position {0}.")]
+    UnexpectedToken(usize, String),
+    #[error("Unexpected end of the filter expression.")]
+    UnexpectedEnd,
+    #[error("Unknown field '{0}'.")]
+    UnknownField(String),
+    #[error("Invalid value '{value}' for field '{field}'.")]
+    InvalidValue { field: String, value: String },
+    #[error("Operator '{operator}' is not supported for field '{field}'.")]
+    UnsupportedOperator { field: String, operator: Operator },
+}
+
+/// Comparison operators of a condition.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    In,
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Operator::Equal => write!(f, "=="),
+            Operator::NotEqual => write!(f, "!="),
+            Operator::Less => write!(f, "<"),
+            Operator::LessOrEqual => write!(f, "<="),
+            Operator::Greater => write!(f, ">"),
+            Operator::GreaterOrEqual => write!(f, ">="),
+            Operator::In => write!(f, "in"),
+        }
+    }
+}
+
+/// A parsed filter expression, which can be evaluated against PDUs.
+///
+/// When the `serde` feature is enabled, the filter (de)serializes from/to its expression string.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct PduFilter {
+    source: String,
+    expression: Expression,
+}
+
+impl PduFilter {
+    /// Parses filter expression `expression`.
+    ///
+    /// # Errors
+    /// Returns a `FilterError` when the expression is malformed,
+    /// refers to an unknown field, or contains an invalid value or operator for a field.
+    pub fn new(expression: &str) -> Result<Self, FilterError> {
+        Ok(Self {
+            source: String::from(expression.trim()),
+            expression: parser::parse(expression)?,
+        })
+    }
+
+    /// Evaluates the filter against `pdu`.
+    #[must_use]
+    pub fn matches(&self, pdu: &Pdu) -> bool {
+        self.expression.evaluate(pdu)
+    }
+}
+
+impl Display for PduFilter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl FromStr for PduFilter {
+    type Err = FilterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<&str> for PduFilter {
+    type Error = FilterError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<String> for PduFilter {
+    type Error = FilterError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
+impl From<PduFilter> for String {
+    fn from(value: PduFilter) -> Self {
+        value.source
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expression {
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Condition(Condition),
+}
+
+impl Expression {
+    fn evaluate(&self, pdu: &Pdu) -> bool {
+        match self {
+            Expression::And(left, right) => left.evaluate(pdu) && right.evaluate(pdu),
+            Expression::Or(left, right) => left.evaluate(pdu) || right.evaluate(pdu),
+            Expression::Not(expression) => !expression.evaluate(pdu),
+            Expression::Condition(condition) => condition.evaluate(pdu),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Condition {
+    field: Field,
+    operator: Operator,
+    values: Vec<Value>,
+}
+
+impl Condition {
+    fn evaluate(&self, pdu: &Pdu) -> bool {
+        let Some(actual) = self.field.value(pdu) else {
+            return false;
+        };
+        match self.operator {
+            Operator::NotEqual => !self.values.iter().any(|value| value.equals(&actual)),
+            Operator::Equal | Operator::In => self.values.iter().any(|value| value.equals(&actual)),
+            Operator::Less
+            | Operator::LessOrEqual
+            | Operator::Greater
+            | Operator::GreaterOrEqual => {
+                let (FieldValue::Number(actual), Some(Value::Number(expected))) =
+                    (actual, self.values.first())
+                else {
+                    return false;
+                };
+                match self.operator {
+                    Operator::Less => actual < *expected,
+                    Operator::LessOrEqual => actual <= *expected,
+                    Operator::Greater => actual > *expected,
+                    _ => actual >= *expected,
+                }
+            }
+        }
+    }
+}
+
+/// The fields of a PDU that can be used in a filter expression.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Field {
+    PduType,
+    ProtocolVersion,
+    ProtocolFamily,
+    ExerciseId,
+    PduLength,
+    Originator(IdPart),
+    Receiver(IdPart),
+    ForceId,
+    EntityType,
+    Marking,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum IdPart {
+    Id,
+    Site,
+    Application,
+    Entity,
+}
+
+/// The kind of values a field holds, which determines how values are parsed and which operators are supported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum FieldKind {
+    PduType,
+    ProtocolVersion,
+    ProtocolFamily,
+    ForceId,
+    Number,
+    EntityId,
+    EntityType,
+    Text,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        let (base, part) = match name.split_once('.') {
+            Some((base, "site")) => (base, IdPart::Site),
+            Some((base, "application")) => (base, IdPart::Application),
+            Some((base, "entity")) => (base, IdPart::Entity),
+            Some(_) => return None,
+            None => (name, IdPart::Id),
+        };
+        match (base, part) {
+            ("type", IdPart::Id) => Some(Field::PduType),
+            ("version", IdPart::Id) => Some(Field::ProtocolVersion),
+            ("family", IdPart::Id) => Some(Field::ProtocolFamily),
+            ("exercise", IdPart::Id) => Some(Field::ExerciseId),
+            ("length", IdPart::Id) => Some(Field::PduLength),
+            ("force", IdPart::Id) => Some(Field::ForceId),
+            ("entity_type", IdPart::Id) => Some(Field::EntityType),
+            ("marking", IdPart::Id) => Some(Field::Marking),
+            ("originator" | "entity", part) => Some(Field::Originator(part)),
+            ("receiver", part) => Some(Field::Receiver(part)),
+            _ => None,
+        }
+    }
+
+    fn kind(self) -> FieldKind {
+        match self {
+            Field::PduType => FieldKind::PduType,
+            Field::ProtocolVersion => FieldKind::ProtocolVersion,
+            Field::ProtocolFamily => FieldKind::ProtocolFamily,
+            Field::ForceId => FieldKind::ForceId,
+            Field::ExerciseId
+            | Field::PduLength
+            | Field::Originator(IdPart::Site | IdPart::Application | IdPart::Entity)
+            | Field::Receiver(IdPart::Site | IdPart::Application | IdPart::Entity) => {
+                FieldKind::Number
+            }
+            Field::Originator(IdPart::Id) | Field::Receiver(IdPart::Id) => FieldKind::EntityId,
+            Field::EntityType => FieldKind::EntityType,
+            Field::Marking => FieldKind::Text,
+        }
+    }
+
+    fn value(self, pdu: &Pdu) -> Option<FieldValue<'_>> {
+        match self {
+            Field::PduType => Some(FieldValue::Number(u8::from(pdu.header.pdu_type).into())),
+            Field::ProtocolVersion => Some(FieldValue::Number(
+                u8::from(pdu.header.protocol_version).into(),
+            )),
+            Field::ProtocolFamily => Some(FieldValue::Number(
+                u8::from(pdu.header.protocol_family).into(),
+            )),
+            Field::ExerciseId => Some(FieldValue::Number(pdu.header.exercise_id.into())),
+            Field::PduLength => Some(FieldValue::Number(pdu.header.pdu_length.into())),
+            Field::Originator(part) => pdu.originator().map(|id| part.value(id)),
+            Field::Receiver(part) => pdu.receiver().map(|id| part.value(id)),
+            Field::ForceId => match &pdu.body {
+                PduBody::EntityState(body) => Some(body.force_id),
+                PduBody::AggregateState(body) => Some(body.force_id),
+                _ => None,
+            }
+            .map(|force_id| FieldValue::Number(u8::from(force_id).into())),
+            Field::EntityType => match &pdu.body {
+                PduBody::EntityState(body) => Some(body.entity_type),
+                PduBody::Fire(body) => Some(match &body.descriptor {
+                    FireDescriptor::Munition(descriptor) => descriptor.entity_type,
+                    FireDescriptor::Expendable(descriptor) => descriptor.entity_type,
+                }),
+                PduBody::Detonation(body) => Some(match &body.descriptor {
+                    DetonationDescriptor::Munition(descriptor) => descriptor.entity_type,
+                    DetonationDescriptor::Explosion(descriptor) => descriptor.entity_type,
+                    DetonationDescriptor::Expendable(descriptor) => descriptor.entity_type,
+                }),
+                _ => None,
+            }
+            .map(FieldValue::EntityType),
+            Field::Marking => match &pdu.body {
+                PduBody::EntityState(body) => Some(body.entity_marking.marking_string.as_str()),
+                PduBody::AggregateState(body) => {
+                    Some(body.aggregate_marking.marking_string.as_str())
+                }
+                _ => None,
+            }
+            .map(FieldValue::Text),
+        }
+    }
+}
+
+impl IdPart {
+    fn value(self, id: &EntityId) -> FieldValue<'static> {
+        match self {
+            IdPart::Id => FieldValue::EntityId(*id),
+            IdPart::Site => FieldValue::Number(id.simulation_address.site_id.into()),
+            IdPart::Application => FieldValue::Number(id.simulation_address.application_id.into()),
+            IdPart::Entity => FieldValue::Number(id.entity_id.into()),
+        }
+    }
+}
+
+/// A value in a filter expression, to compare the field of a PDU with.
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Number(u32),
+    EntityId(EntityId),
+    EntityType(EntityTypePattern),
+    Text(String),
+}
+
+impl Value {
+    fn equals(&self, actual: &FieldValue) -> bool {
+        match (self, actual) {
+            (Value::Number(expected), FieldValue::Number(actual)) => expected == actual,
+            (Value::EntityId(expected), FieldValue::EntityId(actual)) => expected == actual,
+            (Value::EntityType(pattern), FieldValue::EntityType(actual)) => pattern.matches(actual),
+            (Value::Text(expected), FieldValue::Text(actual)) => expected == actual,
+            _ => false,
+        }
+    }
+}
+
+/// The value of a field of a PDU.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum FieldValue<'a> {
+    Number(u32),
+    EntityId(EntityId),
+    EntityType(EntityType),
+    Text(&'a str),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyRaw;
+    use crate::common::BodyInfo;
+    use crate::common::entity_state::model::{EntityMarking, EntityState};
+    use crate::common::fire::model::Fire;
+    use crate::common::model::{MunitionDescriptor, PduHeader, TimeUnits, Timestamp};
+    use crate::common::signal::model::Signal;
+    use crate::enumerations::{Country, EntityKind, ForceId, PduType, PlatformDomain};
+
+    fn finalize(body: PduBody) -> Pdu {
+        Pdu::finalize_from_parts(
+            PduHeader::new_v7(1, body.body_type()),
+            body,
+            Timestamp::Absolute(TimeUnits::new(35_791_394).unwrap()),
+        )
+    }
+
+    fn entity_state() -> Pdu {
+        finalize(
+            EntityState::builder()
+                .with_entity_id(EntityId::new(12, 1, 3))
+                .with_force_id(ForceId::Friendly)
+                .with_entity_type(
+                    EntityType::default()
+                        .with_kind(EntityKind::Platform)
+                        .with_domain(PlatformDomain::Land)
+                        .with_country(Country::from(225)),
+                )
+                .with_marking(EntityMarking::new_ascii("EYE 10"))
+                .build()
+                .into_pdu_body(),
+        )
+    }
+
+    fn fire() -> Pdu {
+        finalize(
+            Fire::builder()
+                .with_firing_entity_id(EntityId::new(12, 1, 3))
+                .with_target_entity_id(EntityId::new(13, 1, 1))
+                .with_descriptor(FireDescriptor::Munition(
+                    MunitionDescriptor::default().with_entity_type(
+                        EntityType::default()
+                            .with_kind(EntityKind::Munition)
+                            .with_category(2),
+                    ),
+                ))
+                .build()
+                .into_pdu_body(),
+        )
+    }
+
+    fn signal() -> Pdu {
+        finalize(Signal::builder().build().into_pdu_body())
+    }
+
+    fn filter(expression: &str) -> PduFilter {
+        PduFilter::new(expression).unwrap()
+    }
+
+    #[test]
+    fn header_and_body_fields() {
+        let filter = filter("type == EntityState && force == Friendly && entity.site == 12");
+        assert!(filter.matches(&entity_state()));
+        assert!(!filter.matches(&fire()));
+        assert!(!filter.matches(&signal()));
+    }
+
+    #[test]
+    fn in_list() {
+        let filter = filter("type in [Fire, Detonation]");
+        assert!(filter.matches(&fire()));
+        assert!(!filter.matches(&entity_state()));
+
+        assert!(self::filter(r#"type in ["Entity State", 2]"#).matches(&entity_state()));
+        assert!(self::filter(r#"type in ["Entity State", 2]"#).matches(&fire()));
+    }
+
+    #[test]
+    fn interactions() {
+        assert!(filter("originator == 12:1:3 && receiver == 13:1:1").matches(&fire()));
+        assert!(filter("receiver.site >= 13").matches(&fire()));
+        assert!(!filter("receiver.site < 13").matches(&fire()));
+        assert!(!filter("receiver.site < 13").matches(&signal()));
+        assert!(!filter("receiver != 1:1:1").matches(&signal()));
+    }
+
+    #[test]
+    fn entity_type_and_marking() {
+        assert!(filter("entity_type == 1.1.225.*.*.*.*").matches(&entity_state()));
+        assert!(filter(r#"entity_type == "2.*.*.2-4""#).matches(&fire()));
+        assert!(filter("entity_type != 2.*.*.2-4").matches(&entity_state()));
+        assert!(filter(r#"marking == "EYE 10""#).matches(&entity_state()));
+        assert!(!filter(r#"marking == "EYE 1""#).matches(&entity_state()));
+    }
+
+    #[test]
+    fn logical_operators() {
+        assert!(
+            filter("type == Fire || type == EntityState && force == Opposing").matches(&fire())
+        );
+        assert!(
+            !filter("(type == Fire || type == EntityState) && force == Opposing")
+                .matches(&entity_state())
+        );
+        assert!(filter("!(type == Fire) && !type == Signal").matches(&entity_state()));
+        assert!(filter("exercise == 1 && length > 12").matches(&signal()));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            PduFilter::new("color == Blue"),
+            Err(FilterError::UnknownField(String::from("color")))
+        );
+        assert_eq!(
+            PduFilter::new("originator.name == 1"),
+            Err(FilterError::UnknownField(String::from("originator.name")))
+        );
+        assert_eq!(
+            PduFilter::new("force == Blue"),
+            Err(FilterError::InvalidValue {
+                field: String::from("force"),
+                value: String::from("Blue")
+            })
+        );
+        assert_eq!(
+            PduFilter::new("type < Fire"),
+            Err(FilterError::UnsupportedOperator {
+                field: String::from("type"),
+                operator: Operator::Less
+            })
+        );
+        assert_eq!(
+            PduFilter::new("type == Fire &&"),
+            Err(FilterError::UnexpectedEnd)
+        );
+        assert_eq!(
+            PduFilter::new("type == Fire )"),
+            Err(FilterError::UnexpectedToken(13, String::from(")")))
+        );
+        assert_eq!(
+            PduFilter::new("type = Fire"),
+            Err(FilterError::UnexpectedToken(5, String::from("=")))
+        );
+    }
+
+    #[test]
+    fn display() {
+        let filter = filter(" type in [Fire, Detonation] ");
+        assert_eq!(filter.to_string(), "type in [Fire, Detonation]");
+    }
+}
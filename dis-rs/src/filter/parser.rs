@@ -0,0 +1,330 @@
+//! Tokenizer and recursive descent parser for filter expressions.
+//!
+//! ```text
+//! or         := and ( "||" and )*
+//! and        := unary ( "&&" unary )*
+//! unary      := "!" unary | "(" or ")" | condition
+//! condition  := field operator value | field "in" "[" value ( "," value )* "]"
+//! ```
+use crate::common::entity_type_pattern::EntityTypePattern;
+use crate::common::model::EntityId;
+use crate::enumerations::{ForceId, PduType, ProtocolFamily, ProtocolVersion};
+use crate::filter::{Condition, Expression, Field, FieldKind, FilterError, Operator, Value};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::Peekable;
+use core::str::{CharIndices, FromStr};
+
+const IN_KEYWORD: &str = "in";
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// Field names and unquoted values, such as `type`, `EntityState`, `12:1:3` or `1.1.225.*`.
+    Word(String),
+    /// Quoted values, such as `"Entity State"`.
+    Text(String),
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    LeftParenthesis,
+    RightParenthesis,
+    LeftBracket,
+    RightBracket,
+    Comma,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Word(word) => word.clone(),
+            Token::Text(text) => alloc::format!("\"{text}\""),
+            Token::Operator(operator) => operator.to_string(),
+            Token::And => String::from("&&"),
+            Token::Or => String::from("||"),
+            Token::Not => String::from("!"),
+            Token::LeftParenthesis => String::from("("),
+            Token::RightParenthesis => String::from(")"),
+            Token::LeftBracket => String::from("["),
+            Token::RightBracket => String::from("]"),
+            Token::Comma => String::from(","),
+        }
+    }
+}
+
+/// Consumes the next character when it equals `expected`.
+fn next_is(chars: &mut Peekable<CharIndices<'_>>, expected: char) -> bool {
+    chars.next_if(|(_, c)| *c == expected).is_some()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | ':' | '*' | '-')
+}
+
+/// Splits `input` into tokens, each paired with its (byte) position in `input`.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParenthesis,
+            ')' => Token::RightParenthesis,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            ',' => Token::Comma,
+            '=' if next_is(&mut chars, '=') => Token::Operator(Operator::Equal),
+            '!' if next_is(&mut chars, '=') => Token::Operator(Operator::NotEqual),
+            '!' => Token::Not,
+            '<' if next_is(&mut chars, '=') => Token::Operator(Operator::LessOrEqual),
+            '<' => Token::Operator(Operator::Less),
+            '>' if next_is(&mut chars, '=') => Token::Operator(Operator::GreaterOrEqual),
+            '>' => Token::Operator(Operator::Greater),
+            '&' if next_is(&mut chars, '&') => Token::And,
+            '|' if next_is(&mut chars, '|') => Token::Or,
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => text.push(c),
+                        None => return Err(FilterError::UnexpectedEnd),
+                    }
+                }
+                Token::Text(text)
+            }
+            c if is_word_char(c) => {
+                let mut word = String::from(c);
+                while let Some((_, c)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+                    word.push(c);
+                }
+                Token::Word(word)
+            }
+            c => return Err(FilterError::UnexpectedToken(position, c.to_string())),
+        };
+        tokens.push((position, token));
+    }
+
+    Ok(tokens)
+}
+
+/// Parses filter expression `input`.
+pub(crate) fn parse(input: &str) -> Result<Expression, FilterError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+    };
+    let expression = parser.or()?;
+    match parser.tokens.get(parser.position) {
+        None => Ok(expression),
+        Some((position, token)) => Err(FilterError::UnexpectedToken(*position, token.describe())),
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), FilterError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(FilterError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: &Token) -> Result<(), FilterError> {
+        let (position, token) = self.next()?;
+        if token == *expected {
+            Ok(())
+        } else {
+            Err(FilterError::UnexpectedToken(position, token.describe()))
+        }
+    }
+
+    fn or(&mut self) -> Result<Expression, FilterError> {
+        let mut expression = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+
+    fn and(&mut self) -> Result<Expression, FilterError> {
+        let mut expression = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            expression = Expression::And(Box::new(expression), Box::new(self.unary()?));
+        }
+        Ok(expression)
+    }
+
+    fn unary(&mut self) -> Result<Expression, FilterError> {
+        match self.next()? {
+            (_, Token::Not) => Ok(Expression::Not(Box::new(self.unary()?))),
+            (_, Token::LeftParenthesis) => {
+                let expression = self.or()?;
+                self.expect(&Token::RightParenthesis)?;
+                Ok(expression)
+            }
+            (_, Token::Word(name)) => self.condition(name),
+            (position, token) => Err(FilterError::UnexpectedToken(position, token.describe())),
+        }
+    }
+
+    fn condition(&mut self, name: String) -> Result<Expression, FilterError> {
+        let field =
+            Field::from_name(&name).ok_or_else(|| FilterError::UnknownField(name.clone()))?;
+
+        let operator = match self.next()? {
+            (_, Token::Operator(operator)) => operator,
+            (_, Token::Word(word)) if word == IN_KEYWORD => Operator::In,
+            (position, token) => {
+                return Err(FilterError::UnexpectedToken(position, token.describe()));
+            }
+        };
+        let is_ordering = matches!(
+            operator,
+            Operator::Less | Operator::LessOrEqual | Operator::Greater | Operator::GreaterOrEqual
+        );
+        if is_ordering && field.kind() != FieldKind::Number {
+            return Err(FilterError::UnsupportedOperator {
+                field: name,
+                operator,
+            });
+        }
+
+        let values = if operator == Operator::In {
+            self.expect(&Token::LeftBracket)?;
+            let mut values = vec![self.value(&name, field)?];
+            while self.peek() == Some(&Token::Comma) {
+                self.position += 1;
+                values.push(self.value(&name, field)?);
+            }
+            self.expect(&Token::RightBracket)?;
+            values
+        } else {
+            vec![self.value(&name, field)?]
+        };
+
+        Ok(Expression::Condition(Condition {
+            field,
+            operator,
+            values,
+        }))
+    }
+
+    fn value(&mut self, name: &str, field: Field) -> Result<Value, FilterError> {
+        let value = match self.next()? {
+            (_, Token::Word(value) | Token::Text(value)) => value,
+            (position, token) => {
+                return Err(FilterError::UnexpectedToken(position, token.describe()));
+            }
+        };
+        parse_value(field.kind(), &value).ok_or_else(|| FilterError::InvalidValue {
+            field: String::from(name),
+            value,
+        })
+    }
+}
+
+/// Parses `value` for a field of kind `kind`.
+/// Enumerations are parsed from their number, variant name or description.
+fn parse_value(kind: FieldKind, value: &str) -> Option<Value> {
+    match kind {
+        FieldKind::PduType => parse_enumeration::<PduType>(value),
+        FieldKind::ProtocolVersion => parse_enumeration::<ProtocolVersion>(value),
+        FieldKind::ProtocolFamily => parse_enumeration::<ProtocolFamily>(value),
+        FieldKind::ForceId => parse_enumeration::<ForceId>(value),
+        FieldKind::Number => value.parse::<u32>().ok().map(Value::Number),
+        FieldKind::EntityId => EntityId::from_str(value).ok().map(Value::EntityId),
+        FieldKind::EntityType => EntityTypePattern::from_str(value)
+            .ok()
+            .map(Value::EntityType),
+        FieldKind::Text => Some(Value::Text(String::from(value))),
+    }
+}
+
+fn parse_enumeration<E>(value: &str) -> Option<Value>
+where
+    E: FromStr,
+    u8: From<E>,
+{
+    value
+        .parse::<u8>()
+        .ok()
+        .or_else(|| E::from_str(value).ok().map(u8::from))
+        .map(|value| Value::Number(value.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_expression() {
+        assert_eq!(
+            tokenize(r#"!(type=="Entity State")||originator in [1:2:3]"#),
+            Ok(vec![
+                (0, Token::Not),
+                (1, Token::LeftParenthesis),
+                (2, Token::Word(String::from("type"))),
+                (6, Token::Operator(Operator::Equal)),
+                (8, Token::Text(String::from("Entity State"))),
+                (22, Token::RightParenthesis),
+                (23, Token::Or),
+                (25, Token::Word(String::from("originator"))),
+                (36, Token::Word(String::from("in"))),
+                (39, Token::LeftBracket),
+                (40, Token::Word(String::from("1:2:3"))),
+                (45, Token::RightBracket),
+            ])
+        );
+    }
+
+    #[test]
+    fn tokenize_errors() {
+        assert_eq!(
+            tokenize(r#"marking == "EYE"#),
+            Err(FilterError::UnexpectedEnd)
+        );
+        assert_eq!(
+            tokenize("type == Fire & force == Friendly"),
+            Err(FilterError::UnexpectedToken(13, String::from("&")))
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        let expression = parse("type == 1 || type == 2 && !type == 3").unwrap();
+        let condition = |value| {
+            Box::new(Expression::Condition(Condition {
+                field: Field::PduType,
+                operator: Operator::Equal,
+                values: vec![Value::Number(value)],
+            }))
+        };
+        assert_eq!(
+            expression,
+            Expression::Or(
+                condition(1),
+                Box::new(Expression::And(
+                    condition(2),
+                    Box::new(Expression::Not(condition(3)))
+                ))
+            )
+        );
+    }
+}
@@ -18,10 +18,18 @@
 
 extern crate alloc;
 
+pub mod aggregate_hierarchy;
+#[cfg(feature = "tokio")]
+pub mod application;
 mod common;
 mod constants;
+pub mod engagement;
+pub mod filter;
 mod fixed_parameters;
+pub mod local_entity;
 mod math;
+pub mod radio_net;
+pub mod simulation_management;
 pub mod utils;
 mod v6;
 mod v7;
@@ -34,6 +42,7 @@
 pub use common::parse;
 pub use common::parse_v6 as parse_v6_pdus;
 pub use common::parse_v7 as parse_v7_pdus;
+pub use common::parse_with_registry;
 pub use common::supported_protocol_versions;
 pub use v7::entity_state::entity_capabilities_from_bytes;
 pub use v7::parser::parse_pdu_status_fields;
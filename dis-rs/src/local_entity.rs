@@ -0,0 +1,468 @@
+//! Managed publication of entities simulated by the local application.
+//!
+//! A [`LocalEntity`] takes the true (simulated) state of an entity and decides when the state must be published:
+//! - an Entity State PDU when the entity is created;
+//! - an update when the dead reckoned state, as other applications extrapolate it from the last published state,
+//!   deviates more than the `DRA_POS_THRSH` / `DRA_ORIENT_THRSH` thresholds from the true state,
+//!   or when the appearance or other fields change;
+//! - a heartbeat Entity State PDU when nothing has been published for the `HBT_ESPDU_KIND_*` / `HBT_ESPDU_PLATFORM_*` interval
+//!   (or `HBT_STATIONARY` for entities that use the static dead reckoning algorithm);
+//! - a Remove Entity PDU, or an Entity State with a deactivated appearance, when the entity is removed or dropped.
+//!
+//! Published PDU bodies are handed to a [`PduSink`]. Time is passed in explicitly by the caller
+//! as a `Duration` since an arbitrary (but fixed) epoch, which allows driving the entity from any clock.
+use crate::BodyRaw;
+use crate::VariableParameters;
+use crate::common::entity_state::dead_reckoning::{location_difference, orientation_difference};
+use crate::common::entity_state::model::EntityState;
+use crate::common::model::{EntityId, EntityType, PduBody};
+use crate::common::remove_entity::model::RemoveEntity;
+use crate::enumerations::{AppearanceEntityOrObjectState, DeadReckoningAlgorithm};
+use crate::fixed_parameters::NO_ENTITY;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+const SECONDS_PER_MINUTE: f32 = 60.0;
+
+/// Receives the PDU bodies published by a [`LocalEntity`].
+pub trait PduSink {
+    fn send_body(&mut self, body: PduBody);
+}
+
+impl PduSink for Vec<PduBody> {
+    fn send_body(&mut self, body: PduBody) {
+        self.push(body);
+    }
+}
+
+impl PduSink for VecDeque<PduBody> {
+    fn send_body(&mut self, body: PduBody) {
+        self.push_back(body);
+    }
+}
+
+impl<S: PduSink + ?Sized> PduSink for &mut S {
+    fn send_body(&mut self, body: PduBody) {
+        (**self).send_body(body);
+    }
+}
+
+/// Bodies sent after the receiving end of the channel is dropped are discarded.
+#[cfg(feature = "std")]
+impl PduSink for std::sync::mpsc::Sender<PduBody> {
+    fn send_body(&mut self, body: PduBody) {
+        let _ = self.send(body);
+    }
+}
+
+/// Bodies sent after the receiving end of the channel is dropped are discarded.
+#[cfg(feature = "tokio")]
+impl PduSink for tokio::sync::mpsc::UnboundedSender<PduBody> {
+    fn send_body(&mut self, body: PduBody) {
+        let _ = self.send(body);
+    }
+}
+
+/// The PDU type used to publish updates of the state.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum StateOutput {
+    /// All updates are published as an Entity State PDU.
+    #[default]
+    EntityState,
+    /// Updates of the location, orientation, velocity, appearance and variable parameters are published as an Entity State Update PDU.
+    /// Creation, heartbeats and changes to other fields are published as an Entity State PDU.
+    EntityStateUpdate,
+}
+
+/// How the removal of an entity is published.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RemovalMode {
+    /// A Remove Entity PDU, sent by the application, for the entity.
+    #[default]
+    RemoveEntity,
+    /// The last state of the entity, with the (Entity or Object) State of the appearance set to `Deactivated`.
+    Deactivate,
+}
+
+/// Settings for publishing the state of a [`LocalEntity`].
+///
+/// The defaults follow the heartbeat and dead reckoning threshold variable parameters for the type of the entity.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LocalEntitySettings {
+    pub heartbeat: Duration,
+    pub stationary_heartbeat: Duration,
+    /// Position threshold in meters.
+    pub position_threshold: f64,
+    /// Orientation threshold in radians.
+    pub orientation_threshold: f64,
+    pub output: StateOutput,
+    pub removal: RemovalMode,
+}
+
+impl LocalEntitySettings {
+    #[must_use]
+    pub fn new(entity_type: &EntityType) -> Self {
+        Self::from_parameters(&VariableParameters::default(), entity_type)
+    }
+
+    /// Creates the settings for `entity_type` from the variable parameters in `parameters`.
+    ///
+    /// Heartbeat intervals that are negative, not a number or too large fall back to the default value of the parameter.
+    #[must_use]
+    pub fn from_parameters(parameters: &VariableParameters, entity_type: &EntityType) -> Self {
+        let defaults = VariableParameters::default();
+        Self {
+            heartbeat: duration_or_default(
+                parameters.hbt_espdu(entity_type),
+                defaults.hbt_espdu(entity_type),
+            ),
+            stationary_heartbeat: duration_or_default(
+                parameters.HBT_STATIONARY * SECONDS_PER_MINUTE,
+                defaults.HBT_STATIONARY * SECONDS_PER_MINUTE,
+            ),
+            position_threshold: f64::from(parameters.DRA_POS_THRSH),
+            orientation_threshold: f64::from(parameters.DRA_ORIENT_THRSH).to_radians(),
+            output: StateOutput::default(),
+            removal: RemovalMode::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_heartbeat(mut self, heartbeat: Duration) -> Self {
+        self.heartbeat = heartbeat;
+        self
+    }
+
+    #[must_use]
+    pub fn with_stationary_heartbeat(mut self, stationary_heartbeat: Duration) -> Self {
+        self.stationary_heartbeat = stationary_heartbeat;
+        self
+    }
+
+    #[must_use]
+    pub fn with_position_threshold(mut self, position_threshold: f64) -> Self {
+        self.position_threshold = position_threshold;
+        self
+    }
+
+    #[must_use]
+    pub fn with_orientation_threshold(mut self, orientation_threshold: f64) -> Self {
+        self.orientation_threshold = orientation_threshold;
+        self
+    }
+
+    #[must_use]
+    pub fn with_output(mut self, output: StateOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    #[must_use]
+    pub fn with_removal(mut self, removal: RemovalMode) -> Self {
+        self.removal = removal;
+        self
+    }
+}
+
+/// Why a [`LocalEntity`] published its state.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PublishReason {
+    /// The dead reckoned location or orientation exceeded a threshold.
+    Threshold,
+    /// The appearance or variable parameters changed.
+    Changed,
+    /// A field that is only part of the Entity State PDU changed (such as the entity type, force or marking).
+    ChangedStatic,
+    /// The heartbeat interval elapsed.
+    Heartbeat,
+}
+
+/// Handle to an entity simulated by the local application, publishing its state to a [`PduSink`].
+///
+/// The removal of the entity is published when the handle is removed using [`LocalEntity::remove`], or dropped.
+pub struct LocalEntity<S: PduSink> {
+    state: EntityState,
+    published: EntityState,
+    last_published: Duration,
+    settings: LocalEntitySettings,
+    sink: S,
+    removed: bool,
+}
+
+impl<S: PduSink> LocalEntity<S> {
+    /// Creates the handle for the entity with initial state `state`, and publishes the state to `sink`.
+    pub fn new(
+        state: EntityState,
+        settings: LocalEntitySettings,
+        mut sink: S,
+        now: Duration,
+    ) -> Self {
+        sink.send_body(state.clone().into_pdu_body());
+        Self {
+            published: state.clone(),
+            state,
+            last_published: now,
+            settings,
+            sink,
+            removed: false,
+        }
+    }
+
+    #[must_use]
+    pub fn entity_id(&self) -> EntityId {
+        self.state.entity_id
+    }
+
+    /// The true state of the entity, as last provided.
+    #[must_use]
+    pub fn state(&self) -> &EntityState {
+        &self.state
+    }
+
+    /// The state of the entity as last published.
+    #[must_use]
+    pub fn published(&self) -> &EntityState {
+        &self.published
+    }
+
+    #[must_use]
+    pub fn settings(&self) -> &LocalEntitySettings {
+        &self.settings
+    }
+
+    /// Updates the true state of the entity at time `now`, and publishes it when required.
+    /// The entity ID of `state` is ignored; the entity keeps the ID it was created with.
+    ///
+    /// Returns the reason the state is published, if it is.
+    pub fn update(&mut self, mut state: EntityState, now: Duration) -> Option<PublishReason> {
+        state.entity_id = self.state.entity_id;
+        self.state = state;
+        self.tick(now)
+    }
+
+    /// Publishes the current true state at time `now` when it deviates from the published state,
+    /// or when the heartbeat interval elapsed.
+    ///
+    /// Returns the reason the state is published, if it is.
+    pub fn tick(&mut self, now: Duration) -> Option<PublishReason> {
+        let reason = self.publish_reason(now)?;
+        let body = if self.settings.output == StateOutput::EntityStateUpdate
+            && matches!(reason, PublishReason::Threshold | PublishReason::Changed)
+        {
+            self.state.to_update().into_pdu_body()
+        } else {
+            self.state.clone().into_pdu_body()
+        };
+        self.sink.send_body(body);
+        self.published = self.state.clone();
+        self.last_published = now;
+
+        Some(reason)
+    }
+
+    /// Publishes the removal of the entity.
+    pub fn remove(mut self) {
+        self.publish_removal();
+    }
+
+    fn publish_reason(&self, now: Duration) -> Option<PublishReason> {
+        let elapsed = now.saturating_sub(self.last_published);
+        let (state, published) = (&self.state, &self.published);
+
+        if state.force_id != published.force_id
+            || state.entity_type != published.entity_type
+            || state.alternative_entity_type != published.alternative_entity_type
+            || state.entity_marking != published.entity_marking
+            || state.entity_capabilities != published.entity_capabilities
+            || state.dead_reckoning_parameters != published.dead_reckoning_parameters
+        {
+            return Some(PublishReason::ChangedStatic);
+        }
+        if state.entity_appearance != published.entity_appearance
+            || state.variable_parameters != published.variable_parameters
+        {
+            return Some(PublishReason::Changed);
+        }
+
+        let (location, orientation) = published.dead_reckon(elapsed);
+        if location_difference(&location, &state.entity_location) > self.settings.position_threshold
+            || orientation_difference(&orientation, &state.entity_orientation)
+                > self.settings.orientation_threshold
+        {
+            return Some(PublishReason::Threshold);
+        }
+
+        let heartbeat = if published.dead_reckoning_parameters.algorithm
+            == DeadReckoningAlgorithm::StaticNonmovingEntity
+        {
+            self.settings.stationary_heartbeat
+        } else {
+            self.settings.heartbeat
+        };
+        (elapsed >= heartbeat).then_some(PublishReason::Heartbeat)
+    }
+
+    fn publish_removal(&mut self) {
+        if self.removed {
+            return;
+        }
+        self.removed = true;
+
+        let body = match self.settings.removal {
+            RemovalMode::RemoveEntity => RemoveEntity::builder()
+                .with_origination_id(EntityId::new_sim_address(
+                    self.state.entity_id.simulation_address,
+                    NO_ENTITY,
+                ))
+                .with_receiving_id(self.state.entity_id)
+                .build()
+                .into_pdu_body(),
+            RemovalMode::Deactivate => {
+                let mut state = self.state.clone();
+                state.entity_appearance = state
+                    .entity_appearance
+                    .with_state(AppearanceEntityOrObjectState::Deactivated);
+                state.into_pdu_body()
+            }
+        };
+        self.sink.send_body(body);
+    }
+}
+
+impl<S: PduSink> Drop for LocalEntity<S> {
+    fn drop(&mut self) {
+        self.publish_removal();
+    }
+}
+
+/// Converts `seconds` to a `Duration`, using `default_seconds` when `seconds` is not a valid duration.
+fn duration_or_default(seconds: f32, default_seconds: f32) -> Duration {
+    Duration::try_from_secs_f32(seconds)
+        .unwrap_or_else(|_| Duration::from_secs_f32(default_seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::entity_state::model::{DrParameters, EntityAppearance};
+    use crate::common::model::{Location, VectorF32};
+    use crate::enumerations::{EntityKind, LandPlatformAppearance, PlatformDomain};
+
+    fn tank() -> EntityState {
+        EntityState::builder()
+            .with_entity_id(EntityId::new(1, 2, 3))
+            .with_entity_type(
+                EntityType::default()
+                    .with_kind(EntityKind::Platform)
+                    .with_domain(PlatformDomain::Land),
+            )
+            .with_velocity(VectorF32::new(10.0, 0.0, 0.0))
+            .with_appearance(EntityAppearance::LandPlatform(
+                LandPlatformAppearance::default(),
+            ))
+            .with_dead_reckoning_parameters(DrParameters::default().with_algorithm(
+                DeadReckoningAlgorithm::DRM_FPW_ConstantVelocityLowAccelerationLinearMotionEntity,
+            ))
+            .build()
+    }
+
+    #[test]
+    fn publishes_on_creation_threshold_and_heartbeat() {
+        let mut sink = Vec::new();
+        let settings = LocalEntitySettings::new(&tank().entity_type)
+            .with_output(StateOutput::EntityStateUpdate);
+        let mut entity = LocalEntity::new(tank(), settings, &mut sink, Duration::ZERO);
+
+        // moving along the dead reckoned path
+        let mut state = tank();
+        state.entity_location = Location::new(20.0, 0.0, 0.0);
+        assert_eq!(entity.update(state.clone(), Duration::from_secs(2)), None);
+
+        // deviating from the dead reckoned path
+        state.entity_location = Location::new(30.0, 2.0, 0.0);
+        assert_eq!(
+            entity.update(state.clone(), Duration::from_secs(3)),
+            Some(PublishReason::Threshold)
+        );
+
+        // following the new dead reckoned path, until the heartbeat interval elapsed
+        state.entity_location = Location::new(50.0, 2.0, 0.0);
+        assert_eq!(entity.update(state.clone(), Duration::from_secs(5)), None);
+        state.entity_location = Location::new(80.0, 2.0, 0.0);
+        assert_eq!(
+            entity.update(state, Duration::from_secs(8)),
+            Some(PublishReason::Heartbeat)
+        );
+        entity.remove();
+
+        assert_eq!(sink.len(), 4);
+        assert!(matches!(sink[0], PduBody::EntityState(_)));
+        assert!(matches!(sink[1], PduBody::EntityStateUpdate(_)));
+        assert!(matches!(sink[2], PduBody::EntityState(_)));
+        if let PduBody::RemoveEntity(remove) = &sink[3] {
+            assert_eq!(remove.receiving_id, EntityId::new(1, 2, 3));
+            assert_eq!(remove.originating_id, EntityId::new(1, 2, NO_ENTITY));
+        } else {
+            panic!("expected a RemoveEntity PDU");
+        }
+    }
+
+    #[test]
+    fn publishes_changes_and_deactivates_on_drop() {
+        let mut sink = VecDeque::new();
+        let settings =
+            LocalEntitySettings::new(&tank().entity_type).with_removal(RemovalMode::Deactivate);
+        {
+            let mut entity = LocalEntity::new(tank(), settings, &mut sink, Duration::ZERO);
+            let mut state = tank();
+            state.entity_appearance = EntityAppearance::LandPlatform(LandPlatformAppearance {
+                is_frozen: true,
+                ..Default::default()
+            });
+            assert_eq!(
+                entity.update(state, Duration::ZERO),
+                Some(PublishReason::Changed)
+            );
+        }
+
+        assert_eq!(sink.len(), 3);
+        if let Some(PduBody::EntityState(state)) = sink.pop_back() {
+            assert_eq!(
+                state.entity_appearance.state(),
+                Some(AppearanceEntityOrObjectState::Deactivated)
+            );
+        } else {
+            panic!("expected an EntityState PDU");
+        }
+    }
+
+    #[test]
+    fn heartbeat_per_entity_kind() {
+        let mut parameters = VariableParameters::default();
+        parameters.HBT_ESPDU_PLATFORM_AIR = 2.0;
+        let aircraft = EntityType::default()
+            .with_kind(EntityKind::Platform)
+            .with_domain(PlatformDomain::Air);
+        let settings = LocalEntitySettings::from_parameters(&parameters, &aircraft);
+        assert_eq!(settings.heartbeat, Duration::from_secs(2));
+        assert_eq!(settings.stationary_heartbeat, Duration::from_mins(1));
+    }
+
+    #[test]
+    fn invalid_heartbeat_parameters() {
+        let aircraft = EntityType::default()
+            .with_kind(EntityKind::Platform)
+            .with_domain(PlatformDomain::Air);
+        let defaults = LocalEntitySettings::new(&aircraft);
+
+        for invalid in [-1.0, f32::NAN, f32::INFINITY, f32::MAX] {
+            let mut parameters = VariableParameters::default();
+            parameters.HBT_ESPDU_PLATFORM_AIR = invalid;
+            parameters.HBT_STATIONARY = invalid;
+            let settings = LocalEntitySettings::from_parameters(&parameters, &aircraft);
+            assert_eq!(settings.heartbeat, defaults.heartbeat);
+            assert_eq!(settings.stationary_heartbeat, defaults.stationary_heartbeat);
+        }
+    }
+}
@@ -40,6 +40,12 @@ pub fn cos(x: f64) -> f64 {
         x.cos()
     }
 
+    /// The base 10 logarithm of `x` (f64).
+    #[inline]
+    pub fn log10(x: f64) -> f64 {
+        x.log10()
+    }
+
     /// Round `x` to the nearest integer, breaking ties away from zero.
     #[inline]
     pub fn round(x: f64) -> f64 {
@@ -63,5 +69,5 @@ pub fn sqrt(x: f64) -> f64 {
 
 #[cfg(not(feature = "std"))]
 mod fns {
-    pub use libm::{acos, asin, atan2, cos, round, sin, sqrt};
+    pub use libm::{acos, asin, atan2, cos, log10, round, sin, sqrt};
 }
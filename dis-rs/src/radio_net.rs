@@ -0,0 +1,508 @@
+//! Radio net helper to determine which simulated radios can hear which transmitters.
+//!
+//! A [`RadioNet`] keeps the latest `Transmitter` PDU of each radio, keyed by [`RadioId`].
+//! For a given [`RadioReceiver`] it determines the transmitters that are in tune and in range,
+//! using free space path loss and the line-of-sight radius (radio horizon) between both antennas.
+//! The result can be turned into the `ReceiverState` (or a complete `Receiver` PDU body) the receiving radio should report.
+use crate::BodyRaw;
+use crate::common::model::{EntityId, Location};
+use crate::common::receiver::model::Receiver;
+use crate::common::transmitter::model::{CryptoKeyId, Transmitter};
+use crate::enumerations::{ReceiverState, TransmitterCryptoSystem, TransmitterTransmitState};
+use crate::utils::ecef_to_geodetic_lla;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// Effective earth radius (4/3 of the mean earth radius of 6371 km) in meters,
+/// accounting for standard atmospheric refraction of radio waves.
+const EFFECTIVE_EARTH_RADIUS_METERS: f64 = 8_494_667.0;
+
+/// Free space path loss constant for distances in meters and frequencies in Hertz, `20 * log10(4 * pi / c)`.
+const FSPL_CONSTANT_DB: f64 = -147.55;
+
+/// Distances are clamped to this minimum (in meters) to keep the path loss finite for co-located antennas.
+const MINIMUM_DISTANCE_METERS: f64 = 1.0;
+
+/// Default sensitivity (minimum received power) of a [`RadioReceiver`], in dBm.
+pub const DEFAULT_RECEIVER_SENSITIVITY_DBM: f32 = -100.0;
+
+/// Identifies a single radio, being the combination of the entity the radio is attached to
+/// (the Radio Reference ID) and the Radio Number of that entity.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RadioId {
+    pub entity_id: EntityId,
+    pub radio_number: u16,
+}
+
+impl RadioId {
+    #[must_use]
+    pub fn new(entity_id: EntityId, radio_number: u16) -> Self {
+        Self {
+            entity_id,
+            radio_number,
+        }
+    }
+}
+
+impl From<&Transmitter> for RadioId {
+    fn from(value: &Transmitter) -> Self {
+        Self::new(value.radio_reference_id, value.radio_number)
+    }
+}
+
+impl From<&Receiver> for RadioId {
+    fn from(value: &Receiver) -> Self {
+        Self::new(value.radio_reference_id, value.radio_number)
+    }
+}
+
+/// The tuning and state of a receiving radio, as input for [`RadioNet::receptions`].
+///
+/// - `frequency` is the center frequency in Hertz.
+/// - `bandwidth` is the receive bandwidth in Hertz.
+/// - `sensitivity` is the minimum received power in dBm for a transmission to be receivable.
+/// - `crypto_system` and `crypto_key_id` must match those of encrypted transmitters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadioReceiver {
+    pub radio_id: RadioId,
+    pub state: ReceiverState,
+    pub antenna_location: Location,
+    pub frequency: u64,
+    pub bandwidth: f32,
+    pub sensitivity: f32,
+    pub crypto_system: TransmitterCryptoSystem,
+    pub crypto_key_id: CryptoKeyId,
+}
+
+impl Default for RadioReceiver {
+    fn default() -> Self {
+        Self {
+            radio_id: RadioId::default(),
+            state: ReceiverState::OnButNotReceiving,
+            antenna_location: Location::default(),
+            frequency: 0,
+            bandwidth: 0.0,
+            sensitivity: DEFAULT_RECEIVER_SENSITIVITY_DBM,
+            crypto_system: TransmitterCryptoSystem::default(),
+            crypto_key_id: CryptoKeyId::default(),
+        }
+    }
+}
+
+impl RadioReceiver {
+    #[must_use]
+    pub fn new(radio_id: RadioId) -> Self {
+        Self {
+            radio_id,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_state(mut self, state: ReceiverState) -> Self {
+        self.state = state;
+        self
+    }
+
+    #[must_use]
+    pub fn with_antenna_location(mut self, antenna_location: Location) -> Self {
+        self.antenna_location = antenna_location;
+        self
+    }
+
+    #[must_use]
+    pub fn with_frequency(mut self, frequency: u64) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    #[must_use]
+    pub fn with_bandwidth(mut self, bandwidth: f32) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    #[must_use]
+    pub fn with_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    #[must_use]
+    pub fn with_crypto_system(mut self, crypto_system: TransmitterCryptoSystem) -> Self {
+        self.crypto_system = crypto_system;
+        self
+    }
+
+    #[must_use]
+    pub fn with_crypto_key_id(mut self, crypto_key_id: CryptoKeyId) -> Self {
+        self.crypto_key_id = crypto_key_id;
+        self
+    }
+
+    /// Returns `true` when the receiver is switched on.
+    #[must_use]
+    pub fn is_on(&self) -> bool {
+        self.state != ReceiverState::Off
+    }
+}
+
+/// A transmission that can be received by a [`RadioReceiver`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Reception {
+    /// The transmitting radio
+    pub transmitter: RadioId,
+    /// Distance between the antennas, in meters
+    pub distance: f64,
+    /// Free space path loss over `distance`, in dB
+    pub path_loss: f64,
+    /// Power at the receiving antenna, in dBm
+    pub received_power: f32,
+}
+
+/// Tracks the transmitters in an exercise and selects which of them a receiving radio can hear.
+#[derive(Clone, Debug, Default)]
+pub struct RadioNet {
+    transmitters: BTreeMap<RadioId, Transmitter>,
+}
+
+impl RadioNet {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores (or replaces) the state of the radio described by `transmitter`.
+    /// Returns the previous state of the radio, if any.
+    pub fn update(&mut self, transmitter: &Transmitter) -> Option<Transmitter> {
+        self.transmitters
+            .insert(RadioId::from(transmitter), transmitter.clone())
+    }
+
+    /// Removes the radio identified by `radio_id`, returning its last known state.
+    pub fn remove(&mut self, radio_id: &RadioId) -> Option<Transmitter> {
+        self.transmitters.remove(radio_id)
+    }
+
+    /// Removes all radios attached to the entity `entity_id`, for instance when the entity is removed from the exercise.
+    pub fn remove_entity(&mut self, entity_id: &EntityId) {
+        self.transmitters
+            .retain(|radio_id, _| radio_id.entity_id != *entity_id);
+    }
+
+    #[must_use]
+    pub fn get(&self, radio_id: &RadioId) -> Option<&Transmitter> {
+        self.transmitters.get(radio_id)
+    }
+
+    pub fn transmitters(&self) -> impl Iterator<Item = (&RadioId, &Transmitter)> {
+        self.transmitters.iter()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.transmitters.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.transmitters.is_empty()
+    }
+
+    /// Computes the transmissions that `receiver` can hear.
+    ///
+    /// A transmission is receivable when the transmitter is transmitting, is in tune with the receiver,
+    /// uses the same crypto system and key (when encrypted), is within the line-of-sight radius of both antennas,
+    /// and arrives with a power at or above the sensitivity of the receiver.
+    /// The radio of the receiver itself is never included.
+    ///
+    /// The resulting list is ordered from strongest to weakest received power.
+    #[must_use]
+    pub fn receptions(&self, receiver: &RadioReceiver) -> Vec<Reception> {
+        if !receiver.is_on() {
+            return Vec::new();
+        }
+
+        let receiver_height = antenna_height(&receiver.antenna_location);
+        let mut receptions: Vec<Reception> = self
+            .transmitters
+            .iter()
+            .filter(|(radio_id, _)| **radio_id != receiver.radio_id)
+            .filter(|(_, transmitter)| {
+                transmitter.transmit_state == TransmitterTransmitState::OnAndTransmitting
+            })
+            .filter(|(_, transmitter)| {
+                is_in_tune(
+                    transmitter.frequency,
+                    transmitter.transmit_frequency_bandwidth,
+                    receiver.frequency,
+                    receiver.bandwidth,
+                )
+            })
+            .filter(|(_, transmitter)| is_crypto_compatible(transmitter, receiver))
+            .filter_map(|(radio_id, transmitter)| {
+                let distance =
+                    ecef_distance(&transmitter.antenna_location, &receiver.antenna_location);
+                let horizon = radio_horizon(
+                    antenna_height(&transmitter.antenna_location),
+                    receiver_height,
+                );
+                if distance > horizon {
+                    return None;
+                }
+                let path_loss = free_space_path_loss(distance, transmitter.frequency as f64);
+                let received_power = (f64::from(transmitter.power) - path_loss) as f32;
+                (received_power >= receiver.sensitivity).then_some(Reception {
+                    transmitter: *radio_id,
+                    distance,
+                    path_loss,
+                    received_power,
+                })
+            })
+            .collect();
+
+        receptions.sort_by(|a, b| b.received_power.total_cmp(&a.received_power));
+        receptions
+    }
+
+    /// Determines the `ReceiverState` that `receiver` should report.
+    #[must_use]
+    pub fn receiver_state(&self, receiver: &RadioReceiver) -> ReceiverState {
+        if !receiver.is_on() {
+            ReceiverState::Off
+        } else if self.receptions(receiver).is_empty() {
+            ReceiverState::OnButNotReceiving
+        } else {
+            ReceiverState::OnAndReceiving
+        }
+    }
+
+    /// Constructs the `Receiver` PDU body that `receiver` should report,
+    /// referencing the strongest transmission when it is receiving.
+    #[must_use]
+    pub fn receiver_body(&self, receiver: &RadioReceiver) -> Receiver {
+        let builder = Receiver::builder()
+            .with_radio_reference_id(receiver.radio_id.entity_id)
+            .with_radio_number(receiver.radio_id.radio_number);
+
+        if !receiver.is_on() {
+            return builder.with_receiver_state(ReceiverState::Off).build();
+        }
+
+        if let Some(strongest) = self.receptions(receiver).first() {
+            builder
+                .with_receiver_state(ReceiverState::OnAndReceiving)
+                .with_received_power(strongest.received_power)
+                .with_transmitter_radio_reference_id(strongest.transmitter.entity_id)
+                .with_transmitter_radio_number(strongest.transmitter.radio_number)
+                .build()
+        } else {
+            builder
+                .with_receiver_state(ReceiverState::OnButNotReceiving)
+                .build()
+        }
+    }
+}
+
+/// Calculates the free space path loss in dB over `distance` meters for a signal of `frequency` Hertz.
+#[must_use]
+pub fn free_space_path_loss(distance: f64, frequency: f64) -> f64 {
+    let distance = distance.max(MINIMUM_DISTANCE_METERS);
+    20.0 * crate::math::log10(distance) + 20.0 * crate::math::log10(frequency) + FSPL_CONSTANT_DB
+}
+
+/// Calculates the line-of-sight radius (radio horizon) in meters between two antennas
+/// at `height_a` and `height_b` meters above the earth's surface.
+#[must_use]
+pub fn radio_horizon(height_a: f64, height_b: f64) -> f64 {
+    crate::math::sqrt(2.0 * EFFECTIVE_EARTH_RADIUS_METERS * height_a.max(0.0))
+        + crate::math::sqrt(2.0 * EFFECTIVE_EARTH_RADIUS_METERS * height_b.max(0.0))
+}
+
+/// Checks whether a transmission at `transmit_frequency` with `transmit_bandwidth` overlaps
+/// with the pass band of a receiver tuned to `receive_frequency` with `receive_bandwidth`.
+/// Frequencies and bandwidths are in Hertz.
+#[must_use]
+pub fn is_in_tune(
+    transmit_frequency: u64,
+    transmit_bandwidth: f32,
+    receive_frequency: u64,
+    receive_bandwidth: f32,
+) -> bool {
+    let separation = transmit_frequency.abs_diff(receive_frequency) as f64;
+    let allowed = (f64::from(transmit_bandwidth) + f64::from(receive_bandwidth)) / 2.0;
+    separation <= allowed
+}
+
+fn is_crypto_compatible(transmitter: &Transmitter, receiver: &RadioReceiver) -> bool {
+    transmitter.crypto_system == TransmitterCryptoSystem::default()
+        || (transmitter.crypto_system == receiver.crypto_system
+            && transmitter.crypto_key_id.pseudo_crypto_key
+                == receiver.crypto_key_id.pseudo_crypto_key)
+}
+
+fn antenna_height(location: &Location) -> f64 {
+    let (_lat, _lon, altitude) = ecef_to_geodetic_lla(
+        location.x_coordinate,
+        location.y_coordinate,
+        location.z_coordinate,
+    );
+    altitude
+}
+
+fn ecef_distance(a: &Location, b: &Location) -> f64 {
+    let dx = a.x_coordinate - b.x_coordinate;
+    let dy = a.y_coordinate - b.y_coordinate;
+    let dz = a.z_coordinate - b.z_coordinate;
+    crate::math::sqrt(dx * dx + dy * dy + dz * dz)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::geodetic_lla_to_ecef;
+
+    const FREQUENCY: u64 = 300_000_000;
+    const BANDWIDTH: f32 = 25_000.0;
+    // one degree of latitude is roughly 111 km
+    const DEGREES_PER_KM: f64 = 1.0 / 111.0;
+
+    fn location_at_km_north(km: f64, altitude: f64) -> Location {
+        let (x, y, z) = geodetic_lla_to_ecef(
+            (52.0 + km * DEGREES_PER_KM).to_radians(),
+            5.0f64.to_radians(),
+            altitude,
+        );
+        Location::new(x, y, z)
+    }
+
+    fn transmitter(entity: u16, location: Location) -> Transmitter {
+        Transmitter::builder()
+            .with_radio_reference_id(EntityId::new(1, 1, entity))
+            .with_radio_number(1)
+            .with_transmit_state(TransmitterTransmitState::OnAndTransmitting)
+            .with_frequency(FREQUENCY)
+            .with_transmit_frequency_bandwidth(BANDWIDTH)
+            .with_power(40.0)
+            .with_antenna_location(location)
+            .build()
+    }
+
+    fn receiver() -> RadioReceiver {
+        RadioReceiver::new(RadioId::new(EntityId::new(1, 1, 100), 1))
+            .with_antenna_location(location_at_km_north(0.0, 10.0))
+            .with_frequency(FREQUENCY)
+            .with_bandwidth(BANDWIDTH)
+    }
+
+    #[test]
+    fn free_space_path_loss_reference_value() {
+        // 10 km at 300 MHz
+        let loss = free_space_path_loss(10_000.0, 300_000_000.0);
+        assert!((loss - 101.99).abs() < 0.01);
+    }
+
+    #[test]
+    fn in_tune_overlapping_bands() {
+        assert!(is_in_tune(FREQUENCY, BANDWIDTH, FREQUENCY, BANDWIDTH));
+        assert!(is_in_tune(
+            FREQUENCY + 25_000,
+            BANDWIDTH,
+            FREQUENCY,
+            BANDWIDTH
+        ));
+        assert!(!is_in_tune(
+            FREQUENCY + 25_001,
+            BANDWIDTH,
+            FREQUENCY,
+            BANDWIDTH
+        ));
+    }
+
+    #[test]
+    fn receives_in_range_transmitter() {
+        let mut net = RadioNet::new();
+        net.update(&transmitter(1, location_at_km_north(10.0, 10.0)));
+
+        let receptions = net.receptions(&receiver());
+        assert_eq!(receptions.len(), 1);
+        assert_eq!(
+            receptions[0].transmitter,
+            RadioId::new(EntityId::new(1, 1, 1), 1)
+        );
+        assert!(receptions[0].received_power < 40.0);
+        assert_eq!(
+            net.receiver_state(&receiver()),
+            ReceiverState::OnAndReceiving
+        );
+    }
+
+    #[test]
+    fn ignores_transmitter_beyond_horizon() {
+        let mut net = RadioNet::new();
+        net.update(&transmitter(1, location_at_km_north(50.0, 10.0)));
+
+        assert!(net.receptions(&receiver()).is_empty());
+        assert_eq!(
+            net.receiver_state(&receiver()),
+            ReceiverState::OnButNotReceiving
+        );
+    }
+
+    #[test]
+    fn ignores_transmitter_out_of_tune_or_not_transmitting() {
+        let mut net = RadioNet::new();
+        net.update(
+            &transmitter(1, location_at_km_north(10.0, 10.0))
+                .into_builder()
+                .with_frequency(FREQUENCY * 2)
+                .build(),
+        );
+        net.update(
+            &transmitter(2, location_at_km_north(10.0, 10.0))
+                .into_builder()
+                .with_transmit_state(TransmitterTransmitState::OnButNotTransmitting)
+                .build(),
+        );
+
+        assert!(net.receptions(&receiver()).is_empty());
+    }
+
+    #[test]
+    fn encrypted_transmission_requires_matching_key() {
+        let crypto_system = TransmitterCryptoSystem::from(2);
+        let key = CryptoKeyId::from(0x0010);
+        let mut net = RadioNet::new();
+        net.update(
+            &transmitter(1, location_at_km_north(10.0, 10.0))
+                .into_builder()
+                .with_crypto_system(crypto_system)
+                .with_crypto_key_id(key)
+                .build(),
+        );
+
+        assert!(net.receptions(&receiver()).is_empty());
+        let keyed_receiver = receiver()
+            .with_crypto_system(crypto_system)
+            .with_crypto_key_id(key);
+        assert_eq!(net.receptions(&keyed_receiver).len(), 1);
+    }
+
+    #[test]
+    fn receiver_body_references_strongest_transmitter() {
+        let mut net = RadioNet::new();
+        net.update(&transmitter(1, location_at_km_north(15.0, 10.0)));
+        net.update(&transmitter(2, location_at_km_north(5.0, 10.0)));
+
+        let body = net.receiver_body(&receiver());
+        assert_eq!(body.receiver_state, ReceiverState::OnAndReceiving);
+        assert_eq!(body.transmitter_radio_reference_id, EntityId::new(1, 1, 2));
+        assert_eq!(body.radio_reference_id, EntityId::new(1, 1, 100));
+
+        let body = net.receiver_body(&receiver().with_state(ReceiverState::Off));
+        assert_eq!(body.receiver_state, ReceiverState::Off);
+
+        net.remove_entity(&EntityId::new(1, 1, 2));
+        assert_eq!(net.len(), 1);
+    }
+}
@@ -0,0 +1,4 @@
+//! Facilities for taking part in Simulation Management (SIMAN) and
+//! Simulation Management with Reliability (SIMAN-R) interactions.
+pub mod reliability;
+pub mod responder;
@@ -0,0 +1,708 @@
+//! Retransmission engine for the acknowledged mode of the Required Reliability Service of
+//! Simulation Management with Reliability (5.12.4, 7.11).
+//!
+//! The [`ReliabilityManager`] is transport-agnostic: it issues Request IDs for outgoing SIMAN-R requests,
+//! tells the caller which requests must be retransmitted, and matches incoming responses
+//! (`AcknowledgeR`, `ActionResponseR`, `DataR`, `RecordR`) to the outstanding requests.
+//! Time is passed in explicitly by the caller as a `Duration` since an arbitrary (but fixed) epoch,
+//! which allows driving the manager from any clock.
+use crate::VariableParameters;
+use crate::common::BodyInfo;
+use crate::common::Interaction;
+use crate::common::model::PduBody;
+use crate::enumerations::{PduType, RequestStatus, RequiredReliabilityService, ResponseFlag};
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+use core::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum ReliabilityError {
+    #[error("PDU type {0} is not a SIMAN-R request with a Request ID.")]
+    NotAReliableRequest(PduType),
+}
+
+/// Timing settings of the acknowledged mode.
+///
+/// The defaults follow the `SM_REL_RETRY_DELAY` (2 s) and `SM_REL_RETRY_CNT` (3) variable parameters.
+/// The standard does not define how long to wait for a final response after an interim response;
+/// `final_response_timeout` defaults to 5 minutes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ReliabilitySettings {
+    pub retry_delay: Duration,
+    pub retry_count: u32,
+    /// Time to wait for a final response after an interim response, measured from the last transmission of the request.
+    pub final_response_timeout: Duration,
+}
+
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(2);
+const DEFAULT_FINAL_RESPONSE_TIMEOUT: Duration = Duration::from_secs(300);
+
+impl ReliabilitySettings {
+    #[must_use]
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    #[must_use]
+    pub fn with_retry_count(mut self, retry_count: u32) -> Self {
+        self.retry_count = retry_count;
+        self
+    }
+
+    #[must_use]
+    pub fn with_final_response_timeout(mut self, final_response_timeout: Duration) -> Self {
+        self.final_response_timeout = final_response_timeout;
+        self
+    }
+}
+
+impl Default for ReliabilitySettings {
+    fn default() -> Self {
+        Self::from(&VariableParameters::default())
+    }
+}
+
+/// A `SM_REL_RETRY_DELAY` that is not a valid duration (negative, NaN or too large) results in the default of 2 s.
+impl From<&VariableParameters> for ReliabilitySettings {
+    fn from(value: &VariableParameters) -> Self {
+        Self {
+            retry_delay: Duration::try_from_secs_f32(value.SM_REL_RETRY_DELAY)
+                .unwrap_or(DEFAULT_RETRY_DELAY),
+            retry_count: value.SM_REL_RETRY_CNT as u32,
+            final_response_timeout: DEFAULT_FINAL_RESPONSE_TIMEOUT,
+        }
+    }
+}
+
+/// The final outcome of an acknowledged request.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RequestOutcome {
+    /// The receiver complied with the request.
+    Completed {
+        request_id: u32,
+        request: PduBody,
+        response: PduBody,
+    },
+    /// The receiver responded, but was unable to comply or rejected the request.
+    Failed {
+        request_id: u32,
+        request: PduBody,
+        response: PduBody,
+    },
+    /// No response was received after all retransmissions,
+    /// or no final response was received within the final response timeout after an interim response.
+    TimedOut { request_id: u32, request: PduBody },
+}
+
+impl RequestOutcome {
+    #[must_use]
+    pub fn request_id(&self) -> u32 {
+        match self {
+            RequestOutcome::Completed { request_id, .. }
+            | RequestOutcome::Failed { request_id, .. }
+            | RequestOutcome::TimedOut { request_id, .. } => *request_id,
+        }
+    }
+}
+
+/// How a response relates to the outstanding request it answers.
+enum ResponseKind {
+    /// The request is finished, successfully or not.
+    Final { success: bool },
+    /// The request is received and being processed; retransmissions stop until a final response arrives.
+    Interim,
+    /// The receiver asks for the request to be retransmitted, immediately or at the next retry.
+    Retransmit { now: bool },
+}
+
+#[derive(Clone, Debug)]
+struct PendingRequest {
+    request: PduBody,
+    last_sent: Duration,
+    retries: u32,
+    awaiting_final: bool,
+    due_now: bool,
+}
+
+/// Tracks outstanding SIMAN-R requests sent in acknowledged mode.
+///
+/// Typical usage:
+/// 1. Pass each outgoing request through [`ReliabilityManager::send`], which assigns the Request ID, and put the returned body on the wire.
+/// 2. Pass each incoming PDU body to [`ReliabilityManager::handle_response`].
+/// 3. Regularly call [`ReliabilityManager::poll`] and put the returned bodies on the wire again.
+/// 4. Collect finished requests using [`ReliabilityManager::pop_outcome`] or [`ReliabilityManager::drain_outcomes`].
+#[derive(Clone, Debug)]
+pub struct ReliabilityManager {
+    settings: ReliabilitySettings,
+    next_request_id: u32,
+    pending: BTreeMap<u32, PendingRequest>,
+    outcomes: VecDeque<RequestOutcome>,
+}
+
+impl Default for ReliabilityManager {
+    fn default() -> Self {
+        Self::new(ReliabilitySettings::default())
+    }
+}
+
+impl ReliabilityManager {
+    #[must_use]
+    pub fn new(settings: ReliabilitySettings) -> Self {
+        Self {
+            settings,
+            next_request_id: 1,
+            pending: BTreeMap::new(),
+            outcomes: VecDeque::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn settings(&self) -> &ReliabilitySettings {
+        &self.settings
+    }
+
+    /// Issues a new Request ID that is not in use by an outstanding request.
+    pub fn next_request_id(&mut self) -> u32 {
+        loop {
+            let request_id = self.next_request_id;
+            self.next_request_id = self.next_request_id.wrapping_add(1);
+            if !self.pending.contains_key(&request_id) {
+                return request_id;
+            }
+        }
+    }
+
+    /// Prepares `request` for sending at time `now` by assigning a new Request ID.
+    ///
+    /// When the request specifies the acknowledged Required Reliability Service,
+    /// the request is tracked for retransmission and response matching.
+    /// Returns the body to be sent.
+    ///
+    /// # Errors
+    /// Returns a `ReliabilityError::NotAReliableRequest` when `request` is not a SIMAN-R request that carries a Request ID.
+    pub fn send(
+        &mut self,
+        mut request: PduBody,
+        now: Duration,
+    ) -> Result<PduBody, ReliabilityError> {
+        let Some(service) = required_reliability_service(&request) else {
+            return Err(ReliabilityError::NotAReliableRequest(request.body_type()));
+        };
+
+        let request_id = self.next_request_id();
+        set_request_id(&mut request, request_id);
+
+        if service == RequiredReliabilityService::Acknowledged {
+            self.pending.insert(
+                request_id,
+                PendingRequest {
+                    request: request.clone(),
+                    last_sent: now,
+                    retries: 0,
+                    awaiting_final: false,
+                    due_now: false,
+                },
+            );
+        }
+
+        Ok(request)
+    }
+
+    /// Matches an incoming PDU body against the outstanding requests.
+    ///
+    /// Returns the Request ID of the matched request, or `None` when `response` does not answer an outstanding request.
+    /// Final responses result in a `RequestOutcome` being queued.
+    pub fn handle_response(&mut self, response: &PduBody) -> Option<u32> {
+        let request_id = response_request_id(response)?;
+        let pending = self.pending.get_mut(&request_id)?;
+
+        if let (Some(requester), Some(addressee)) =
+            (pending.request.originator(), response.receiver())
+            && requester != addressee
+        {
+            return None;
+        }
+
+        match classify_response(&pending.request, response)? {
+            ResponseKind::Final { success } => {
+                let pending = self
+                    .pending
+                    .remove(&request_id)
+                    .expect("Impossible - pending request was just found");
+                let outcome = if success {
+                    RequestOutcome::Completed {
+                        request_id,
+                        request: pending.request,
+                        response: response.clone(),
+                    }
+                } else {
+                    RequestOutcome::Failed {
+                        request_id,
+                        request: pending.request,
+                        response: response.clone(),
+                    }
+                };
+                self.outcomes.push_back(outcome);
+            }
+            ResponseKind::Interim => {
+                pending.awaiting_final = true;
+            }
+            ResponseKind::Retransmit { now } => {
+                pending.awaiting_final = false;
+                pending.due_now = now;
+            }
+        }
+
+        Some(request_id)
+    }
+
+    /// Advances the retransmission schedule to time `now`.
+    ///
+    /// Returns the requests that must be retransmitted.
+    /// Requests that exhausted their retries are removed and queued as `RequestOutcome::TimedOut`.
+    /// Requests for which an interim response (e.g., Pending Operator Action) was received are not retransmitted,
+    /// and time out when no final response is received within the final response timeout.
+    pub fn poll(&mut self, now: Duration) -> Vec<PduBody> {
+        let mut retransmissions = Vec::new();
+        let mut timed_out = Vec::new();
+
+        for (request_id, pending) in &mut self.pending {
+            if pending.awaiting_final {
+                if now.saturating_sub(pending.last_sent) >= self.settings.final_response_timeout {
+                    timed_out.push(*request_id);
+                }
+                continue;
+            }
+            let is_due = pending.due_now
+                || now.saturating_sub(pending.last_sent) >= self.settings.retry_delay;
+            if !is_due {
+                continue;
+            }
+            if pending.retries < self.settings.retry_count {
+                pending.retries += 1;
+                pending.last_sent = now;
+                pending.due_now = false;
+                retransmissions.push(pending.request.clone());
+            } else {
+                timed_out.push(*request_id);
+            }
+        }
+
+        for request_id in timed_out {
+            if let Some(pending) = self.pending.remove(&request_id) {
+                self.outcomes.push_back(RequestOutcome::TimedOut {
+                    request_id,
+                    request: pending.request,
+                });
+            }
+        }
+
+        retransmissions
+    }
+
+    /// Stops tracking the request with `request_id`, without producing an outcome.
+    /// Returns the cancelled request, if it was outstanding.
+    pub fn cancel(&mut self, request_id: u32) -> Option<PduBody> {
+        self.pending
+            .remove(&request_id)
+            .map(|pending| pending.request)
+    }
+
+    #[must_use]
+    pub fn is_pending(&self, request_id: u32) -> bool {
+        self.pending.contains_key(&request_id)
+    }
+
+    #[must_use]
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Takes the oldest available outcome.
+    pub fn pop_outcome(&mut self) -> Option<RequestOutcome> {
+        self.outcomes.pop_front()
+    }
+
+    /// Takes all available outcomes, oldest first.
+    pub fn drain_outcomes(&mut self) -> impl Iterator<Item = RequestOutcome> + '_ {
+        self.outcomes.drain(..)
+    }
+}
+
+/// Returns the Required Reliability Service of a SIMAN-R request body,
+/// or `None` when the body is not such a request.
+fn required_reliability_service(body: &PduBody) -> Option<RequiredReliabilityService> {
+    match body {
+        PduBody::CreateEntityR(body) => Some(body.required_reliability_service),
+        PduBody::RemoveEntityR(body) => Some(body.required_reliability_service),
+        PduBody::StartResumeR(body) => Some(body.required_reliability_service),
+        PduBody::StopFreezeR(body) => Some(body.required_reliability_service),
+        PduBody::ActionRequestR(body) => Some(body.required_reliability_service),
+        PduBody::DataQueryR(body) => Some(body.required_reliability_service),
+        PduBody::SetDataR(body) => Some(body.required_reliability_service),
+        PduBody::SetRecordR(body) => Some(body.required_reliability_service),
+        PduBody::RecordQueryR(body) => Some(body.required_reliability_service),
+        _ => None,
+    }
+}
+
+fn set_request_id(body: &mut PduBody, request_id: u32) {
+    match body {
+        PduBody::CreateEntityR(body) => body.request_id = request_id,
+        PduBody::RemoveEntityR(body) => body.request_id = request_id,
+        PduBody::StartResumeR(body) => body.request_id = request_id,
+        PduBody::StopFreezeR(body) => body.request_id = request_id,
+        PduBody::ActionRequestR(body) => body.request_id = request_id,
+        PduBody::DataQueryR(body) => body.request_id = request_id,
+        PduBody::SetDataR(body) => body.request_id = request_id,
+        PduBody::SetRecordR(body) => body.request_id = request_id,
+        PduBody::RecordQueryR(body) => body.request_id = request_id,
+        _ => (),
+    }
+}
+
+fn response_request_id(body: &PduBody) -> Option<u32> {
+    match body {
+        PduBody::AcknowledgeR(body) => Some(body.request_id),
+        PduBody::ActionResponseR(body) => Some(body.request_id),
+        PduBody::DataR(body) => Some(body.request_id),
+        PduBody::RecordR(body) => Some(body.request_id),
+        _ => None,
+    }
+}
+
+/// Determines how `response` answers `request`, or `None` when the response type does not belong to the request type.
+fn classify_response(request: &PduBody, response: &PduBody) -> Option<ResponseKind> {
+    match (request, response) {
+        (
+            PduBody::CreateEntityR(_)
+            | PduBody::RemoveEntityR(_)
+            | PduBody::StartResumeR(_)
+            | PduBody::StopFreezeR(_),
+            PduBody::AcknowledgeR(acknowledge),
+        ) => Some(match acknowledge.response_flag {
+            ResponseFlag::AbleToComply => ResponseKind::Final { success: true },
+            ResponseFlag::PendingOperatorAction => ResponseKind::Interim,
+            _ => ResponseKind::Final { success: false },
+        }),
+        (PduBody::ActionRequestR(_), PduBody::ActionResponseR(action_response)) => {
+            Some(match action_response.request_status {
+                RequestStatus::Complete | RequestStatus::RequestDone => {
+                    ResponseKind::Final { success: true }
+                }
+                RequestStatus::Pending
+                | RequestStatus::Executing
+                | RequestStatus::PartiallyComplete => ResponseKind::Interim,
+                RequestStatus::RetransmitRequestNow => ResponseKind::Retransmit { now: true },
+                RequestStatus::RetransmitRequestLater => ResponseKind::Retransmit { now: false },
+                _ => ResponseKind::Final { success: false },
+            })
+        }
+        (PduBody::DataQueryR(_) | PduBody::SetDataR(_), PduBody::DataR(_))
+        | (PduBody::SetRecordR(_) | PduBody::RecordQueryR(_), PduBody::RecordR(_)) => {
+            Some(ResponseKind::Final { success: true })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BodyRaw;
+    use crate::common::acknowledge_r::model::AcknowledgeR;
+    use crate::common::action_request_r::model::ActionRequestR;
+    use crate::common::action_response_r::model::ActionResponseR;
+    use crate::common::create_entity_r::model::CreateEntityR;
+    use crate::common::entity_state::model::EntityState;
+    use crate::common::model::{EntityId, SimulationAddress};
+    use crate::enumerations::AcknowledgeFlag;
+
+    const MANAGER: EntityId = EntityId {
+        simulation_address: SimulationAddress {
+            site_id: 1,
+            application_id: 1,
+        },
+        entity_id: 0,
+    };
+    const SIMULATION: EntityId = EntityId {
+        simulation_address: SimulationAddress {
+            site_id: 2,
+            application_id: 2,
+        },
+        entity_id: 0,
+    };
+
+    fn create_entity(service: RequiredReliabilityService) -> PduBody {
+        CreateEntityR::builder()
+            .with_origination_id(MANAGER)
+            .with_receiving_id(SIMULATION)
+            .with_required_reliability_service(service)
+            .build()
+            .into_pdu_body()
+    }
+
+    fn acknowledge(request_id: u32, response_flag: ResponseFlag) -> PduBody {
+        AcknowledgeR::builder()
+            .with_origination_id(SIMULATION)
+            .with_receiving_id(MANAGER)
+            .with_acknowledge_flag(AcknowledgeFlag::CreateEntity)
+            .with_response_flag(response_flag)
+            .with_request_id(request_id)
+            .build()
+            .into_pdu_body()
+    }
+
+    fn request_id_of(body: &PduBody) -> u32 {
+        if let PduBody::CreateEntityR(body) = body {
+            body.request_id
+        } else {
+            panic!("Not a CreateEntityR body");
+        }
+    }
+
+    #[test]
+    fn issues_unique_request_ids() {
+        let mut manager = ReliabilityManager::default();
+        let first = manager
+            .send(
+                create_entity(RequiredReliabilityService::Acknowledged),
+                Duration::ZERO,
+            )
+            .unwrap();
+        let second = manager
+            .send(
+                create_entity(RequiredReliabilityService::Acknowledged),
+                Duration::ZERO,
+            )
+            .unwrap();
+
+        assert_ne!(request_id_of(&first), request_id_of(&second));
+        assert_eq!(manager.pending_len(), 2);
+    }
+
+    #[test]
+    fn unacknowledged_requests_are_not_tracked() {
+        let mut manager = ReliabilityManager::default();
+        let sent = manager
+            .send(
+                create_entity(RequiredReliabilityService::Unacknowledged),
+                Duration::ZERO,
+            )
+            .unwrap();
+
+        assert!(!manager.is_pending(request_id_of(&sent)));
+        assert!(manager.poll(Duration::from_secs(60)).is_empty());
+        assert!(manager.pop_outcome().is_none());
+    }
+
+    #[test]
+    fn rejects_non_reliable_requests() {
+        let mut manager = ReliabilityManager::default();
+        let result = manager.send(EntityState::default().into_pdu_body(), Duration::ZERO);
+
+        assert_eq!(
+            result,
+            Err(ReliabilityError::NotAReliableRequest(PduType::EntityState))
+        );
+    }
+
+    #[test]
+    fn completes_on_acknowledge() {
+        let mut manager = ReliabilityManager::default();
+        let sent = manager
+            .send(
+                create_entity(RequiredReliabilityService::Acknowledged),
+                Duration::ZERO,
+            )
+            .unwrap();
+        let request_id = request_id_of(&sent);
+
+        assert_eq!(
+            manager.handle_response(&acknowledge(request_id, ResponseFlag::AbleToComply)),
+            Some(request_id)
+        );
+        assert!(!manager.is_pending(request_id));
+        assert!(matches!(
+            manager.pop_outcome(),
+            Some(RequestOutcome::Completed { request_id: id, .. }) if id == request_id
+        ));
+    }
+
+    #[test]
+    fn fails_when_unable_to_comply() {
+        let mut manager = ReliabilityManager::default();
+        let sent = manager
+            .send(
+                create_entity(RequiredReliabilityService::Acknowledged),
+                Duration::ZERO,
+            )
+            .unwrap();
+        let request_id = request_id_of(&sent);
+
+        manager.handle_response(&acknowledge(request_id, ResponseFlag::UnableToComply));
+        assert!(matches!(
+            manager.pop_outcome(),
+            Some(RequestOutcome::Failed { .. })
+        ));
+    }
+
+    #[test]
+    fn ignores_unrelated_responses() {
+        let mut manager = ReliabilityManager::default();
+        let sent = manager
+            .send(
+                create_entity(RequiredReliabilityService::Acknowledged),
+                Duration::ZERO,
+            )
+            .unwrap();
+        let request_id = request_id_of(&sent);
+
+        // wrong request id
+        assert!(
+            manager
+                .handle_response(&acknowledge(request_id + 1, ResponseFlag::AbleToComply))
+                .is_none()
+        );
+        // wrong response type for a CreateEntityR
+        let action_response = ActionResponseR::builder()
+            .with_receiving_id(MANAGER)
+            .with_request_id(request_id)
+            .with_request_status(RequestStatus::Complete)
+            .build()
+            .into_pdu_body();
+        assert!(manager.handle_response(&action_response).is_none());
+        assert!(manager.is_pending(request_id));
+    }
+
+    #[test]
+    fn retransmits_and_times_out() {
+        let settings = ReliabilitySettings::default()
+            .with_retry_delay(Duration::from_secs(2))
+            .with_retry_count(3);
+        let mut manager = ReliabilityManager::new(settings);
+        let sent = manager
+            .send(
+                create_entity(RequiredReliabilityService::Acknowledged),
+                Duration::ZERO,
+            )
+            .unwrap();
+        let request_id = request_id_of(&sent);
+
+        assert!(manager.poll(Duration::from_secs(1)).is_empty());
+        for retry in 1..=3 {
+            let retransmissions = manager.poll(Duration::from_secs(2 * retry));
+            assert_eq!(retransmissions, vec![sent.clone()]);
+        }
+        assert!(manager.pop_outcome().is_none());
+
+        assert!(manager.poll(Duration::from_secs(8)).is_empty());
+        assert!(!manager.is_pending(request_id));
+        assert_eq!(
+            manager.pop_outcome(),
+            Some(RequestOutcome::TimedOut {
+                request_id,
+                request: sent
+            })
+        );
+    }
+
+    #[test]
+    fn interim_response_stops_retransmission() {
+        let mut manager = ReliabilityManager::default();
+        let sent = manager
+            .send(
+                ActionRequestR::builder()
+                    .with_origination_id(MANAGER)
+                    .with_receiving_id(SIMULATION)
+                    .with_required_reliability_service(RequiredReliabilityService::Acknowledged)
+                    .build()
+                    .into_pdu_body(),
+                Duration::ZERO,
+            )
+            .unwrap();
+        let PduBody::ActionRequestR(ref request) = sent else {
+            panic!("Not an ActionRequestR body");
+        };
+        let request_id = request.request_id;
+
+        let response = |status| {
+            ActionResponseR::builder()
+                .with_origination_id(SIMULATION)
+                .with_receiving_id(MANAGER)
+                .with_request_id(request_id)
+                .with_request_status(status)
+                .build()
+                .into_pdu_body()
+        };
+
+        manager.handle_response(&response(RequestStatus::Pending));
+        assert!(manager.poll(Duration::from_secs(60)).is_empty());
+        assert!(manager.is_pending(request_id));
+
+        manager.handle_response(&response(RequestStatus::Complete));
+        assert!(matches!(
+            manager.pop_outcome(),
+            Some(RequestOutcome::Completed { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_retry_delay_parameter() {
+        let parameters = VariableParameters {
+            SM_REL_RETRY_DELAY: -1.0,
+            ..VariableParameters::default()
+        };
+        assert_eq!(
+            ReliabilitySettings::from(&parameters).retry_delay,
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn times_out_without_final_response() {
+        let settings =
+            ReliabilitySettings::default().with_final_response_timeout(Duration::from_secs(30));
+        let mut manager = ReliabilityManager::new(settings);
+        let sent = manager
+            .send(
+                ActionRequestR::builder()
+                    .with_origination_id(MANAGER)
+                    .with_receiving_id(SIMULATION)
+                    .with_required_reliability_service(RequiredReliabilityService::Acknowledged)
+                    .build()
+                    .into_pdu_body(),
+                Duration::from_secs(10),
+            )
+            .unwrap();
+        let PduBody::ActionRequestR(ref request) = sent else {
+            panic!("Not an ActionRequestR body");
+        };
+        let request_id = request.request_id;
+
+        let pending = ActionResponseR::builder()
+            .with_origination_id(SIMULATION)
+            .with_receiving_id(MANAGER)
+            .with_request_id(request_id)
+            .with_request_status(RequestStatus::Pending)
+            .build()
+            .into_pdu_body();
+        manager.handle_response(&pending);
+
+        assert!(manager.poll(Duration::from_secs(39)).is_empty());
+        assert!(manager.is_pending(request_id));
+        assert!(manager.pop_outcome().is_none());
+
+        assert!(manager.poll(Duration::from_secs(40)).is_empty());
+        assert!(!manager.is_pending(request_id));
+        assert_eq!(
+            manager.pop_outcome(),
+            Some(RequestOutcome::TimedOut {
+                request_id,
+                request: sent
+            })
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! Responder side of Simulation Management (SIMAN) interactions (5.6.5, 7.5).
+//!
+//! An application implements the [`SimulationManagementHandler`] callbacks for the requests it supports.
+//! The [`SimulationManagementResponder`] checks whether incoming requests are addressed to the application,
+//! invokes the callbacks, and builds the correlated `Acknowledge` and `Data` replies:
+//! the reply carries the `request_id` of the request and is addressed to the originator of the request.
+//!
+//! A `DataQuery` with a non-zero time interval results in periodic `Data` replies,
+//! which are produced by [`SimulationManagementResponder::poll`].
+use crate::common::BodyRaw;
+use crate::common::acknowledge::model::Acknowledge;
+use crate::common::create_entity::model::CreateEntity;
+use crate::common::data::model::Data;
+use crate::common::data_query::model::DataQuery;
+use crate::common::model::{DatumSpecification, EntityId, PduBody};
+use crate::common::remove_entity::model::RemoveEntity;
+use crate::common::set_data::model::SetData;
+use crate::common::start_resume::model::StartResume;
+use crate::common::stop_freeze::model::StopFreeze;
+use crate::enumerations::{AcknowledgeFlag, ResponseFlag};
+use crate::fixed_parameters::{ALL_APPLIC, ALL_ENTITIES, ALL_SITES};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// Callbacks for the Simulation Management requests a simulation application responds to.
+///
+/// The default implementations refuse the action requests (`ResponseFlag::UnableToComply`)
+/// and reply with empty datum records to data requests.
+pub trait SimulationManagementHandler {
+    /// Called for a `CreateEntity` request addressed to this application.
+    fn on_create_entity(&mut self, _request: &CreateEntity) -> ResponseFlag {
+        ResponseFlag::UnableToComply
+    }
+
+    /// Called for a `RemoveEntity` request addressed to this application.
+    fn on_remove_entity(&mut self, _request: &RemoveEntity) -> ResponseFlag {
+        ResponseFlag::UnableToComply
+    }
+
+    /// Called for a `StartResume` request addressed to this application.
+    fn on_start_resume(&mut self, _request: &StartResume) -> ResponseFlag {
+        ResponseFlag::UnableToComply
+    }
+
+    /// Called for a `StopFreeze` request addressed to this application.
+    fn on_stop_freeze(&mut self, _request: &StopFreeze) -> ResponseFlag {
+        ResponseFlag::UnableToComply
+    }
+
+    /// Called for a `DataQuery` request addressed to this application,
+    /// and each time a periodic reply to the query is due.
+    /// Returns the values of the queried datums.
+    fn on_data_query(&mut self, _request: &DataQuery) -> DatumSpecification {
+        DatumSpecification::default()
+    }
+
+    /// Called for a `SetData` request addressed to this application.
+    /// Returns the datums as they are set after processing the request.
+    fn on_set_data(&mut self, _request: &SetData) -> DatumSpecification {
+        DatumSpecification::default()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct PeriodicQuery {
+    query: DataQuery,
+    interval: Duration,
+    last_sent: Duration,
+}
+
+/// Dispatches Simulation Management requests to a [`SimulationManagementHandler`] and builds the replies.
+///
+/// Time is passed in explicitly by the caller as a `Duration` since an arbitrary (but fixed) epoch.
+#[derive(Clone, Debug)]
+pub struct SimulationManagementResponder {
+    own_id: EntityId,
+    periodic_queries: BTreeMap<(EntityId, u32), PeriodicQuery>,
+}
+
+impl SimulationManagementResponder {
+    /// Creates a responder for the simulation application (or entity) identified by `own_id`.
+    #[must_use]
+    pub fn new(own_id: EntityId) -> Self {
+        Self {
+            own_id,
+            periodic_queries: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn own_id(&self) -> &EntityId {
+        &self.own_id
+    }
+
+    /// Checks whether `receiving_id` addresses this application, taking the
+    /// `ALL_SITES`, `ALL_APPLIC` and `ALL_ENTITIES` wildcards into account.
+    #[must_use]
+    pub fn is_addressed_to(&self, receiving_id: EntityId) -> bool {
+        let site = receiving_id.simulation_address.site_id;
+        let application = receiving_id.simulation_address.application_id;
+        let entity = receiving_id.entity_id;
+
+        (site == self.own_id.simulation_address.site_id || u32::from(site) == ALL_SITES)
+            && (application == self.own_id.simulation_address.application_id
+                || u32::from(application) == ALL_APPLIC)
+            && (entity == self.own_id.entity_id || u32::from(entity) == ALL_ENTITIES)
+    }
+
+    /// Handles an incoming PDU body at time `now`.
+    ///
+    /// Returns the reply to be sent, or `None` when `request` is not a Simulation Management request
+    /// addressed to this application.
+    pub fn handle<H: SimulationManagementHandler>(
+        &mut self,
+        handler: &mut H,
+        request: &PduBody,
+        now: Duration,
+    ) -> Option<PduBody> {
+        match request {
+            PduBody::CreateEntity(body) if self.is_addressed_to(body.receiving_id) => {
+                let response_flag = handler.on_create_entity(body);
+                Some(self.acknowledge(
+                    body.originating_id,
+                    body.request_id,
+                    AcknowledgeFlag::CreateEntity,
+                    response_flag,
+                ))
+            }
+            PduBody::RemoveEntity(body) if self.is_addressed_to(body.receiving_id) => {
+                let response_flag = handler.on_remove_entity(body);
+                Some(self.acknowledge(
+                    body.originating_id,
+                    body.request_id,
+                    AcknowledgeFlag::RemoveEntity,
+                    response_flag,
+                ))
+            }
+            PduBody::StartResume(body) if self.is_addressed_to(body.receiving_id) => {
+                let response_flag = handler.on_start_resume(body);
+                Some(self.acknowledge(
+                    body.originating_id,
+                    body.request_id,
+                    AcknowledgeFlag::StartResume,
+                    response_flag,
+                ))
+            }
+            PduBody::StopFreeze(body) if self.is_addressed_to(body.receiving_id) => {
+                let response_flag = handler.on_stop_freeze(body);
+                Some(self.acknowledge(
+                    body.originating_id,
+                    body.request_id,
+                    AcknowledgeFlag::StopFreeze,
+                    response_flag,
+                ))
+            }
+            PduBody::DataQuery(body) if self.is_addressed_to(body.receiving_id) => {
+                let key = (body.originating_id, body.request_id);
+                let interval = body.time_interval.to_duration();
+                if interval.is_zero() {
+                    self.periodic_queries.remove(&key);
+                } else {
+                    self.periodic_queries.insert(
+                        key,
+                        PeriodicQuery {
+                            query: body.clone(),
+                            interval,
+                            last_sent: now,
+                        },
+                    );
+                }
+                let datums = handler.on_data_query(body);
+                Some(self.data(body.originating_id, body.request_id, datums))
+            }
+            PduBody::SetData(body) if self.is_addressed_to(body.receiving_id) => {
+                let datums = handler.on_set_data(body);
+                Some(self.data(body.originating_id, body.request_id, datums))
+            }
+            _ => None,
+        }
+    }
+
+    /// Produces the periodic `Data` replies that are due at time `now`.
+    pub fn poll<H: SimulationManagementHandler>(
+        &mut self,
+        handler: &mut H,
+        now: Duration,
+    ) -> Vec<PduBody> {
+        let own_id = self.own_id;
+        self.periodic_queries
+            .values_mut()
+            .filter(|periodic| now.saturating_sub(periodic.last_sent) >= periodic.interval)
+            .map(|periodic| {
+                periodic.last_sent = now;
+                let datums = handler.on_data_query(&periodic.query);
+                build_data(
+                    own_id,
+                    periodic.query.originating_id,
+                    periodic.query.request_id,
+                    datums,
+                )
+            })
+            .collect()
+    }
+
+    /// Stops the periodic replies for the `DataQuery` with `request_id` from `requester`.
+    /// Returns `true` when such a periodic query was active.
+    pub fn cancel_periodic_query(&mut self, requester: EntityId, request_id: u32) -> bool {
+        self.periodic_queries
+            .remove(&(requester, request_id))
+            .is_some()
+    }
+
+    /// The number of `DataQuery` requests that are being replied to periodically.
+    #[must_use]
+    pub fn periodic_queries_len(&self) -> usize {
+        self.periodic_queries.len()
+    }
+
+    fn acknowledge(
+        &self,
+        requester: EntityId,
+        request_id: u32,
+        acknowledge_flag: AcknowledgeFlag,
+        response_flag: ResponseFlag,
+    ) -> PduBody {
+        Acknowledge::builder()
+            .with_origination_id(self.own_id)
+            .with_receiving_id(requester)
+            .with_acknowledge_flag(acknowledge_flag)
+            .with_response_flag(response_flag)
+            .with_request_id(request_id)
+            .build()
+            .into_pdu_body()
+    }
+
+    fn data(&self, requester: EntityId, request_id: u32, datums: DatumSpecification) -> PduBody {
+        build_data(self.own_id, requester, request_id, datums)
+    }
+}
+
+fn build_data(
+    own_id: EntityId,
+    requester: EntityId,
+    request_id: u32,
+    datums: DatumSpecification,
+) -> PduBody {
+    Data::builder()
+        .with_origination_id(own_id)
+        .with_receiving_id(requester)
+        .with_request_id(request_id)
+        .with_fixed_datums(datums.fixed_datum_records)
+        .with_variable_datums(datums.variable_datum_records)
+        .build()
+        .into_pdu_body()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::model::{FixedDatum, SimulationAddress, TimeUnits, Timestamp};
+    use crate::enumerations::VariableRecordType;
+
+    const MANAGER: EntityId = EntityId {
+        simulation_address: SimulationAddress {
+            site_id: 1,
+            application_id: 1,
+        },
+        entity_id: 0,
+    };
+    const SIMULATION: EntityId = EntityId {
+        simulation_address: SimulationAddress {
+            site_id: 2,
+            application_id: 2,
+        },
+        entity_id: 0,
+    };
+
+    #[derive(Default)]
+    struct TestSimulation {
+        running: bool,
+        queries: usize,
+    }
+
+    impl SimulationManagementHandler for TestSimulation {
+        fn on_start_resume(&mut self, _request: &StartResume) -> ResponseFlag {
+            self.running = true;
+            ResponseFlag::AbleToComply
+        }
+
+        fn on_data_query(&mut self, _request: &DataQuery) -> DatumSpecification {
+            self.queries += 1;
+            DatumSpecification::new(
+                vec![FixedDatum::new(
+                    VariableRecordType::from(1),
+                    self.queries as u32,
+                )],
+                vec![],
+            )
+        }
+    }
+
+    fn data_query(time_interval: Duration) -> PduBody {
+        DataQuery::builder()
+            .with_origination_id(MANAGER)
+            .with_receiving_id(SIMULATION)
+            .with_request_id(42)
+            .with_time_interval(Timestamp::Relative(
+                TimeUnits::from_duration(time_interval).unwrap(),
+            ))
+            .build()
+            .into_pdu_body()
+    }
+
+    #[test]
+    fn acknowledges_start_resume() {
+        let mut simulation = TestSimulation::default();
+        let mut responder = SimulationManagementResponder::new(SIMULATION);
+        let request = StartResume::builder()
+            .with_origination_id(MANAGER)
+            .with_receiving_id(SIMULATION)
+            .with_request_id(7)
+            .build()
+            .into_pdu_body();
+
+        let reply = responder.handle(&mut simulation, &request, Duration::ZERO);
+
+        assert!(simulation.running);
+        assert_eq!(
+            reply,
+            Some(
+                Acknowledge::builder()
+                    .with_origination_id(SIMULATION)
+                    .with_receiving_id(MANAGER)
+                    .with_acknowledge_flag(AcknowledgeFlag::StartResume)
+                    .with_response_flag(ResponseFlag::AbleToComply)
+                    .with_request_id(7)
+                    .build()
+                    .into_pdu_body()
+            )
+        );
+    }
+
+    #[test]
+    fn refuses_unsupported_requests() {
+        let mut simulation = TestSimulation::default();
+        let mut responder = SimulationManagementResponder::new(SIMULATION);
+        let request = CreateEntity::builder()
+            .with_origination_id(MANAGER)
+            .with_receiving_id(SIMULATION)
+            .build()
+            .into_pdu_body();
+
+        let Some(PduBody::Acknowledge(reply)) =
+            responder.handle(&mut simulation, &request, Duration::ZERO)
+        else {
+            panic!("Expected an Acknowledge reply");
+        };
+        assert_eq!(reply.acknowledge_flag, AcknowledgeFlag::CreateEntity);
+        assert_eq!(reply.response_flag, ResponseFlag::UnableToComply);
+    }
+
+    #[test]
+    fn ignores_requests_for_others() {
+        let mut simulation = TestSimulation::default();
+        let mut responder = SimulationManagementResponder::new(SIMULATION);
+        let request = StartResume::builder()
+            .with_origination_id(SIMULATION)
+            .with_receiving_id(MANAGER)
+            .build()
+            .into_pdu_body();
+
+        assert!(
+            responder
+                .handle(&mut simulation, &request, Duration::ZERO)
+                .is_none()
+        );
+        assert!(!simulation.running);
+    }
+
+    #[test]
+    fn accepts_wildcard_addresses() {
+        let responder = SimulationManagementResponder::new(SIMULATION);
+        let all_applications = EntityId::new(2, ALL_APPLIC as u16, ALL_ENTITIES as u16);
+        let everyone = EntityId::new(ALL_SITES as u16, ALL_APPLIC as u16, ALL_ENTITIES as u16);
+        let other_site = EntityId::new(3, ALL_APPLIC as u16, ALL_ENTITIES as u16);
+
+        assert!(responder.is_addressed_to(all_applications));
+        assert!(responder.is_addressed_to(everyone));
+        assert!(!responder.is_addressed_to(other_site));
+    }
+
+    #[test]
+    fn single_data_reply_for_zero_interval() {
+        let mut simulation = TestSimulation::default();
+        let mut responder = SimulationManagementResponder::new(SIMULATION);
+
+        let Some(PduBody::Data(reply)) =
+            responder.handle(&mut simulation, &data_query(Duration::ZERO), Duration::ZERO)
+        else {
+            panic!("Expected a Data reply");
+        };
+        assert_eq!(reply.originating_id, SIMULATION);
+        assert_eq!(reply.receiving_id, MANAGER);
+        assert_eq!(reply.request_id, 42);
+        assert_eq!(reply.fixed_datum_records.len(), 1);
+
+        assert_eq!(responder.periodic_queries_len(), 0);
+        assert!(
+            responder
+                .poll(&mut simulation, Duration::from_secs(60))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn periodic_data_replies() {
+        let mut simulation = TestSimulation::default();
+        let mut responder = SimulationManagementResponder::new(SIMULATION);
+
+        let query = data_query(Duration::from_secs(5));
+        assert!(
+            responder
+                .handle(&mut simulation, &query, Duration::ZERO)
+                .is_some()
+        );
+        assert_eq!(responder.periodic_queries_len(), 1);
+
+        assert!(
+            responder
+                .poll(&mut simulation, Duration::from_secs(4))
+                .is_empty()
+        );
+        let replies = responder.poll(&mut simulation, Duration::from_secs(5));
+        assert_eq!(replies.len(), 1);
+        let PduBody::Data(reply) = &replies[0] else {
+            panic!("Expected a Data reply");
+        };
+        assert_eq!(reply.request_id, 42);
+        assert_eq!(reply.fixed_datum_records[0].datum_value, 2);
+
+        assert!(responder.cancel_periodic_query(MANAGER, 42));
+        assert!(
+            responder
+                .poll(&mut simulation, Duration::from_secs(10))
+                .is_empty()
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use crate::common::model::EntityId;
+use crate::common::model::{EntityId, EntityType};
+use crate::enumerations::{EntityKind, PlatformDomain};
 use crate::{NO_APPLIC, NO_ENTITY, NO_SITE};
 
 #[allow(non_snake_case)]
@@ -112,6 +113,32 @@ impl VariableParameters {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Returns the Entity State PDU heartbeat interval (in seconds) for entities of `entity_type`,
+    /// being the `HBT_ESPDU_KIND_*` or `HBT_ESPDU_PLATFORM_*` parameter matching the kind and domain.
+    /// Platforms of other domains, and entities of kind Other, use `HBT_ESPDU_PLATFORM_LAND`.
+    #[must_use]
+    #[allow(clippy::match_same_arms)]
+    pub fn hbt_espdu(&self, entity_type: &EntityType) -> f32 {
+        match (entity_type.kind, entity_type.domain) {
+            (EntityKind::Platform, PlatformDomain::Air) => self.HBT_ESPDU_PLATFORM_AIR,
+            (EntityKind::Platform, PlatformDomain::Surface) => self.HBT_ESPDU_PLATFORM_SURFACE,
+            (EntityKind::Platform, PlatformDomain::Subsurface) => {
+                self.HBT_ESPDU_PLATFORM_SUBSURFACE
+            }
+            (EntityKind::Platform, PlatformDomain::Space) => self.HBT_ESPDU_PLATFORM_SPACE,
+            (EntityKind::Platform, _) => self.HBT_ESPDU_PLATFORM_LAND,
+            (EntityKind::Munition, _) => self.HBT_ESPDU_KIND_MUNITION,
+            (EntityKind::LifeForm, _) => self.HBT_ESPDU_KIND_LIFE_FORM,
+            (EntityKind::Environmental, _) => self.HBT_ESPDU_KIND_ENVIRONMENTAL,
+            (EntityKind::CulturalFeature, _) => self.HBT_ESPDU_KIND_CULTURAL_FEATURE,
+            (EntityKind::Supply, _) => self.HBT_ESPDU_KIND_SUPPLY,
+            (EntityKind::Radio, _) => self.HBT_ESPDU_KIND_RADIO,
+            (EntityKind::Expendable, _) => self.HBT_ESPDU_KIND_EXPENDABLE,
+            (EntityKind::SensorEmitter, _) => self.HBT_ESPDU_KIND_SENSOR,
+            (_, _) => self.HBT_ESPDU_PLATFORM_LAND,
+        }
+    }
 }
 
 impl Default for VariableParameters {
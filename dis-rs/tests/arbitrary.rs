@@ -0,0 +1,36 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use bytes::BytesMut;
+use dis_rs::enumerations::ProtocolVersion;
+use dis_rs::model::Pdu;
+use nom::AsBytes;
+use proptest::prelude::*;
+
+fn serialize_and_parse(pdu: &Pdu) -> Result<Pdu, TestCaseError> {
+    let mut buf = BytesMut::with_capacity(pdu.pdu_length() as usize);
+    pdu.serialize(&mut buf)
+        .map_err(|err| TestCaseError::fail(format!("{err:?}")))?;
+    let mut pdus =
+        dis_rs::parse(buf.as_bytes()).map_err(|err| TestCaseError::fail(format!("{err:?}")))?;
+
+    prop_assert_eq!(pdus.len(), 1);
+    Ok(pdus.remove(0))
+}
+
+proptest! {
+    #[test]
+    fn pdu_round_trip(data in proptest::collection::vec(any::<u8>(), 0..512)) {
+        let mut u = Unstructured::new(&data);
+        let pdu = Pdu::arbitrary(&mut u).unwrap();
+
+        for version in [ProtocolVersion::IEEE1278_1A1998, ProtocolVersion::IEEE1278_12012] {
+            // Not all PDU types exist in both versions
+            if let Ok(converted) = pdu.convert_to(version) {
+                let parsed = serialize_and_parse(&converted.pdu)?;
+                // Compare the Debug output, as floating point fields may be NaN
+                prop_assert_eq!(format!("{parsed:?}"), format!("{:?}", converted.pdu));
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use core::str::FromStr;
+use dis_rs::enumerations::{ForceId, LandPlatformCapabilities, PduType};
+
+#[test]
+fn enum_description() {
+    assert_eq!(ForceId::Friendly.description(), "Friendly");
+    assert_eq!(PduType::EntityState.description(), "Entity State");
+    assert_eq!(ForceId::Unspecified(200).description(), "Unspecified");
+}
+
+#[test]
+fn enum_from_str() {
+    assert_eq!(ForceId::from_str("Friendly"), Ok(ForceId::Friendly));
+    assert_eq!(PduType::from_str("EntityState"), Ok(PduType::EntityState));
+    assert_eq!(PduType::from_str("Entity State"), Ok(PduType::EntityState));
+    assert_eq!(
+        ForceId::from_str("Unspecified (200)"),
+        Ok(ForceId::Unspecified(200))
+    );
+    assert!(ForceId::from_str("Blue").is_err());
+    assert!(ForceId::from_str("Unspecified (1)").is_err());
+}
+
+#[test]
+fn enum_display_from_str_roundtrip() {
+    let force = ForceId::Opposing;
+    assert_eq!(ForceId::from_str(&force.to_string()), Ok(force));
+
+    let pdu_type = PduType::EntityState;
+    assert_eq!(PduType::from_str(&pdu_type.to_string()), Ok(pdu_type));
+
+    let force = ForceId::from(200);
+    assert_eq!(ForceId::from_str(&force.to_string()), Ok(force));
+}
+
+#[test]
+fn bitfield_display_from_str_roundtrip() {
+    let capabilities = LandPlatformCapabilities {
+        ammunition_supply: true,
+        repair: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        LandPlatformCapabilities::from_str(&capabilities.to_string()),
+        Ok(capabilities)
+    );
+    assert_eq!(
+        LandPlatformCapabilities::from_str("ammunition_supply | repair"),
+        Ok(capabilities)
+    );
+    assert_eq!(LandPlatformCapabilities::default().to_string(), "None");
+    assert_eq!(
+        LandPlatformCapabilities::from_str("None"),
+        Ok(LandPlatformCapabilities::default())
+    );
+    assert!(LandPlatformCapabilities::from_str("flying").is_err());
+}
@@ -7,6 +7,7 @@
 use crate::node_data_impl;
 use crate::runtime::{Command, Event};
 use bytes::{Bytes, BytesMut};
+use dis_rs::application::{SocketError, UdpEndpoint};
 use serde_derive::{Deserialize, Serialize};
 use std::any::Any;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
@@ -192,6 +193,16 @@ pub(crate) enum UdpMode {
     MultiCast,
 }
 
+impl From<UdpMode> for dis_rs::application::UdpMode {
+    fn from(value: UdpMode) -> Self {
+        match value {
+            UdpMode::UniCast => Self::UniCast,
+            UdpMode::BroadCast => Self::BroadCast,
+            UdpMode::MultiCast => Self::MultiCast,
+        }
+    }
+}
+
 impl TryFrom<&str> for UdpMode {
     type Error = UdpNodeError;
 
@@ -445,101 +456,33 @@ pub enum UdpNodeError {
 
 impl NodeError for UdpNodeError {}
 
-/// Creates an UDP socket based on the settings contained in `endpoint`.
-/// The created `tokio::net::udp::UdpSocket` is returned wrapped in an `Arc`
-/// so that it can be used by multiple tasks (i.e., for both writing and sending).
-#[allow(clippy::too_many_lines)]
+/// Creates an UDP socket based on the settings contained in `endpoint`,
+/// using `dis_rs::application::create_udp_socket`.
 fn create_udp_socket(endpoint: &UdpNodeData) -> Result<UdpSocket, UdpNodeError> {
-    use socket2::{Domain, Protocol, Socket, Type};
-
-    // Create socket using socket2 crate, to be able to set required socket options (SO_REUSEADDR, SO_REUSEPORT, ...)
-    let is_ipv4 = endpoint.address.is_ipv4();
-    let socket_domain = if is_ipv4 { Domain::IPV4 } else { Domain::IPV6 };
-    let socket_type = Type::DGRAM;
-    let socket_protocol = Protocol::UDP;
-    let socket = Socket::new(socket_domain, socket_type, Some(socket_protocol))
-        .map_err(|_err| UdpNodeError::CreateSocket(endpoint.base.instance_id))?;
-
-    socket
-        .set_reuse_address(true)
-        .map_err(|_| UdpNodeError::SetReuseAddress(endpoint.base.instance_id, endpoint.address))?;
-
-    #[cfg(all(
-        target_family = "unix",
-        not(any(target_os = "solaris", target_os = "illumos"))
-    ))]
-    socket
-        .set_reuse_port(true)
-        .map_err(|_| UdpNodeError::SetReusePort(endpoint.base.instance_id, endpoint.address))?;
-    socket
-        .set_nonblocking(true)
-        .map_err(|_| UdpNodeError::SetNonblocking(endpoint.base.instance_id, endpoint.address))?;
-
-    match (is_ipv4, endpoint.mode) {
-        (true, UdpMode::UniCast) => {
-            socket.bind(&endpoint.interface.into()).map_err(|_| {
-                UdpNodeError::BindToAddress(endpoint.base.instance_id, endpoint.address)
-            })?;
-        }
-        (true, UdpMode::BroadCast) => {
-            socket.set_broadcast(true).map_err(|_| {
-                UdpNodeError::SetBroadcast(endpoint.base.instance_id, endpoint.interface)
-            })?;
-            socket
-                .set_ttl_v4(endpoint.ttl)
-                .map_err(|_| UdpNodeError::SetTtl(endpoint.base.instance_id, endpoint.interface))?;
-            socket.bind(&endpoint.interface.into()).map_err(|_| {
-                UdpNodeError::BindToAddress(endpoint.base.instance_id, endpoint.interface)
-            })?;
+    let instance_id = endpoint.base.instance_id;
+    // multicast sockets are not bound, only joined to the group
+    let udp_endpoint = UdpEndpoint::new(endpoint.mode.into(), endpoint.interface, endpoint.address)
+        .with_ttl(endpoint.ttl)
+        .with_bind_multicast(false);
+
+    dis_rs::application::create_udp_socket(&udp_endpoint).map_err(|err| match err {
+        SocketError::CreateSocket => UdpNodeError::CreateSocket(instance_id),
+        SocketError::SetReuseAddress(address) => {
+            UdpNodeError::SetReuseAddress(instance_id, address)
         }
-        (true, UdpMode::MultiCast) => {
-            if let IpAddr::V4(ip_address_v4) = endpoint.address.ip()
-                && let IpAddr::V4(interface_v4) = endpoint.interface.ip()
-            {
-                socket
-                    .join_multicast_v4(&ip_address_v4, &interface_v4)
-                    .map_err(|_| {
-                        UdpNodeError::JoinMulticastV4(
-                            endpoint.base.instance_id,
-                            ip_address_v4,
-                            interface_v4,
-                        )
-                    })?
-            }
+        SocketError::SetReusePort(address) => UdpNodeError::SetReusePort(instance_id, address),
+        SocketError::SetNonblocking(address) => UdpNodeError::SetNonblocking(instance_id, address),
+        SocketError::SetBroadcast(address) => UdpNodeError::SetBroadcast(instance_id, address),
+        SocketError::SetTtl(address) => UdpNodeError::SetTtl(instance_id, address),
+        SocketError::JoinMulticastV4(group, interface) => {
+            UdpNodeError::JoinMulticastV4(instance_id, group, interface)
         }
-        (false, UdpMode::UniCast) => socket.bind(&endpoint.interface.into()).map_err(|_| {
-            UdpNodeError::BindToAddress(endpoint.base.instance_id, endpoint.address)
-        })?,
-        (false, UdpMode::BroadCast) => {
-            socket.set_broadcast(true).map_err(|_| {
-                UdpNodeError::SetBroadcast(endpoint.base.instance_id, endpoint.interface)
-            })?;
-            socket.bind(&endpoint.interface.into()).map_err(|_| {
-                UdpNodeError::BindToAddress(endpoint.base.instance_id, endpoint.interface)
-            })?;
+        SocketError::JoinMulticastV6(group, interface) => {
+            UdpNodeError::JoinMulticastV6(instance_id, group, interface)
         }
-        (false, UdpMode::MultiCast) => {
-            if let IpAddr::V6(ip_address_v6) = endpoint.address.ip()
-                && let IpAddr::V6(interface_v6) = endpoint.interface.ip()
-            {
-                // TODO how does IPv6 work with u32 interface numbers - pick 'any' for now.
-                socket.join_multicast_v6(&ip_address_v6, 0).map_err(|_| {
-                    UdpNodeError::JoinMulticastV6(
-                        endpoint.base.instance_id,
-                        ip_address_v6,
-                        interface_v6,
-                    )
-                })?;
-            }
-        }
-    }
-
-    // Convert socket2::Socket to tokio::net::UdpSocket via std::net::UdpSocket
-    let socket = std::net::UdpSocket::from(socket);
-    let socket = UdpSocket::try_from(socket)
-        .map_err(|_| UdpNodeError::ConvertToAsync(endpoint.base.instance_id))?;
-
-    Ok(socket)
+        SocketError::BindToAddress(address) => UdpNodeError::BindToAddress(instance_id, address),
+        SocketError::ConvertToAsync => UdpNodeError::ConvertToAsync(instance_id),
+    })
 }
 
 #[derive(Debug, Serialize, Deserialize)]